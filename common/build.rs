@@ -0,0 +1,59 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+fn main() {
+    // identifies the common sources, so `epilogue!(codegen_cache)` is invalidated when they change
+    // even if the version is unchanged (e.g. path or git dependencies).
+    let manifest_dir = std::path::PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let mut hasher = DefaultHasher::new();
+    std::env::var("CARGO_PKG_VERSION")
+        .unwrap()
+        .hash(&mut hasher);
+    hash_dir(&manifest_dir, &manifest_dir.join("src"), &mut hasher);
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=build.rs");
+    println!(
+        "cargo:rustc-env=LOCKJAW_COMMON_BUILD_ID={:016x}",
+        hasher.finish()
+    );
+}
+
+/// Hashes the path and content of every file under `dir`, in a stable order.
+fn hash_dir(base: &Path, dir: &Path, hasher: &mut DefaultHasher) {
+    let mut entries: Vec<_> = std::fs::read_dir(dir)
+        .expect("cannot read common sources")
+        .map(|entry| entry.expect("cannot read common sources").path())
+        .collect();
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            hash_dir(base, &path, hasher);
+        } else {
+            path.strip_prefix(base)
+                .unwrap()
+                .to_string_lossy()
+                .replace('\\', "/")
+                .hash(hasher);
+            std::fs::read(&path)
+                .expect("cannot read common sources")
+                .hash(hasher);
+        }
+    }
+}
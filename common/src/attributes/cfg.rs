@@ -18,7 +18,7 @@ use anyhow::Result;
 use proc_macro2::Ident;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
-use syn::{parenthesized, Lit, MetaList, Token};
+use syn::{parenthesized, Lit, Meta, MetaList, Token};
 
 mod kw {
     syn::custom_keyword!(all);
@@ -29,8 +29,40 @@ pub fn handle_cfg(meta_list: &MetaList) -> Result<ConfigurationPredicate> {
     Ok(syn::parse2(meta_list.tokens.clone())?)
 }
 
+pub fn handle_cfg_attr(meta_list: &MetaList) -> Result<CfgAttr> {
+    Ok(syn::parse2(meta_list.tokens.clone())?)
+}
+
+/// `#[cfg_attr(predicate, attr1, attr2...)]`
+#[derive(Debug)]
+#[allow(unused)]
+pub struct CfgAttr {
+    pub predicate: ConfigurationPredicate,
+    comma_token: Token![,],
+    pub attrs: Punctuated<Meta, Token![,]>,
+}
+
+impl Parse for CfgAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(CfgAttr {
+            predicate: input.parse()?,
+            comma_token: input.parse()?,
+            attrs: Punctuated::parse_terminated(input)?,
+        })
+    }
+}
+
+/// The configuration `#[cfg]` predicates are evaluated against.
+#[derive(Debug, Clone, Copy)]
+pub struct Cfg<'a> {
+    pub test: bool,
+    /// Features enabled on the crate being parsed, resolved by `cargo metadata`. `None` reads the
+    /// features of the crate running the build script from the environment instead.
+    pub features: Option<&'a [String]>,
+}
+
 pub trait CfgEval {
-    fn eval(&self, cfg_test: bool) -> bool;
+    fn eval(&self, cfg: &Cfg) -> bool;
 }
 
 #[derive(Debug)]
@@ -56,12 +88,12 @@ impl Parse for ConfigurationPredicate {
 }
 
 impl CfgEval for ConfigurationPredicate {
-    fn eval(&self, cfg_test: bool) -> bool {
+    fn eval(&self, cfg: &Cfg) -> bool {
         match self {
-            ConfigurationPredicate::Option(option) => option.eval(cfg_test),
-            ConfigurationPredicate::All(all) => all.eval(cfg_test),
-            ConfigurationPredicate::Any(any) => any.eval(cfg_test),
-            ConfigurationPredicate::Not(not) => not.eval(cfg_test),
+            ConfigurationPredicate::Option(option) => option.eval(cfg),
+            ConfigurationPredicate::All(all) => all.eval(cfg),
+            ConfigurationPredicate::Any(any) => any.eval(cfg),
+            ConfigurationPredicate::Not(not) => not.eval(cfg),
         }
     }
 }
@@ -96,9 +128,24 @@ impl Parse for ConfigurationOption {
 }
 
 impl CfgEval for ConfigurationOption {
-    fn eval(&self, cfg_test: bool) -> bool {
+    fn eval(&self, cfg: &Cfg) -> bool {
         if self.identifier.to_string() == "test" {
-            return cfg_test;
+            return cfg.test;
+        }
+
+        if self.identifier.to_string() == "feature" {
+            let Some(Lit::Str(lit_str)) = &self.string else {
+                return false;
+            };
+            return if let Some(features) = cfg.features {
+                features.contains(&lit_str.value())
+            } else {
+                std::env::var(format!(
+                    "CARGO_FEATURE_{}",
+                    lit_str.value().to_uppercase().replace("-", "_")
+                ))
+                .is_ok()
+            };
         }
 
         if let Some(Lit::Str(lit_str)) = &self.string {
@@ -106,7 +153,7 @@ impl CfgEval for ConfigurationOption {
                 "CARGO_CFG_{}",
                 self.identifier.to_string().to_uppercase().replace("-", "_")
             ))
-            .unwrap()
+            .unwrap_or_default()
             .split(",")
             .any(|s| s == lit_str.value())
         } else {
@@ -140,8 +187,8 @@ impl Parse for ConfigurationAll {
     }
 }
 impl CfgEval for ConfigurationAll {
-    fn eval(&self, cfg_test: bool) -> bool {
-        self.list.iter().all(|predicate| predicate.eval(cfg_test))
+    fn eval(&self, cfg: &Cfg) -> bool {
+        self.list.iter().all(|predicate| predicate.eval(cfg))
     }
 }
 #[derive(Debug)]
@@ -166,8 +213,8 @@ impl Parse for ConfigurationAny {
 }
 
 impl CfgEval for ConfigurationAny {
-    fn eval(&self, cfg_test: bool) -> bool {
-        self.list.iter().any(|predicate| predicate.eval(cfg_test))
+    fn eval(&self, cfg: &Cfg) -> bool {
+        self.list.iter().any(|predicate| predicate.eval(cfg))
     }
 }
 #[derive(Debug)]
@@ -191,7 +238,7 @@ impl Parse for ConfigurationNot {
     }
 }
 impl CfgEval for ConfigurationNot {
-    fn eval(&self, cfg_test: bool) -> bool {
-        !self.predicate.eval(cfg_test)
+    fn eval(&self, cfg: &Cfg) -> bool {
+        !self.predicate.eval(cfg)
     }
 }
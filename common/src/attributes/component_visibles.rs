@@ -21,7 +21,7 @@ use crate::type_data::TypeData;
 use crate::manifest_parser::Mod;
 use anyhow::{bail, Result};
 use proc_macro2::TokenStream;
-use syn::{ItemStruct, ItemTrait};
+use syn::{ItemEnum, ItemStruct, ItemTrait};
 
 pub fn handle_component_visible_attribute(
     _attr: TokenStream,
@@ -32,6 +32,10 @@ pub fn handle_component_visible_attribute(
         return handle_item_struct(item_struct, _mod);
     };
 
+    if let Ok(item_enum) = syn::parse2::<syn::ItemEnum>(input.clone()) {
+        return handle_item_enum(item_enum, _mod);
+    };
+
     if let Ok(item_trait) = syn::parse2::<syn::ItemTrait>(input.clone()) {
         return handle_item_trait(item_trait, _mod);
     };
@@ -39,7 +43,14 @@ pub fn handle_component_visible_attribute(
 }
 
 fn handle_item_struct(item_struct: ItemStruct, mod_: &Mod) -> Result<Manifest> {
-    let original_ident = item_struct.ident.clone();
+    handle_item_type(&item_struct.ident, mod_)
+}
+
+fn handle_item_enum(item_enum: ItemEnum, mod_: &Mod) -> Result<Manifest> {
+    handle_item_type(&item_enum.ident, mod_)
+}
+
+fn handle_item_type(original_ident: &syn::Ident, mod_: &Mod) -> Result<Manifest> {
     let exported_ident = format!("lockjaw_export_type_{}", original_ident);
 
     let type_ = type_data::from_local(&original_ident.to_string(), mod_)?;
@@ -20,8 +20,8 @@ use std::ops::Deref;
 
 use crate::environment::current_package;
 use crate::manifest::{
-    BuilderModules, Component, ComponentType, Dependency, ExpandedVisibility, Manifest, Module,
-    TypeRoot,
+    BuilderModules, Component, ComponentFragment, ComponentType, Dependency, ExpandedVisibility,
+    Manifest, Module, TypeRoot,
 };
 use crate::manifest_parser::Mod;
 use crate::parsing::FieldValue;
@@ -33,13 +33,26 @@ use lazy_static::lazy_static;
 use proc_macro2::TokenStream;
 use syn::__private::ToTokens;
 use syn::spanned::Spanned;
-use syn::{Attribute, ItemTrait};
+use syn::{Attribute, GenericArgument, ItemTrait, PathArguments, TypeParamBound};
 
 lazy_static! {
     static ref COMPONENT_METADATA_KEYS: HashSet<String> = {
         let mut set = HashSet::<String>::new();
         set.insert("modules".to_owned());
         set.insert("builder_modules".to_owned());
+        set.insert("auto_default_zst".to_owned());
+        set.insert("scope_group".to_owned());
+        set.insert("thread_safe".to_owned());
+        set.insert("fragments".to_owned());
+        set
+    };
+}
+
+lazy_static! {
+    static ref COMPONENT_FRAGMENT_METADATA_KEYS: HashSet<String> = {
+        let mut set = HashSet::<String>::new();
+        set.insert("modules".to_owned());
+        set.insert("fragments".to_owned());
         set
     };
 }
@@ -62,6 +75,18 @@ pub fn handle_component_attribute(
     let mut item_trait: ItemTrait = syn::parse2(input).with_context(|| "trait expected")?;
 
     let provisions = get_provisions(&mut item_trait, mod_)?;
+    if provisions.iter().any(|provision| provision.optional) {
+        bail!("#[optional] is only allowed in #[entry_point]");
+    }
+
+    let (attr, instance_bindings) = parsing::take_type_array(attr, "instance_bindings")?;
+    if !instance_bindings.is_empty() && component_type == ComponentType::Subcomponent {
+        bail!("instance_bindings is only allowed in #[component]");
+    }
+    let instance_bindings = instance_bindings
+        .iter()
+        .map(|type_| type_data::from_syn_type(type_, mod_))
+        .collect::<Result<Vec<TypeData>>>()?;
 
     let attributes = parsing::get_attribute_field_values(attr.clone())?;
     for key in attributes.keys() {
@@ -112,8 +137,41 @@ pub fn handle_component_attribute(
         None
     };
 
+    let auto_default_zst = if let Some(value) = attributes.get("auto_default_zst") {
+        if let FieldValue::BoolLiteral(value) = value {
+            *value
+        } else {
+            bail!("bool expected for auto_default_zst");
+        }
+    } else {
+        false
+    };
+
+    let thread_safe = if let Some(value) = attributes.get("thread_safe") {
+        if let FieldValue::BoolLiteral(value) = value {
+            *value
+        } else {
+            bail!("bool expected for thread_safe");
+        }
+    } else {
+        false
+    };
+
+    let scope_groups = parsing::get_types(attributes.get("scope_group"), mod_)?;
+    let fragments = parsing::get_types(attributes.get("fragments"), mod_)?;
+
+    let type_params = item_trait
+        .generics
+        .type_params()
+        .map(|param| type_data::from_local(&param.ident.to_string(), mod_))
+        .collect::<Result<Vec<TypeData>>>()?;
+    if !type_params.is_empty() && component_type == ComponentType::Subcomponent {
+        bail!("subcomponents cannot be generic");
+    }
+
     let mut component = Component::new();
     component.name = item_trait.ident.to_string();
+    component.type_params = type_params;
     component.type_data = type_data::from_local(&item_trait.ident.to_string(), mod_)?;
     component.component_type = component_type;
     component.provisions.extend(provisions);
@@ -124,6 +182,11 @@ pub fn handle_component_attribute(
         component.modules = m.clone();
     }
     component.definition_only = definition_only;
+    component.auto_default_zst = auto_default_zst;
+    component.thread_safe = thread_safe;
+    component.scope_groups = scope_groups;
+    component.fragments = fragments;
+    component.instance_bindings = instance_bindings;
     component.address = from_local(
         &format!(
             "LOCKJAW_COMPONENT_BUILDER_ADDR_{}",
@@ -155,6 +218,7 @@ pub fn handle_component_attribute(
                 bindings: vec![],
                 subcomponents: HashSet::from([from_local(&subcomponent_name, mod_)?]),
                 install_in: HashSet::from([from_path(path, mod_)?]),
+                source_file: mod_.source_file.to_owned(),
             });
         } else {
             bail!("path expected for parent");
@@ -179,16 +243,83 @@ pub fn get_provisions(item_trait: &ItemTrait, mod_: &Mod) -> Result<Vec<Dependen
                             mod_,
                         )?);
                     }
+                    "optional" => {
+                        provision.optional = true;
+                        provision.has_default = method.default.is_some();
+                    }
                     _ => new_attrs.push(attr.clone()),
                 }
             }
             provision.name = method.sig.ident.to_string();
+            for input in &method.sig.inputs {
+                let syn::FnArg::Typed(ref pat_type) = input else {
+                    continue;
+                };
+                let syn::Pat::Ident(ref ident) = pat_type.pat.deref() else {
+                    build_script_fatal!(pat_type.span(), mod_, "identifier expected");
+                };
+                let mut parameter = Dependency::new();
+                parameter.name = ident.ident.to_string();
+                parameter.type_data = type_data::from_syn_type(pat_type.ty.deref(), mod_)?;
+                provision.parameters.push(parameter);
+            }
             if let syn::ReturnType::Type(ref _token, ref ty) = method.sig.output {
+                if let syn::Type::ImplTrait(ref impl_trait) = ty.deref() {
+                    if let Some(item) = iterator_item(impl_trait) {
+                        // iterates a Vec<T> multibinding lazily.
+                        let elements_type: syn::Type =
+                            syn::parse_quote! { ::lockjaw::Elements<#item> };
+                        provision.type_data = type_data::from_syn_type(&elements_type, mod_)?;
+                        provision.type_data.qualifier = qualifier.map(Box::new);
+                        provisions.push(provision);
+                        continue;
+                    }
+                    let Some(trait_) = impl_trait.bounds.iter().find_map(|bound| match bound {
+                        TypeParamBound::Trait(trait_) => Some(&trait_.path),
+                        _ => None,
+                    }) else {
+                        build_script_fatal!(
+                            ty.span(),
+                            mod_,
+                            "impl Trait provision expects a trait"
+                        );
+                    };
+                    let cl_type: syn::Type = syn::parse_quote! { ::lockjaw::Cl<dyn #trait_> };
+                    provision.type_data = type_data::from_syn_type(&cl_type, mod_)?;
+                    provision.type_data.qualifier = qualifier.map(Box::new);
+                    provision.impl_trait = true;
+                    provisions.push(provision);
+                    continue;
+                }
                 if is_trait_object_without_lifetime(ty.deref(), mod_)? {
                     build_script_fatal!(ty.span(), mod_, "trait object return type may depend on scoped objects, and must have lifetime bounded by the component by wrapping with lockjaw::Cl<>.");
                 }
                 provision.type_data = type_data::from_syn_type(ty.deref(), mod_)?;
+                if provision.optional && !provision.has_default {
+                    if provision.type_data.path != "std::option::Option"
+                        || provision.type_data.args.len() != 1
+                    {
+                        build_script_fatal!(
+                            ty.span(),
+                            mod_,
+                            "#[optional] provisions must return Option<T> or have a default implementation"
+                        );
+                    }
+                    provision.type_data = provision.type_data.args[0].clone();
+                }
                 provision.type_data.qualifier = qualifier.map(Box::new);
+                if let syn::Type::Reference(ref reference) = ty.deref() {
+                    if reference.mutability.is_some() {
+                        if !has_mut_receiver(&method.sig) {
+                            build_script_fatal!(
+                                method.sig.span(),
+                                mod_,
+                                "provisions returning &mut must have a &mut self receiver"
+                            );
+                        }
+                        provision.mutable = true;
+                    }
+                }
             } else {
                 bail!("return type expected for component provisions",);
             }
@@ -198,11 +329,54 @@ pub fn get_provisions(item_trait: &ItemTrait, mod_: &Mod) -> Result<Vec<Dependen
     Ok(provisions)
 }
 
+/// The `Item` of an `impl Iterator<Item = T>` return type.
+fn iterator_item(impl_trait: &syn::TypeImplTrait) -> Option<&syn::Type> {
+    impl_trait.bounds.iter().find_map(|bound| {
+        let TypeParamBound::Trait(trait_) = bound else {
+            return None;
+        };
+        let segment = trait_.path.segments.last()?;
+        if segment.ident != "Iterator" {
+            return None;
+        }
+        let PathArguments::AngleBracketed(ref args) = segment.arguments else {
+            return None;
+        };
+        args.args.iter().find_map(|arg| match arg {
+            GenericArgument::AssocType(assoc) if assoc.ident == "Item" => Some(&assoc.ty),
+            _ => None,
+        })
+    })
+}
+
+fn has_mut_receiver(sig: &syn::Signature) -> bool {
+    if let Some(syn::FnArg::Receiver(ref receiver)) = sig.inputs.first() {
+        return receiver.reference.is_some() && receiver.mutability.is_some();
+    }
+    false
+}
+
 fn is_trait_object_without_lifetime(ty: &syn::Type, mod_: &Mod) -> Result<bool> {
     let type_ = type_data::from_syn_type(ty, mod_)?;
     if type_.root == TypeRoot::GLOBAL && type_.path == "lockjaw::Cl" {
         return Ok(false);
     }
+    if type_.root == TypeRoot::GLOBAL && type_.path == "std::any::Any" && type_.field_ref {
+        // the component itself, which is 'static.
+        return Ok(false);
+    }
+    if type_.root == TypeRoot::GLOBAL && type_.path == "lockjaw::Deferred" {
+        // only holds 'static items.
+        return Ok(false);
+    }
+    if type_.root == TypeRoot::GLOBAL
+        && (type_.path == "std::sync::Arc" || type_.path == "std::boxed::Box")
+        && type_.args.len() == 1
+        && type_.args[0].trait_object
+    {
+        // the default object lifetime of an owned trait object is 'static.
+        return Ok(false);
+    }
     let tokens: Vec<String> = ty
         .to_token_stream()
         .into_iter()
@@ -236,8 +410,32 @@ pub fn handle_builder_modules_attribute(
     let mut builder_modules = BuilderModules::new();
     builder_modules.type_data = Some(type_data::from_local(&item_struct.ident.to_string(), mod_)?);
     builder_modules.builder_modules.extend(modules);
+    builder_modules.source_file = mod_.source_file.to_owned();
     let mut result = Manifest::new();
     result.builder_modules.push(builder_modules);
 
     Ok(result)
 }
+
+pub fn handle_component_fragment_attribute(
+    attr: TokenStream,
+    input: TokenStream,
+    mod_: &Mod,
+) -> Result<Manifest> {
+    let item_trait: ItemTrait = syn::parse2(input).with_context(|| "trait expected")?;
+    let attributes = parsing::get_attribute_field_values(attr)?;
+    for key in attributes.keys() {
+        if !COMPONENT_FRAGMENT_METADATA_KEYS.contains(key) {
+            bail!("unknown key: {}", key);
+        }
+    }
+
+    let fragment = ComponentFragment {
+        type_data: type_data::from_local(&item_trait.ident.to_string(), mod_)?,
+        modules: parsing::get_types(attributes.get("modules"), mod_)?,
+        fragments: parsing::get_types(attributes.get("fragments"), mod_)?,
+    };
+    let mut result = Manifest::new();
+    result.component_fragments.push(fragment);
+    Ok(result)
+}
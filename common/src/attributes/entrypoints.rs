@@ -44,6 +44,9 @@ pub fn handle_entry_point_attribute(
     let item_trait: syn::ItemTrait = syn::parse2(input).with_context(|| "trait expected")?;
 
     let provisions = components::get_provisions(&item_trait, mod_)?;
+    if provisions.iter().any(|provision| provision.mutable) {
+        bail!("#[entry_point] cannot have &mut provisions");
+    }
 
     let attributes = parsing::get_attribute_field_values(attr.clone())?;
 
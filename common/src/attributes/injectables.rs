@@ -19,8 +19,9 @@ use std::collections::{HashMap, HashSet};
 use crate::manifest::{Dependency, ExpandedVisibility, Injectable, Manifest, TypeRoot};
 use crate::manifest_parser::Mod;
 use crate::parsing::{
-    get_attribute, get_attribute_field_values, get_parenthesized_field_values, get_type, get_types,
-    has_attribute, is_attribute, FieldValue,
+    generic_type_params, get_attribute, get_attribute_field_values, get_doc,
+    get_parenthesized_field_values, get_type, get_types, has_attribute, is_attribute,
+    mentions_idents, FieldValue,
 };
 use crate::type_data::{from_syn_type, TypeData};
 use anyhow::{bail, Context, Result};
@@ -29,6 +30,7 @@ use lazy_static::lazy_static;
 use crate::type_data;
 use proc_macro2::TokenStream;
 use syn::__private::quote::format_ident;
+use syn::__private::ToTokens;
 use syn::{FnArg, GenericArgument, ImplItem, ImplItemFn, Pat, PathArguments, Visibility};
 
 lazy_static! {
@@ -36,6 +38,7 @@ lazy_static! {
         let mut set = HashSet::<String>::new();
         set.insert("scope".to_owned());
         set.insert("container".to_owned());
+        set.insert("default".to_owned());
         set
     };
 }
@@ -45,10 +48,14 @@ lazy_static! {
         let mut set = HashSet::<String>::new();
         set.insert("implementing".to_owned());
         set.insert("visibility".to_owned());
+        set.insert("into_vec".to_owned());
         set
     };
 }
 
+/// Name of the ctor generated by `#[injectable(default)]`.
+const DEFAULT_CTOR_NAME: &str = "lockjaw_new_default";
+
 #[derive(PartialEq)]
 enum CtorType {
     Inject,
@@ -59,18 +66,25 @@ pub fn handle_injectable_attribute(
     input: TokenStream,
     mod_: &Mod,
 ) -> Result<Manifest> {
-    let mut item: syn::ItemImpl = syn::parse2(input).with_context(|| "impl block expected")?;
-
     let attributes = get_attribute_field_values(attr.clone())?;
     for key in attributes.keys() {
         if !INJECTABLE_METADATA_KEYS.contains(key) {
             bail!("unknown key: {}", key);
         }
     }
+    if is_default(&attributes)? {
+        return handle_default(input, &attributes, mod_);
+    }
+
+    let mut item: syn::ItemImpl = syn::parse2(input).with_context(|| "impl block expected")?;
     let (ctor_type, ctor, fields) = get_ctor(&mut item.items)?;
     if ctor_type == CtorType::Factory {
         return handle_factory(item.self_ty.clone(), ctor.clone(), fields.clone(), mod_);
     }
+    if !generic_type_params(&ctor.sig.generics).is_empty() {
+        // The proc macro reports this with a span.
+        return Ok(Manifest::new());
+    }
 
     let mut dependencies = Vec::<Dependency>::new();
     for arg in ctor.sig.inputs.iter_mut() {
@@ -126,13 +140,16 @@ pub fn handle_injectable_attribute(
     }
 
     let mut injectable = Injectable::new();
+    injectable.source_file = mod_.source_file.to_owned();
     injectable.type_data = crate::type_data::from_local(&type_name, mod_)?;
     let scopes = get_types(attributes.get("scope"), mod_)?;
 
     injectable.container = get_container(mod_, &attributes, &scopes)?;
     injectable.type_data.scopes.extend(scopes);
     injectable.ctor_name = ctor.sig.ident.to_string();
+    injectable.doc = get_doc(&ctor.attrs);
     injectable.dependencies.extend(dependencies);
+    injectable.fallible = fallible_error(&fields, &ctor.sig, mod_)?;
 
     let mut result = Manifest::new();
 
@@ -143,6 +160,80 @@ pub fn handle_injectable_attribute(
     Ok(result)
 }
 
+fn is_default(attributes: &HashMap<String, FieldValue>) -> Result<bool> {
+    match attributes.get("default") {
+        None => Ok(false),
+        // `#[injectable(default)]` is parsed as the shorthand `default: default`
+        Some(FieldValue::Path(path)) if path.is_ident("default") => Ok(true),
+        Some(FieldValue::BoolLiteral(value)) => Ok(*value),
+        Some(_) => bail!("bool expected for 'default'"),
+    }
+}
+
+/// `#[inject(fallible)]` constructors return `Result<Self, E>`, and the injectable is bound as
+/// `Result<T, E>`.
+fn fallible_error(
+    fields: &HashMap<String, FieldValue>,
+    sig: &syn::Signature,
+    mod_: &Mod,
+) -> Result<Option<TypeData>> {
+    match fields.get("fallible") {
+        None => return Ok(None),
+        // `#[inject(fallible)]` is parsed as the shorthand `fallible: fallible`
+        Some(FieldValue::Path(path)) if path.is_ident("fallible") => {}
+        Some(FieldValue::BoolLiteral(true)) => {}
+        Some(FieldValue::BoolLiteral(false)) => return Ok(None),
+        Some(_) => bail!("bool expected for 'fallible'"),
+    }
+    let syn::ReturnType::Type(_, ref ty) = sig.output else {
+        // The proc macro reports this with a span.
+        return Ok(None);
+    };
+    let syn::Type::Path(ref path) = **ty else {
+        return Ok(None);
+    };
+    let Some(PathArguments::AngleBracketed(angle)) =
+        path.path.segments.last().map(|segment| &segment.arguments)
+    else {
+        return Ok(None);
+    };
+    match angle.args.iter().nth(1) {
+        Some(GenericArgument::Type(error)) => Ok(Some(from_syn_type(error, mod_)?)),
+        _ => Ok(None),
+    }
+}
+
+/// Handles `#[injectable(default)]` on a struct or enum, which is constructed with its `Default`
+/// implementation through a generated ctor.
+fn handle_default(
+    input: TokenStream,
+    attributes: &HashMap<String, FieldValue>,
+    mod_: &Mod,
+) -> Result<Manifest> {
+    let (ident, generics) = match syn::parse2(input) {
+        Ok(syn::Item::Struct(item)) => (item.ident, item.generics),
+        Ok(syn::Item::Enum(item)) => (item.ident, item.generics),
+        _ => bail!("struct or enum expected for #[injectable(default)]"),
+    };
+
+    let mut injectable = Injectable::new();
+    injectable.source_file = mod_.source_file.to_owned();
+    injectable.type_data = crate::type_data::from_local(&ident.to_string(), mod_)?;
+    let scopes = get_types(attributes.get("scope"), mod_)?;
+
+    injectable.container = get_container(mod_, attributes, &scopes)?;
+    injectable.type_data.scopes.extend(scopes);
+    injectable.ctor_name = DEFAULT_CTOR_NAME.to_string();
+
+    let mut result = Manifest::new();
+
+    if generics.lifetimes().next().is_some() {
+        result.lifetimed_types.insert(injectable.type_data.clone());
+    }
+    result.injectables.push(injectable);
+    Ok(result)
+}
+
 fn get_ctor(
     items: &mut Vec<ImplItem>,
 ) -> Result<(CtorType, &mut ImplItemFn, HashMap<String, FieldValue>)> {
@@ -198,6 +289,16 @@ fn get_container(
                 bail!("the 'container' metadata should only be used with an injectable that also has 'scope'",
                 );
             }
+            if let Some(ident) = path.get_ident() {
+                if !mod_.uses.contains_key(&ident.to_string()) {
+                    if let Some(sync_container) = type_data::sync_container(&ident.to_string()) {
+                        let mut container = TypeData::new();
+                        container.root = TypeRoot::GLOBAL;
+                        container.path = sync_container.to_owned();
+                        return Ok(Some(container));
+                    }
+                }
+            }
             let container = crate::type_data::from_path(path, mod_)?;
             return Ok(Some(container));
         } else {
@@ -218,6 +319,7 @@ fn handle_factory(
             bail!("unknown key: {}", k);
         }
     }
+    let generic_params = generic_type_params(&method.sig.generics);
     let mut dependencies = Vec::<Dependency>::new();
     for arg in method.sig.inputs.iter() {
         if let FnArg::Receiver(_) = arg {
@@ -226,6 +328,10 @@ fn handle_factory(
         if let FnArg::Typed(ref type_) = arg {
             if let Pat::Ident(ref ident) = *type_.pat {
                 if !has_attribute(&type_.attrs, "runtime") {
+                    if mentions_idents(type_.ty.to_token_stream(), &generic_params) {
+                        // The proc macro reports this with a span.
+                        return Ok(Manifest::new());
+                    }
                     let ty = &type_.ty;
                     let mut dependency = Dependency::new();
                     dependency.type_data = provider_type(&from_syn_type(ty, mod_)?);
@@ -275,9 +381,11 @@ fn handle_factory(
     };
 
     let mut injectable = Injectable::new();
+    injectable.source_file = mod_.source_file.to_owned();
     injectable.type_data = from_syn_type(&factory_ty, mod_)?;
     injectable.ctor_name = "lockjaw_new_factory".to_string();
     injectable.dependencies.extend(dependencies);
+    injectable.factory_into_vec = factory_into_vec(&metadata, mod_)?;
 
     result.lifetimed_types.insert(injectable.type_data.clone());
 
@@ -286,6 +394,32 @@ fn handle_factory(
     Ok(result)
 }
 
+/// `#[factory(implementing: T, into_vec)]` contributes the factory to `Vec<Cl<dyn T>>`.
+fn factory_into_vec(
+    metadata: &HashMap<String, FieldValue>,
+    mod_: &Mod,
+) -> Result<Option<TypeData>> {
+    match metadata.get("into_vec") {
+        None => return Ok(None),
+        // `#[factory(into_vec)]` is parsed as the shorthand `into_vec: into_vec`
+        Some(FieldValue::Path(path)) if path.is_ident("into_vec") => {}
+        Some(FieldValue::BoolLiteral(true)) => {}
+        Some(FieldValue::BoolLiteral(false)) => return Ok(None),
+        Some(_) => bail!("bool expected for 'into_vec'"),
+    }
+    let Some(FieldValue::Path(implementing)) = metadata.get("implementing") else {
+        // The proc macro reports this with a span.
+        return Ok(None);
+    };
+    let mut trait_type = type_data::from_path(implementing, mod_)?;
+    trait_type.trait_object = true;
+    let mut cl_type = TypeData::new();
+    cl_type.root = TypeRoot::GLOBAL;
+    cl_type.path = "lockjaw::Cl".to_string();
+    cl_type.args.push(trait_type);
+    Ok(Some(cl_type))
+}
+
 fn add_component_visible(ident: &str, mod_: &Mod, manifest: &mut Manifest) -> Result<()> {
     let exported_ident = format!("lockjaw_export_type_{}", ident);
 
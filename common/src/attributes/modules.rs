@@ -14,11 +14,11 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
 
 use crate::build_script_fatal;
-use crate::manifest::BindingType::{Binds, BindsOptionOf, Multibinds, Provides};
+use crate::manifest::BindingType::{Binds, BindsOptionOf, Decorator, Multibinds, Provides};
 use crate::manifest::{
     Binding, BindingType, Dependency, Manifest, Module, MultibindingMapKey, MultibindingType,
 };
@@ -32,7 +32,10 @@ use lazy_static::lazy_static;
 use proc_macro2::TokenStream;
 use std::convert::TryFrom;
 use std::iter::FromIterator;
+use syn::punctuated::Punctuated;
+use syn::visit_mut::{self, VisitMut};
 use syn::ImplItemFn;
+use syn::Token;
 use syn::__private::ToTokens;
 use syn::spanned::Spanned;
 
@@ -41,6 +44,7 @@ lazy_static! {
         let mut set = HashSet::<String>::new();
         set.insert("subcomponents".to_owned());
         set.insert("install_in".to_owned());
+        set.insert("subcomponent_qualifier".to_owned());
         set
     };
 }
@@ -76,19 +80,38 @@ fn handle_module_attribute_internal(
     }
     let module_type = crate::type_data::from_local(&module_path.to_owned(), mod_)?;
     let mut bindings: Vec<Binding> = Vec::new();
+    let mut lifetimed_types: HashSet<TypeData> = HashSet::new();
     for i in 0..item_impl.items.len() {
         #[allow(unused_mut)] // required
         let mut item = item_impl.items.get_mut(i).unwrap();
         if let syn::ImplItem::Fn(ref mut method) = item {
-            bindings.push(parse_binding(method, mod_)?);
+            let method_bindings = parse_binding(method, mod_)?;
+            if returns_lifetimed_type(&method.sig) {
+                lifetimed_types.extend(
+                    method_bindings
+                        .iter()
+                        .filter(|binding| binding.binding_type == Provides)
+                        .map(|binding| binding.type_data.clone()),
+                );
+            }
+            bindings.extend(method_bindings);
         }
     }
 
     let mut module = Module::new();
     module.type_data = module_type;
     module.bindings.extend(bindings);
+    module.source_file = mod_.source_file.to_owned();
     if let Some(subcomponents) = attributes.get("subcomponents") {
-        let types = subcomponents.get_types(mod_)?;
+        let mut types = subcomponents.get_types(mod_)?;
+        if let Some(qualifier) = attributes.get("subcomponent_qualifier") {
+            let qualifier = parsing::get_types(Some(qualifier), mod_)?
+                .pop()
+                .context("path expected for subcomponent_qualifier")?;
+            for type_ in &mut types {
+                type_.qualifier = Some(Box::new(qualifier.clone()));
+            }
+        }
         module.subcomponents = HashSet::from_iter(types);
     }
     if let Some(install_in) = attributes.get("install_in") {
@@ -98,44 +121,87 @@ fn handle_module_attribute_internal(
     let mut manifest = Manifest::new();
 
     manifest.modules.push(module);
+    manifest.lifetimed_types.extend(lifetimed_types);
 
     Ok(manifest)
 }
 
-fn parse_binding(method: &ImplItemFn, mod_: &Mod) -> Result<Binding> {
+/// Whether the method returns a path type with lifetime arguments, like `Foo<'_>`.
+fn returns_lifetimed_type(signature: &syn::Signature) -> bool {
+    let syn::ReturnType::Type(_, ref ty) = signature.output else {
+        return false;
+    };
+    let syn::Type::Path(ref path) = ty.deref() else {
+        return false;
+    };
+    let Some(segment) = path.path.segments.last() else {
+        return false;
+    };
+    let syn::PathArguments::AngleBracketed(ref angle) = segment.arguments else {
+        return false;
+    };
+    angle
+        .args
+        .iter()
+        .any(|arg| matches!(arg, syn::GenericArgument::Lifetime(_)))
+}
+
+fn parse_binding(method: &ImplItemFn, mod_: &Mod) -> Result<Vec<Binding>> {
     let mut option_binding: Option<Binding> = None;
+    let mut binds_all: Vec<TypeData> = Vec::new();
     let mut multibinding = MultibindingType::None;
     let mut map_key = MultibindingMapKey::None;
     let mut qualifier: Option<Box<TypeData>> = None;
+    let mut if_present: Option<TypeData> = None;
     for attr in &method.attrs {
         let attr_str = parsing::get_attribute(attr);
         match attr_str.as_str() {
             "provides" => {
                 if option_binding.is_some() {
-                    bail!("#[module] methods can only be annotated by one of #[provides]/#[binds]/#[binds_option_of]/#[multibinds]");
+                    bail!("#[module] methods can only be annotated by one of #[provides]/#[binds]/#[binds_option_of]/#[binds_decorator]/#[multibinds]");
                 }
                 option_binding = Some(handle_provides(attr, &method.sig, mod_)?);
             }
             "binds" => {
                 if option_binding.is_some() {
-                    bail!("#[module] methods can only be annotated by one of #[provides]/#[binds]/#[binds_option_of]/#[multibinds]");
+                    bail!("#[module] methods can only be annotated by one of #[provides]/#[binds]/#[binds_option_of]/#[binds_decorator]/#[multibinds]");
                 }
                 option_binding = Some(handle_binds(attr, &method.sig, &method.block, mod_)?);
             }
+            "binds_all" => {
+                if option_binding.is_some() {
+                    bail!("#[module] methods can only be annotated by one of #[provides]/#[binds]/#[binds_option_of]/#[binds_decorator]/#[multibinds]");
+                }
+                option_binding = Some(handle_binds_all(&method.sig, mod_)?);
+                binds_all = get_binds_all_traits(attr, mod_)?;
+            }
             "binds_option_of" => {
                 if option_binding.is_some() {
-                    bail!("#[module] methods can only be annotated by one of #[provides]/#[binds]/#[binds_option_of]/#[multibinds]");
+                    bail!("#[module] methods can only be annotated by one of #[provides]/#[binds]/#[binds_option_of]/#[binds_decorator]/#[multibinds]");
                 }
                 option_binding = Some(handle_binds_option_of(&method.sig, &method.block, mod_)?);
             }
+            "binds_decorator" => {
+                if option_binding.is_some() {
+                    bail!("#[module] methods can only be annotated by one of #[provides]/#[binds]/#[binds_option_of]/#[binds_decorator]/#[multibinds]");
+                }
+                option_binding = Some(handle_binds_decorator(attr, &method.sig, mod_)?);
+            }
             "multibinds" => {
                 if option_binding.is_some() {
-                    bail!("#[module] methods can only be annotated by one of #[provides]/#[binds]/#[binds_option_of]/#[multibinds]");
+                    bail!("#[module] methods can only be annotated by one of #[provides]/#[binds]/#[binds_option_of]/#[binds_decorator]/#[multibinds]");
                 }
                 option_binding = Some(handle_multibinds(&method.sig, &method.block, mod_)?);
             }
             "into_vec" => {
                 multibinding = MultibindingType::IntoVec;
+                let fields = get_parenthesized_field_values(&attr.meta)?;
+                if let Some(field) = fields.get("if_present") {
+                    let FieldValue::Path(ref path) = field else {
+                        bail!("path expected for if_present");
+                    };
+                    if_present = Some(crate::type_data::from_path(path, mod_)?);
+                }
             }
             "elements_into_vec" => {
                 multibinding = MultibindingType::ElementsIntoVec;
@@ -187,6 +253,19 @@ fn parse_binding(method: &ImplItemFn, mod_: &Mod) -> Result<Binding> {
                     } else {
                         bail!("i32 literal expected for i32_key",);
                     }
+                } else if let Some(field) = fields.get("const_key") {
+                    let FieldValue::Path(ref path) = field else {
+                        bail!("path expected for const_key");
+                    };
+                    let Some(FieldValue::Path(ref key_type)) = fields.get("key_type") else {
+                        bail!("key_type path expected for const_key");
+                    };
+                    map_key = MultibindingMapKey::Const(
+                        crate::type_data::from_path(key_type, mod_)?,
+                        crate::type_data::from_path(path, mod_)?,
+                    );
+                } else if let Some(field) = fields.get("key") {
+                    map_key = handle_expr_key(field, fields.get("key_type"), mod_)?;
                 }
             }
             _ => {}
@@ -209,8 +288,142 @@ fn parse_binding(method: &ImplItemFn, mod_: &Mod) -> Result<Binding> {
     }
     binding.multibinding_type = multibinding;
     binding.map_key = map_key;
+    binding.doc = parsing::get_doc(&method.attrs);
     binding.type_data.qualifier = qualifier;
-    Ok(binding)
+    binding.if_present = if_present;
+    if binds_all.is_empty() {
+        return Ok(vec![binding]);
+    }
+    Ok(binds_all
+        .into_iter()
+        .map(|trait_type| {
+            let mut trait_binding = binding.clone();
+            trait_binding.type_data = trait_type;
+            trait_binding.type_data.qualifier = binding.type_data.qualifier.clone();
+            trait_binding
+        })
+        .collect())
+}
+
+/// `#[binds_all]` is a `#[binds]` to each of the listed traits, and the binding type is filled in by
+/// [`get_binds_all_traits()`].
+fn handle_binds_all(signature: &syn::Signature, mod_: &Mod) -> Result<Binding> {
+    let mut binds = Binding::new(Binds);
+    binds.name = signature.ident.to_string();
+    if signature.inputs.len() != 1 {
+        bail!("binds_all method must only take the binding type as parameter",);
+    }
+    let syn::FnArg::Typed(ref type_) = signature.inputs.first().unwrap() else {
+        bail!("binds_all method must only take the binding type as parameter",);
+    };
+    let mut dependency = Dependency::new();
+    let syn::Pat::Ident(ref ident) = type_.pat.deref() else {
+        bail!("identifier expected");
+    };
+    dependency.name = ident.ident.to_string();
+    dependency.type_data = crate::type_data::from_syn_type(type_.ty.deref(), mod_)?;
+    binds.dependencies.push(dependency);
+    Ok(binds)
+}
+
+/// Parses `#[binds_all(Trait1, Trait2)]` into `dyn Trait1`, `dyn Trait2`.
+fn get_binds_all_traits(attr: &syn::Attribute, mod_: &Mod) -> Result<Vec<TypeData>> {
+    let paths = attr
+        .parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+        .with_context(|| "trait paths expected for #[binds_all]")?;
+    let mut result = Vec::new();
+    for path in paths {
+        let trait_object: syn::Type = syn::parse_str(&format!("dyn {}", path.to_token_stream()))?;
+        result.push(crate::type_data::from_syn_type(&trait_object, mod_)?);
+    }
+    if result.is_empty() {
+        bail!("#[binds_all] requires at least one trait");
+    }
+    Ok(result)
+}
+
+/// `#[into_map(key: MyKey::new("a", 3))]`, the key type is inferred from the constructor unless
+/// `key_type` is given.
+fn handle_expr_key(
+    key: &FieldValue,
+    key_type: Option<&FieldValue>,
+    mod_: &Mod,
+) -> Result<MultibindingMapKey> {
+    let mut expr = match key {
+        FieldValue::Expr(ref expr) => expr.clone(),
+        FieldValue::Path(ref path) => syn::parse2(path.to_token_stream())?,
+        _ => bail!("expression expected for key"),
+    };
+    let key_type = match key_type {
+        Some(FieldValue::Path(ref path)) => crate::type_data::from_path(path, mod_)?,
+        Some(_) => bail!("path expected for key_type"),
+        None => {
+            let syn::Expr::Call(ref call) = expr else {
+                bail!("key_type is required unless the key is constructed with Type::function()");
+            };
+            let syn::Expr::Path(ref function) = call.func.deref() else {
+                bail!("key_type is required unless the key is constructed with Type::function()");
+            };
+            if function.path.segments.len() < 2 {
+                bail!("key_type is required unless the key is constructed with Type::function()");
+            }
+            let mut type_path = function.path.clone();
+            type_path.segments.pop();
+            type_path.segments.pop_punct();
+            crate::type_data::from_path(&type_path, mod_)?
+        }
+    };
+    let mut resolver = KeyPathResolver {
+        mod_,
+        paths: Vec::new(),
+        error: None,
+    };
+    resolver.visit_expr_mut(&mut expr);
+    if let Some(error) = resolver.error {
+        return Err(error);
+    }
+    Ok(MultibindingMapKey::Expr(
+        key_type,
+        expr.to_token_stream().to_string(),
+        resolver.paths,
+    ))
+}
+
+/// Replaces the paths in a key expression with placeholders, since they are resolved relative to
+/// the module but the map is generated at the crate root.
+struct KeyPathResolver<'a> {
+    mod_: &'a Mod<'a>,
+    paths: Vec<TypeData>,
+    error: Option<anyhow::Error>,
+}
+
+impl KeyPathResolver<'_> {
+    fn placeholder(&mut self, path: &syn::Path) -> syn::Path {
+        let type_data = match crate::type_data::from_path(path, self.mod_) {
+            Ok(type_data) => type_data,
+            Err(error) => {
+                self.error.get_or_insert(error);
+                TypeData::new()
+            }
+        };
+        let ident = syn::Ident::new(
+            &format!("lockjaw_key_path_{}", self.paths.len()),
+            proc_macro2::Span::call_site(),
+        );
+        self.paths.push(type_data);
+        syn::Path::from(ident)
+    }
+}
+
+impl VisitMut for KeyPathResolver<'_> {
+    fn visit_expr_path_mut(&mut self, expr: &mut syn::ExprPath) {
+        expr.path = self.placeholder(&expr.path);
+    }
+
+    fn visit_expr_struct_mut(&mut self, expr: &mut syn::ExprStruct) {
+        expr.path = self.placeholder(&expr.path);
+        visit_mut::visit_expr_struct_mut(self, expr);
+    }
 }
 
 fn handle_provides(
@@ -247,13 +460,36 @@ fn handle_provides(
     }
     let provides_attr = parsing::get_parenthesized_field_values(&attr.meta)?;
     if let Some(scope) = provides_attr.get("scope") {
-        let scopes = parsing::get_types(Some(scope), mod_)?;
-
-        provides.type_data.scopes.extend(scopes);
+        set_scopes(&mut provides, scope, mod_)?;
     }
     Ok(provides)
 }
 
+fn handle_binds_decorator(
+    attr: &syn::Attribute,
+    signature: &syn::Signature,
+    mod_: &Mod,
+) -> Result<Binding> {
+    let mut decorator = handle_provides(attr, signature, mod_)?;
+    decorator.binding_type = Decorator;
+    let inner_count = decorator
+        .dependencies
+        .iter()
+        .filter(|dependency| dependency.type_data == decorator.type_data)
+        .count();
+    if inner_count != 1 {
+        bail!("#[binds_decorator] method must take exactly one parameter of the decorated type");
+    }
+    let fields = get_parenthesized_field_values(&attr.meta)?;
+    if let Some(priority) = fields.get("priority") {
+        let FieldValue::IntLiteral(priority) = priority else {
+            bail!("integer literal expected for priority");
+        };
+        decorator.priority = *priority;
+    }
+    Ok(decorator)
+}
+
 fn handle_binds(
     attr: &syn::Attribute,
     signature: &syn::Signature,
@@ -266,16 +502,37 @@ fn handle_binds(
 
     let mut binds = Binding::new(Binds);
     binds.name = signature.ident.to_string();
-    if let syn::ReturnType::Type(ref _token, ref ty) = signature.output {
+    let provides_attr = parsing::get_parenthesized_field_values(&attr.meta)?;
+    binds.static_dispatch = is_static_dispatch(&provides_attr);
+    if binds.static_dispatch {
+        let syn::ReturnType::Type(ref _token, ref ty) = signature.output else {
+            bail!("return type expected");
+        };
+        if !matches!(ty.deref(), syn::Type::ImplTrait(_)) {
+            build_script_fatal!(
+                signature.span(),
+                mod_,
+                "#[binds(static_dispatch)] methods must return impl Trait"
+            )
+        }
+        binds.type_data = crate::type_data::from_syn_type(ty.deref(), mod_)?;
+    } else if let syn::ReturnType::Type(ref _token, ref ty) = signature.output {
         let return_type = crate::type_data::from_syn_type(ty.deref(), mod_)?;
         match return_type.path.as_str() {
             "lockjaw::Cl" => {}
             "Cl" => {}
-            _ => {
-                build_script_fatal!(signature.span(), mod_, "#[binds] methods must return Cl<T>")
-            }
+            "std::sync::Arc" => {}
+            _ => build_script_fatal!(
+                signature.span(),
+                mod_,
+                "#[binds] methods must return Cl<T> or Arc<T>"
+            ),
+        }
+        if return_type.path == "std::sync::Arc" {
+            binds.type_data = return_type;
+        } else {
+            binds.type_data = return_type.args[0].clone();
         }
-        binds.type_data = return_type.args[0].clone();
     } else {
         bail!("return type expected");
     }
@@ -298,14 +555,43 @@ fn handle_binds(
             binds.dependencies.push(dependency);
         }
     }
-    let provides_attr = parsing::get_parenthesized_field_values(&attr.meta)?;
     if let Some(scope) = provides_attr.get("scope") {
-        let scopes = parsing::get_types(Some(scope), mod_)?;
-        binds.type_data.scopes.extend(scopes);
+        set_scopes(&mut binds, scope, mod_)?;
     }
     Ok(binds)
 }
 
+/// Whether `scope` is the `install` keyword, scoping the binding to the component the module is
+/// installed in.
+fn is_install_scope(scope: &FieldValue) -> bool {
+    match scope {
+        FieldValue::Path(path) => path.is_ident("install"),
+        FieldValue::Array(array) => array.iter().any(is_install_scope),
+        _ => false,
+    }
+}
+
+fn set_scopes(binding: &mut Binding, scope: &FieldValue, mod_: &Mod) -> Result<()> {
+    if is_install_scope(scope) {
+        if let FieldValue::Array(_) = scope {
+            bail!("`scope: install` cannot be combined with other scopes");
+        }
+        binding.install_scoped = true;
+        return Ok(());
+    }
+    let scopes = parsing::get_types(Some(scope), mod_)?;
+    binding.type_data.scopes.extend(scopes);
+    Ok(())
+}
+
+/// `#[binds(static_dispatch)]` or `#[binds(static_dispatch: true)]`.
+fn is_static_dispatch(fields: &HashMap<String, FieldValue>) -> bool {
+    matches!(
+        fields.get("static_dispatch"),
+        Some(FieldValue::Path(_)) | Some(FieldValue::BoolLiteral(true))
+    )
+}
+
 fn handle_binds_option_of(
     signature: &syn::Signature,
     block: &syn::Block,
@@ -16,7 +16,7 @@ limitations under the License.
 
 use crate::manifest::Manifest;
 use crate::manifest_parser::Mod;
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use proc_macro2::TokenStream;
 
 pub fn handle_qualifier_attribute(
@@ -24,11 +24,25 @@ pub fn handle_qualifier_attribute(
     input: TokenStream,
     mod_: &Mod,
 ) -> Result<Manifest> {
-    let item: syn::ItemStruct = syn::parse2(input).with_context(|| "struct block expected")?;
+    let item: syn::Item = syn::parse2(input).with_context(|| "struct or enum block expected")?;
 
     let mut manifest = Manifest::new();
-    manifest
-        .qualifiers
-        .push(crate::type_data::from_local(&item.ident.to_string(), mod_)?);
+    match item {
+        syn::Item::Struct(item_struct) => {
+            manifest.qualifiers.push(crate::type_data::from_local(
+                &item_struct.ident.to_string(),
+                mod_,
+            )?);
+        }
+        syn::Item::Enum(item_enum) => {
+            let enum_type = crate::type_data::from_local(&item_enum.ident.to_string(), mod_)?;
+            for variant in &item_enum.variants {
+                let mut qualifier = enum_type.clone();
+                qualifier.path.push_str(&format!("::{}", variant.ident));
+                manifest.qualifiers.push(qualifier);
+            }
+        }
+        _ => bail!("struct or enum block expected"),
+    }
     Ok(manifest)
 }
@@ -75,8 +75,37 @@ impl SpanData {
     }
 }
 
+impl SpanData {
+    /// Location of errors that are not about a specific item, or whose span is no longer known.
+    pub fn from_file(source_file: &str) -> Self {
+        SpanData {
+            location: source_file.to_string(),
+            line: "".to_string(),
+            marker: "".to_string(),
+        }
+    }
+}
+
+impl FatalBuildScriptError {
+    /// Fails the build script, reporting the error to cargo.
+    pub fn report(&self) -> ! {
+        let message = self.to_string();
+        for m in message.split('\n') {
+            println!("cargo::error={}", m);
+        }
+        panic!("{}", message);
+    }
+}
+
 impl Display for FatalBuildScriptError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.span.line.is_empty() {
+            return write!(
+                f,
+                "lockjaw fatal build script error:in {}\n{}",
+                self.span.location, self.message
+            );
+        }
         write!(
             f,
             "lockjaw fatal build script error:in {}\n{}\n{}\n{}",
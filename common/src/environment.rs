@@ -14,13 +14,109 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Env var that relocates the lockjaw artifacts away from `OUT_DIR`.
+pub const LOCKJAW_OUTPUT_DIR_ENV: &str = "LOCKJAW_OUTPUT_DIR";
+
+/// Env var that overrides [`symbol_suffix()`].
+pub const LOCKJAW_SYMBOL_SUFFIX_ENV: &str = "LOCKJAW_SYMBOL_SUFFIX";
+
+/// Env var that fails the build script when source cannot be parsed, instead of skipping it with a
+/// warning.
+pub const LOCKJAW_STRICT_PARSE_ENV: &str = "LOCKJAW_STRICT_PARSE";
+
+/// Env var pointing the proc_macro at a single source file to parse bindings from, set by
+/// `lockjaw::testing::set_src_path()` for trybuild test cases.
+pub const LOCKJAW_TRYBUILD_PATH_ENV: &str = "LOCKJAW_TRYBUILD_PATH";
+
+/// Whether `LOCKJAW_STRICT_PARSE` is set to anything other than `0`.
+pub fn strict_parse() -> bool {
+    std::env::var(LOCKJAW_STRICT_PARSE_ENV)
+        .map(|value| !value.is_empty() && value != "0")
+        .unwrap_or(false)
+}
+
 pub fn current_package() -> String {
     std::env::var("CARGO_PKG_NAME")
         .expect("missing pkg name env var")
         .replace("-", "_")
 }
+pub fn current_version() -> String {
+    std::env::var("CARGO_PKG_VERSION").expect("missing pkg version env var")
+}
 pub fn current_crate() -> String {
     std::env::var("CARGO_CRATE_NAME")
         .expect("missing crate name env var")
         .replace("-", "_")
 }
+
+/// Directory where the build script and the proc_macro exchange artifacts for the current package.
+///
+/// `$LOCKJAW_OUTPUT_DIR/<package>/` if the env var is set (relative paths are resolved against
+/// `CARGO_MANIFEST_DIR`), otherwise `$OUT_DIR/lockjaw/`.
+pub fn lockjaw_output_dir() -> Option<PathBuf> {
+    if let Ok(dir) = std::env::var(LOCKJAW_OUTPUT_DIR_ENV) {
+        let mut path = PathBuf::from(dir);
+        if path.is_relative() {
+            if let Ok(manifest_dir) = std::env::var("CARGO_MANIFEST_DIR") {
+                path = Path::new(&manifest_dir).join(path);
+            }
+        }
+        return Some(normalize_path(&path.join(current_package())));
+    }
+    std::env::var("OUT_DIR")
+        .ok()
+        .map(|out_dir| normalize_path(&Path::new(&out_dir).join("lockjaw")))
+}
+
+/// Makes the path absolute and canonical if it exists, so paths handed between the build script
+/// and the proc_macro are spelled the same regardless of the working directory.
+///
+/// On windows the path only uses `\` as separator, and the `\\?\` prefix added by
+/// [`std::fs::canonicalize()`] is removed, since verbatim paths do not accept `/` and cannot be
+/// joined with paths from other sources.
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let absolute = std::fs::canonicalize(path)
+        .or_else(|_| std::path::absolute(path))
+        .unwrap_or_else(|_| path.to_owned());
+    if cfg!(windows) {
+        PathBuf::from(normalize_windows_path(&absolute.to_string_lossy()))
+    } else {
+        absolute
+    }
+}
+
+/// Normalizes the separators of a windows path to `\`, and turns verbatim paths (`\\?\C:\`,
+/// `\\?\UNC\server\share\`) into their regular form.
+pub fn normalize_windows_path(path: &str) -> String {
+    let path = path.replace('/', "\\");
+    if let Some(unc) = path.strip_prefix(r"\\?\UNC\") {
+        format!(r"\\{}", unc)
+    } else if let Some(local) = path.strip_prefix(r"\\?\") {
+        local.to_owned()
+    } else {
+        path
+    }
+}
+
+/// Suffix of the unmangled symbols a crate declaring components links against, so roots of
+/// different packages linked into one binary (e.g. dylib plugins) do not collide.
+///
+/// It is `$LOCKJAW_SYMBOL_SUFFIX` if set, otherwise a hash of the package name and version of the
+/// crate declaring the components. The root crate exports its symbols under the suffix of every
+/// crate whose components it generates.
+pub fn symbol_suffix(package: &str, version: &str) -> String {
+    if let Ok(suffix) = std::env::var(LOCKJAW_SYMBOL_SUFFIX_ENV) {
+        return suffix
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+    }
+    let mut hasher = DefaultHasher::new();
+    package.replace("-", "_").hash(&mut hasher);
+    version.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
@@ -23,5 +23,9 @@ pub mod manifest;
 #[doc(hidden)]
 pub mod manifest_parser;
 mod parsing;
+#[cfg(feature = "query")]
+pub mod query;
+#[doc(hidden)]
+pub mod timings;
 #[doc(hidden)]
 pub mod type_data;
@@ -21,6 +21,17 @@ use crate::manifest::TypeRoot::UNSPECIFIED;
 use crate::type_data::TypeData;
 use std::collections::{HashMap, HashSet};
 
+/// Version of the serialized [`DepManifests`] format. Bump it whenever the format changes, so a
+/// processor reading a manifest written by a different lockjaw can report it instead of failing to
+/// deserialize.
+pub const MANIFEST_SCHEMA_VERSION: u32 = 2;
+
+/// Version of lockjaw writing or reading the manifest.
+pub const LOCKJAW_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Hash of the `lockjaw_common` sources, which changes even if [`LOCKJAW_VERSION`] does not.
+pub const LOCKJAW_COMMON_BUILD_ID: &str = env!("LOCKJAW_COMMON_BUILD_ID");
+
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default)]
 pub struct Manifest {
     pub injectables: Vec<Injectable>,
@@ -33,6 +44,16 @@ pub struct Manifest {
     pub root: bool,
     pub expanded_visibilities: HashMap<String, ExpandedVisibility>,
     pub lifetimed_types: HashSet<TypeData>,
+    /// Components with `<dyn Component>::new()`/`build()` calls in the source.
+    pub instantiated_components: HashSet<TypeData>,
+    /// Structs used by `#[builder_modules]` or as `#[injectable]` containers that are private to a
+    /// nested mod, and cannot be named by the epilogue at the crate root.
+    pub private_types: HashSet<TypeData>,
+    pub component_fragments: Vec<ComponentFragment>,
+    /// `type` aliases and renamed `use`s, which are the same type as their target to rustc.
+    pub type_aliases: HashSet<TypeData>,
+    /// Package version of each crate the bindings are gathered from, keyed by crate name.
+    pub crate_versions: HashMap<String, String>,
 }
 
 impl Manifest {
@@ -51,6 +72,11 @@ impl Manifest {
         self.root = false;
         self.expanded_visibilities.clear();
         self.lifetimed_types.clear();
+        self.instantiated_components.clear();
+        self.private_types.clear();
+        self.component_fragments.clear();
+        self.type_aliases.clear();
+        self.crate_versions.clear();
     }
 
     pub fn merge_from(&mut self, other: &Manifest) {
@@ -75,6 +101,20 @@ impl Manifest {
         );
         self.lifetimed_types
             .extend(other.lifetimed_types.iter().map(Clone::clone));
+        self.instantiated_components
+            .extend(other.instantiated_components.iter().map(Clone::clone));
+        self.private_types
+            .extend(other.private_types.iter().map(Clone::clone));
+        self.component_fragments
+            .extend_from_slice(other.component_fragments.as_slice());
+        self.type_aliases
+            .extend(other.type_aliases.iter().map(Clone::clone));
+        self.crate_versions.extend(
+            other
+                .crate_versions
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone())),
+        );
     }
 }
 
@@ -84,6 +124,19 @@ pub struct Injectable {
     pub ctor_name: String,
     pub dependencies: Vec<Dependency>,
     pub container: Option<TypeData>,
+    /// File declaring the injectable, for debug output.
+    pub source_file: String,
+    /// Doc comments of the `#[inject]` method.
+    pub doc: String,
+    /// Whether the fields of the struct contain types that can be mutated through a shared
+    /// reference. `None` if the struct is not declared in the same mod as the `#[injectable]`.
+    pub interior_mutability: Option<bool>,
+    /// `#[factory(implementing: T, into_vec)]`, the `Cl<dyn T>` the factory contributes to
+    /// `Vec<Cl<dyn T>>`.
+    pub factory_into_vec: Option<TypeData>,
+    /// `#[inject(fallible)]`, the error type `E` of the `Result<Self, E>` returned by the
+    /// constructor.
+    pub fallible: Option<TypeData>,
 }
 
 impl Injectable {
@@ -121,6 +174,18 @@ pub struct Component {
     pub modules: Vec<TypeData>,
     pub definition_only: bool,
     pub address: TypeData,
+    pub auto_default_zst: bool,
+    /// Scopes the component claims in addition to its own type.
+    pub scope_groups: Vec<TypeData>,
+    /// Whether every binding must be `Send + Sync`.
+    pub thread_safe: bool,
+    /// Type parameters of a generic component, as resolved in the provisions. The component is
+    /// generated once for each set of arguments it is instantiated with.
+    pub type_params: Vec<TypeData>,
+    /// `#[component_fragment]`s embedded in the component.
+    pub fragments: Vec<TypeData>,
+    /// Types of the pre-built values passed to `build_with()`.
+    pub instance_bindings: Vec<TypeData>,
 }
 
 impl Component {
@@ -129,6 +194,15 @@ impl Component {
     }
 }
 
+/// Modules and scope shared by the components embedding the `#[component_fragment]`.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default)]
+pub struct ComponentFragment {
+    pub type_data: TypeData,
+    pub modules: Vec<TypeData>,
+    /// Other fragments embedded by this fragment.
+    pub fragments: Vec<TypeData>,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default)]
 pub struct EntryPoint {
     pub type_data: TypeData,
@@ -147,6 +221,8 @@ impl EntryPoint {
 pub struct BuilderModules {
     pub type_data: Option<TypeData>,
     pub builder_modules: Vec<Dependency>,
+    /// File declaring the struct, to tell apart structs of the same name in different mods.
+    pub source_file: String,
 }
 
 impl BuilderModules {
@@ -159,6 +235,18 @@ impl BuilderModules {
 pub struct Dependency {
     pub name: String,
     pub type_data: TypeData,
+    /// Provision returning `&mut T` through a `&mut self` receiver.
+    pub mutable: bool,
+    /// `#[optional]` entry point provision, only fulfilled if the binding exists.
+    pub optional: bool,
+    /// The `#[optional]` provision falls back to its default body instead of returning `None`.
+    pub has_default: bool,
+    /// Provision declared as `-> impl Trait`, `type_data` is the `Cl<dyn Trait>` that must be bound
+    /// with `#[binds]`, and the implementation type is returned instead.
+    pub impl_trait: bool,
+    /// Parameters of the provision, forwarded as the `#[runtime]` arguments of the `#[factory]` of
+    /// `type_data`.
+    pub parameters: Vec<Dependency>,
 }
 
 impl Dependency {
@@ -187,6 +275,8 @@ pub struct Module {
     pub bindings: Vec<Binding>,
     pub subcomponents: HashSet<TypeData>,
     pub install_in: HashSet<TypeData>,
+    /// File declaring the module, for debug output.
+    pub source_file: String,
 }
 
 impl Module {
@@ -204,6 +294,17 @@ pub struct Binding {
     pub binding_type: BindingType,
     pub multibinding_type: MultibindingType,
     pub map_key: MultibindingMapKey,
+    /// `#[binds(static_dispatch)]`, `type_data` is the trait requested as `impl Trait`, resolved to
+    /// the implementation instead of `Cl<dyn Trait>`.
+    pub static_dispatch: bool,
+    /// `scope: install`, the binding is scoped to whichever component the module is installed in.
+    pub install_scoped: bool,
+    /// Doc comments of the binding method.
+    pub doc: String,
+    /// `#[into_vec(if_present: T)]`, the contribution is dropped if `T` is not bound.
+    pub if_present: Option<TypeData>,
+    /// `#[binds_decorator(priority: N)]`, decorators with lower priority wrap the binding first.
+    pub priority: i64,
 }
 
 impl Binding {
@@ -222,6 +323,8 @@ pub enum BindingType {
     Binds,
     BindsOptionOf,
     Multibinds,
+    /// `#[binds_decorator]`, wraps the binding of the same type.
+    Decorator,
 }
 
 impl Default for BindingType {
@@ -250,6 +353,13 @@ pub enum MultibindingMapKey {
     String(String),
     I32(i32),
     Enum(TypeData, TypeData),
+    /// (key type, path to the const value)
+    Const(TypeData, TypeData),
+    /// (key type, expression, paths in the expression)
+    ///
+    /// Paths in the expression are replaced by `lockjaw_key_path_N` placeholders, N being the index
+    /// in the paths, so they can be named from the crate root.
+    Expr(TypeData, String, Vec<TypeData>),
 }
 
 impl Default for MultibindingMapKey {
@@ -279,17 +389,46 @@ impl CfgManifest {
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct DepManifests {
+    /// [`MANIFEST_SCHEMA_VERSION`] of the build script writing the manifest.
+    pub schema_version: u32,
+    /// [`LOCKJAW_VERSION`] of the build script writing the manifest.
+    pub lockjaw_version: String,
+    /// Version of the lockjaw dependency of each crate in the manifest, including the current one.
+    pub crate_lockjaw_versions: HashMap<String, String>,
     pub crate_name: String,
+    /// Whether test manifests were gathered, see `lockjaw::BuildScriptOptions`.
+    pub include_tests: bool,
     pub prod_manifest: Vec<Manifest>,
     pub test_manifest: Vec<Manifest>,
+    /// Manifests of each root target of the current package, keyed by [`DepManifests::target_key`].
     pub root_manifests: HashMap<String, CfgManifest>,
+    /// Cargo target kinds (`lib`, `bin`, `example`, `bench`, `test`...) of each root target.
+    pub root_kinds: HashMap<String, Vec<String>>,
+}
+
+impl DepManifests {
+    /// Key of a root target in `root_manifests` and `root_kinds`.
+    ///
+    /// Binaries are qualified since `src/main.rs` shares the package name with `src/lib.rs`, and
+    /// entry points installed by one must not end up in the graph of the other.
+    pub fn target_key(kinds: &[String], name: &str) -> String {
+        if kinds.iter().any(|kind| kind == "bin") {
+            format!("bin:{}", name)
+        } else {
+            name.to_string()
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct LockjawPackage {
     pub id: String,
     pub name: String,
+    pub version: String,
     pub src_path: String,
     pub direct_prod_crate_deps: Vec<String>,
     pub direct_test_crate_deps: Vec<String>,
+    /// Features of the package resolved by `cargo metadata`, or `None` for the package running the
+    /// build script, whose features are read from the environment.
+    pub features: Option<Vec<String>>,
 }
@@ -15,31 +15,53 @@ limitations under the License.
 */
 
 use crate::attributes;
-use crate::attributes::cfg::CfgEval;
-use crate::build_log::FatalBuildScriptError;
+use crate::attributes::cfg::{Cfg, CfgEval};
+use crate::build_log::{FatalBuildScriptError, SpanData};
+use crate::build_script_fatal;
+use crate::environment;
 use crate::log;
 use crate::manifest::{
-    CfgManifest, ComponentType, DepManifests, LockjawPackage, Manifest, TypeRoot,
+    CfgManifest, Component, ComponentType, DepManifests, LockjawPackage, Manifest, TypeRoot,
+    LOCKJAW_VERSION, MANIFEST_SCHEMA_VERSION,
 };
 use crate::parsing::find_attribute;
+use crate::timings;
 use crate::type_data;
 use crate::type_data::TypeData;
 use anyhow::{bail, Context, Result};
-use proc_macro2::TokenStream;
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
 use serde::Deserialize;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt::{Debug, Formatter};
 use std::fs::File;
 use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::Mutex;
 use syn::__private::ToTokens;
-use syn::{Attribute, Item, ItemUse, Meta, UseTree};
+use syn::parse::discouraged::Speculative;
+use syn::parse::{ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Attribute, Expr, ExprLit, Item, ItemUse, Lit, Meta, Token, UseTree, Visibility};
+
+/// Files read while building the manifest, see [`source_files()`].
+static SOURCE_FILES: Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+
+/// Files read by [`build_manifest()`] so far, which the build script must be rerun for.
+pub fn source_files() -> Vec<PathBuf> {
+    SOURCE_FILES.lock().unwrap().iter().cloned().collect()
+}
+
+fn track_source_file(path: &Path) {
+    SOURCE_FILES.lock().unwrap().insert(path.to_owned());
+}
 
 #[derive(Deserialize, Debug, Default, Clone)]
 struct CargoMetadata {
     packages: Vec<CargoMetadataPackage>,
     resolve: CargoResolve,
+    workspace_root: String,
 }
 
 #[allow(dead_code)]
@@ -47,6 +69,7 @@ struct CargoMetadata {
 struct CargoMetadataPackage {
     name: String,
     id: String,
+    version: String,
     manifest_path: String,
     dependencies: Vec<CargoMetadataDependency>,
     targets: Vec<CargoTarget>,
@@ -75,6 +98,7 @@ struct CargoResolve {
 struct CargoNode {
     id: String,
     deps: Vec<CargoNodeDep>,
+    features: Vec<String>,
 }
 
 #[allow(dead_code)]
@@ -99,18 +123,26 @@ enum LockjawPackageKind {
     Test,
 }
 
-pub fn build_manifest() -> DepManifests {
-    let cargo_output = Command::new("cargo")
-        .arg("metadata")
-        .arg("--manifest-path")
-        .arg(std::env::var("CARGO_MANIFEST_PATH").expect("missing manifest dir"))
-        //.arg("--filter-platform")
-        //.arg(std::env::var("TARGET").expect("missing TARGET"))
-        .arg("--format-version")
-        .arg("1")
-        .arg("--frozen")
-        .output()
-        .unwrap();
+/// Gathers the manifest of the current package and its dependencies. The test manifests are left
+/// empty if `include_tests` is false.
+pub fn build_manifest(include_tests: bool) -> DepManifests {
+    let timings = timings::enabled();
+    let (cargo_output, duration) = timings::time(|| {
+        Command::new("cargo")
+            .arg("metadata")
+            .arg("--manifest-path")
+            .arg(std::env::var("CARGO_MANIFEST_PATH").expect("missing manifest dir"))
+            //.arg("--filter-platform")
+            //.arg(std::env::var("TARGET").expect("missing TARGET"))
+            .arg("--format-version")
+            .arg("1")
+            .arg("--frozen")
+            .output()
+            .unwrap()
+    });
+    if timings {
+        log!("{}", timings::format("cargo metadata", duration));
+    }
 
     let cargo_metadata_json = String::from_utf8(cargo_output.stdout).unwrap();
 
@@ -142,16 +174,39 @@ pub fn build_manifest() -> DepManifests {
     //log!("package_id: {}", package_id);
 
     let toml = toml_map.get(&package_id).unwrap();
+    track_source_file(Path::new(&toml.manifest_path));
+    // new targets are discovered from these directories without changing `Cargo.toml`, and
+    // `cargo update` changes the resolved dependencies. Missing paths are skipped since cargo
+    // always reruns the build script for them.
+    let package_dir = Path::new(&toml.manifest_path).parent().unwrap();
+    for path in [
+        package_dir.join("src").join("bin"),
+        package_dir.join("tests"),
+        package_dir.join("examples"),
+        package_dir.join("benches"),
+        Path::new(&cargo_metadata.workspace_root).join("Cargo.lock"),
+    ] {
+        if path.exists() {
+            track_source_file(&path);
+        }
+    }
     let mut target_packages: HashMap<String, LockjawPackage> = HashMap::new();
     for target in &toml.targets {
         if target.kind == vec!["custom-build".to_string()] {
             continue;
         }
+        if target.kind.contains(&"bin".to_string()) && !invokes_epilogue(&target.src_path) {
+            log!(
+                "{} does not call lockjaw::epilogue!() at its crate root, components will fail to link",
+                target.src_path
+            );
+        }
         target_packages.insert(
-            target.name.clone(),
+            DepManifests::target_key(&target.kind, &target.name),
             LockjawPackage {
                 id: toml.id.clone(),
                 name: toml.name.clone(),
+                version: toml.version.clone(),
                 src_path: target.src_path.clone(),
                 direct_prod_crate_deps: toml
                     .dependencies
@@ -165,14 +220,26 @@ pub fn build_manifest() -> DepManifests {
                     .filter(|dep| dep.kind == Some("dev".to_string()))
                     .map(|dep| dep.name.clone())
                     .collect(),
+                features: None,
             },
         );
     }
     //log!("target packages:{:#?}", target_packages);
 
-    let prod_packages = gather_lockjaw_packages(&package_id, &toml_map, &dep_map, true, false);
+    let mut prod_packages = gather_lockjaw_packages(&package_id, &toml_map, &dep_map, true, false);
     //log!("prod packages:{:#?}", prod_packages);
-    let test_packages = gather_lockjaw_packages(&package_id, &toml_map, &dep_map, true, true);
+    let mut test_packages = if include_tests {
+        gather_lockjaw_packages(&package_id, &toml_map, &dep_map, true, true)
+    } else {
+        Vec::new()
+    };
+    // lockjaw itself provides the modules in `lockjaw::contrib`.
+    if let Some(lockjaw_package) = lockjaw_package(&package_id, &toml_map, &dep_map) {
+        prod_packages.push(lockjaw_package.clone());
+        if include_tests {
+            test_packages.push(lockjaw_package);
+        }
+    }
     //log!("test packages:{:#?}", test_packages);
 
     let mut all_packages: HashSet<LockjawPackage> = HashSet::new();
@@ -188,11 +255,35 @@ pub fn build_manifest() -> DepManifests {
 
     let cfg_manifest_map: HashMap<LockjawPackage, CfgManifest> = all_packages
         .iter()
-        .map(|package| (package.clone(), parse_manifest(package)))
+        .map(|package| {
+            let (manifest, duration) = timings::time(|| parse_package(package, include_tests));
+            if timings {
+                log!(
+                    "{}",
+                    timings::format(
+                        &format!("parse {} ({})", package.name, package.src_path),
+                        duration
+                    )
+                );
+            }
+            (package.clone(), manifest)
+        })
         .collect();
 
-    DepManifests {
+    let dep_manifests = DepManifests {
+        schema_version: MANIFEST_SCHEMA_VERSION,
+        lockjaw_version: LOCKJAW_VERSION.to_string(),
+        crate_lockjaw_versions: all_packages
+            .iter()
+            .filter_map(|package| {
+                Some((
+                    package.name.clone(),
+                    lockjaw_version(&package.id, &toml_map, &dep_map)?,
+                ))
+            })
+            .collect(),
         crate_name: package_name,
+        include_tests,
         prod_manifest: prod_packages
             .iter()
             .map(|package| {
@@ -222,7 +313,127 @@ pub fn build_manifest() -> DepManifests {
                 )
             })
             .collect(),
+        root_kinds: toml
+            .targets
+            .iter()
+            .map(|target| {
+                (
+                    DepManifests::target_key(&target.kind, &target.name),
+                    target.kind.clone(),
+                )
+            })
+            .filter(|(key, _)| target_packages.contains_key(key))
+            .collect(),
+    };
+    for (key, package) in &target_packages {
+        let root_manifest = dep_manifests.root_manifests.get(key).unwrap();
+        if let Err(fatal) = validate_install_in(&dep_manifests, root_manifest, &package.src_path) {
+            fatal.report();
+        }
+    }
+    dep_manifests
+}
+
+/// Reports modules and entry points of a root target that are `install_in` a component declared
+/// with `#[component]`/`#[subcomponent]` instead of its `#[define_...]` counterpart, which would
+/// otherwise only be caught by the root crate that generates the component.
+///
+/// Each target is checked against the components visible to it, since other targets (e.g. other
+/// integration tests) may declare unrelated components of the same name.
+fn validate_install_in(
+    dep_manifests: &DepManifests,
+    root_manifest: &CfgManifest,
+    src_path: &str,
+) -> std::result::Result<(), FatalBuildScriptError> {
+    for (manifest, deps) in [
+        (&root_manifest.prod_manifest, &dep_manifests.prod_manifest),
+        (&root_manifest.test_manifest, &dep_manifests.test_manifest),
+    ] {
+        let components: HashMap<String, &Component> = deps
+            .iter()
+            .chain([manifest])
+            .flat_map(|manifest| manifest.components.iter())
+            .map(|component| (component.type_data.canonical_string_path(), component))
+            .collect();
+        let define_attribute_hint = |type_data: &TypeData| -> Option<&str> {
+            let component = components.get(&type_data.canonical_string_path())?;
+            if component.definition_only {
+                return None;
+            }
+            Some(match component.component_type {
+                ComponentType::Component => "change #[component] to #[define_component]",
+                ComponentType::Subcomponent => "change #[subcomponent] to #[define_subcomponent]",
+            })
+        };
+        for module in &manifest.modules {
+            for install_in in &module.install_in {
+                let Some(hint) = define_attribute_hint(install_in) else {
+                    continue;
+                };
+                let installed = if module.bindings.is_empty() && module.subcomponents.len() == 1 {
+                    format!(
+                        "#[subcomponent] {} has `parent` {}",
+                        module.subcomponents.iter().next().unwrap().readable(),
+                        install_in.readable()
+                    )
+                } else {
+                    format!(
+                        "#[module] {} is `install_in` {}",
+                        module.type_data.readable(),
+                        install_in.readable()
+                    )
+                };
+                return Err(FatalBuildScriptError {
+                    span: SpanData::from_file(&module.source_file),
+                    message: format!(
+                        "{}, but the component is not annotated with #[define_component] or \
+                        #[define_subcomponent], {}",
+                        installed, hint
+                    ),
+                });
+            }
+        }
+        for entry_point in &manifest.entry_points {
+            let Some(hint) = define_attribute_hint(&entry_point.component) else {
+                continue;
+            };
+            return Err(FatalBuildScriptError {
+                span: SpanData::from_file(src_path),
+                message: format!(
+                    "#[entry_point] {} is `install_in` {}, but the component is not annotated \
+                    with #[define_component] or #[define_subcomponent], {}",
+                    entry_point.type_data.readable(),
+                    entry_point.component.readable(),
+                    hint
+                ),
+            });
+        }
     }
+    Ok(())
+}
+
+/// Whether `lockjaw::epilogue!()` is invoked among the top level items of the file.
+fn invokes_epilogue(src_path: &str) -> bool {
+    let Ok(src) = std::fs::read_to_string(src_path) else {
+        // let the compiler report the real error.
+        return true;
+    };
+    let Ok(syn_file) = syn::parse_file(&src) else {
+        return true;
+    };
+    syn_file.items.iter().any(|item| {
+        if let Item::Macro(item_macro) = item {
+            item_macro
+                .mac
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident == "epilogue")
+                .unwrap_or(false)
+        } else {
+            false
+        }
+    })
 }
 
 fn gather_lockjaw_packages(
@@ -259,9 +470,11 @@ fn gather_lockjaw_packages(
         result.push(LockjawPackage {
             id: node.id.clone(),
             name: toml.name.clone(),
+            version: toml.version.clone(),
             src_path: target.src_path.clone(),
             direct_prod_crate_deps,
             direct_test_crate_deps,
+            features: Some(node.features.clone()),
         });
     }
 
@@ -289,87 +502,278 @@ fn gather_lockjaw_packages(
     result
 }
 
+/// Version of the lockjaw package the package `id` depends on.
+fn lockjaw_version(
+    id: &String,
+    toml_map: &HashMap<String, CargoMetadataPackage>,
+    dep_map: &HashMap<String, CargoNode>,
+) -> Option<String> {
+    let lockjaw = dep_map
+        .get(id)?
+        .deps
+        .iter()
+        .find(|dep| dep.name == "lockjaw")?;
+    Some(toml_map.get(&lockjaw.pkg)?.version.clone())
+}
+
+/// The lockjaw package the package `id` depends on.
+fn lockjaw_package(
+    id: &String,
+    toml_map: &HashMap<String, CargoMetadataPackage>,
+    dep_map: &HashMap<String, CargoNode>,
+) -> Option<LockjawPackage> {
+    let lockjaw = dep_map
+        .get(id)?
+        .deps
+        .iter()
+        .find(|dep| dep.name == "lockjaw")?;
+    let toml = toml_map.get(&lockjaw.pkg)?;
+    let node = dep_map.get(&lockjaw.pkg)?;
+    let target = toml
+        .targets
+        .iter()
+        .find(|target| target.kind.contains(&"lib".to_string()))?;
+    Some(LockjawPackage {
+        id: toml.id.clone(),
+        name: toml.name.clone(),
+        version: toml.version.clone(),
+        src_path: target.src_path.clone(),
+        // `extern crate self as lockjaw` lets the sources refer to the crate by its name.
+        direct_prod_crate_deps: vec![toml.name.clone()],
+        direct_test_crate_deps: Vec::new(),
+        features: Some(node.features.clone()),
+    })
+}
+
 pub fn parse_manifest(lockjaw_package: &LockjawPackage) -> CfgManifest {
+    parse_package(lockjaw_package, true)
+}
+
+fn parse_package(lockjaw_package: &LockjawPackage, include_tests: bool) -> CfgManifest {
+    let src_path = Path::new(&lockjaw_package.src_path);
     let result = parse_file(
-        &Path::new(&lockjaw_package.src_path),
+        src_path,
+        src_path.parent().unwrap(),
         "(src)",
         &Vec::new(),
         lockjaw_package,
+        include_tests,
     );
-    result.unwrap_or_else(|err| {
-        if let Some(fatal) = err.downcast_ref::<FatalBuildScriptError>() {
-            let message = fatal.to_string();
-            for m in message.split('\n') {
-                println!("cargo::error={}", m);
+    result
+        .map(|mut manifest| {
+            retain_validated_private_types(&mut manifest.prod_manifest);
+            retain_validated_private_types(&mut manifest.test_manifest);
+            for manifest in [&mut manifest.prod_manifest, &mut manifest.test_manifest] {
+                manifest.crate_versions.insert(
+                    lockjaw_package.name.clone(),
+                    lockjaw_package.version.clone(),
+                );
             }
-            panic!("{}", message);
-        }
-        log!("{}", err);
-        CfgManifest::default()
-    })
+            manifest
+        })
+        .unwrap_or_else(|err| {
+            if let Some(fatal) = err.downcast_ref::<FatalBuildScriptError>() {
+                fatal.report();
+            }
+            log!("{}", err);
+            CfgManifest::default()
+        })
 }
 
+/// Parses the file of a module.
+///
+/// `mod_dir` is the directory the `mod` declarations inside the file are resolved against, which is
+/// the directory of the file for crate roots, `mod.rs` and files loaded with `#[path]`, or
+/// `<dir>/<name>/` otherwise.
 fn parse_file(
     src_path: &Path,
+    mod_dir: &Path,
     name: &str,
     parents: &Vec<String>,
     lockjaw_package: &LockjawPackage,
+    include_tests: bool,
 ) -> Result<CfgManifest> {
     //log!("parsing {}: {:?}", lockjaw_package.name, src_path);
+    // tracked even if missing, so the build script reruns once it is created.
+    track_source_file(src_path);
     let mut src = String::new();
     File::open(src_path)
         .with_context(|| "source  doesn't exist")?
         .read_to_string(&mut src)
         .with_context(|| "unable to read source")?;
 
-    if let Ok(syn_file) = syn::parse_file(&src) {
-        #[cfg(disabled)]
-        if let Ok(out_dir) = std::env::var("OUT_DIR") {
-            let debug_out_name = format!(
-                "{}/{}_{}_{}.json",
-                out_dir.replace('\\', "/"),
-                lockjaw_package.name,
-                parents.join("_"),
-                if name == "(src)" { "" } else { name }
-            );
-            log!("debug ast: file:///{}", &debug_out_name);
-            std::fs::write(&debug_out_name, format!("{:#?}", syn_file)).unwrap();
-        }
-        parse_mods(
+    let items = parse_source(&src, src_path)?;
+    #[cfg(disabled)]
+    if let Ok(out_dir) = std::env::var("OUT_DIR") {
+        let debug_out_name = format!(
+            "{}/{}_{}_{}.json",
+            out_dir.replace('\\', "/"),
+            lockjaw_package.name,
+            parents.join("_"),
+            if name == "(src)" { "" } else { name }
+        );
+        log!("debug ast: file:///{}", &debug_out_name);
+        std::fs::write(&debug_out_name, format!("{:#?}", items)).unwrap();
+    }
+    parse_mods(
+        &ModSource {
+            mod_dir,
+            inline: false,
+            source_file: src_path.to_str().unwrap(),
+            source: &src,
+        },
+        name,
+        &items,
+        parents,
+        &lockjaw_package,
+        include_tests,
+    )
+}
+
+/// Where the items passed to [`parse_mods()`] come from, and how the `mod` declarations among them
+/// are resolved.
+struct ModSource<'a> {
+    /// Directory the `mod` declarations are resolved against.
+    mod_dir: &'a Path,
+    /// Whether the items are inside an inline `mod {}` block, in which case `#[path]` is resolved
+    /// against `mod_dir` instead of the directory of `source_file`.
+    inline: bool,
+    source_file: &'a str,
+    source: &'a str,
+}
+
+/// Parses the items of a source file.
+///
+/// Items syn cannot parse, e.g. syntax newer than the syn lockjaw is built with, are skipped with a
+/// warning so the rest of the crate can still be used, and the whole file is skipped if it cannot
+/// be tokenized. Both fail the build if `LOCKJAW_STRICT_PARSE` is set.
+fn parse_source(src: &str, src_path: &Path) -> Result<Vec<Item>> {
+    if let Ok(syn_file) = syn::parse_file(src) {
+        return Ok(syn_file.items);
+    }
+    let Ok(tokens) = src.parse::<TokenStream>() else {
+        parse_failure(
             src_path,
-            name,
-            &syn_file.items,
-            parents,
-            &lockjaw_package,
-            src_path.to_str().unwrap(),
-            &src,
-        )
-    } else {
-        bail!("{} is not valid rust", src_path.to_str().unwrap());
+            &format!(
+                "lockjaw is unable to parse {}, bindings in the file are ignored",
+                src_path.display()
+            ),
+        )?;
+        return Ok(Vec::new());
+    };
+    let (items, errors) = parse_items_lossy.parse2(tokens)?;
+    for error in errors {
+        let start = error.span().start();
+        parse_failure(
+            src_path,
+            &format!(
+                "lockjaw is unable to parse the item at {}:{}:{}, bindings in the item are \
+                ignored: {}",
+                src_path.display(),
+                start.line,
+                start.column + 1,
+                error
+            ),
+        )?;
     }
+    Ok(items)
+}
+
+/// Parses items one by one, skipping to the end of the item (the next `;` or `{}` block) when one
+/// fails to parse.
+fn parse_items_lossy(input: ParseStream) -> syn::Result<(Vec<Item>, Vec<syn::Error>)> {
+    input.call(Attribute::parse_inner)?;
+    let mut items = Vec::new();
+    let mut errors = Vec::new();
+    while !input.is_empty() {
+        let fork = input.fork();
+        match fork.parse::<Item>() {
+            Ok(item) => {
+                input.advance_to(&fork);
+                items.push(item);
+            }
+            Err(error) => {
+                errors.push(error);
+                input.step(|cursor| {
+                    let mut rest = *cursor;
+                    while let Some((token, next)) = rest.token_tree() {
+                        rest = next;
+                        match token {
+                            TokenTree::Punct(punct) if punct.as_char() == ';' => break,
+                            TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
+                                if let Some((TokenTree::Punct(punct), next)) = rest.token_tree() {
+                                    if punct.as_char() == ';' {
+                                        rest = next;
+                                    }
+                                }
+                                break;
+                            }
+                            _ => {}
+                        }
+                    }
+                    Ok(((), rest))
+                })?;
+            }
+        }
+    }
+    Ok((items, errors))
+}
+
+/// Warns about source skipped by [`parse_source()`], or fails the build if `LOCKJAW_STRICT_PARSE`
+/// is set.
+fn parse_failure(src_path: &Path, message: &str) -> Result<()> {
+    if environment::strict_parse() {
+        return Err(FatalBuildScriptError {
+            span: SpanData::from_file(&src_path.display().to_string()),
+            message: message.to_string(),
+        }
+        .into());
+    }
+    log!(
+        "{} (set {}=1 to fail the build instead)",
+        message,
+        environment::LOCKJAW_STRICT_PARSE_ENV
+    );
+    Ok(())
 }
 
 fn parse_mods(
-    src_path: &Path,
+    mod_source: &ModSource,
     name: &str,
     items: &Vec<Item>,
     parents: &Vec<String>,
     lockjaw_package: &LockjawPackage,
-    source_file: &str,
-    source: &str,
+    include_tests: bool,
 ) -> Result<CfgManifest> {
+    let source_file = mod_source.source_file;
+    let source = mod_source.source;
+    let items = &expand_item_macros(items, Path::new(source_file).parent().unwrap())?;
     let mut new_parents = parents.clone();
     if name.ne("(src)") {
         new_parents.push(name.to_owned());
     }
 
-    let prod_uses = get_uses(items, lockjaw_package, &new_parents, false)?;
-    let test_uses = get_uses(items, lockjaw_package, &new_parents, true)?;
+    let prod_cfg = Cfg {
+        test: false,
+        features: lockjaw_package.features.as_deref(),
+    };
+    let test_cfg = Cfg {
+        test: true,
+        ..prod_cfg
+    };
+    let prod_uses = get_uses(items, lockjaw_package, &new_parents, &prod_cfg)?;
+    let test_uses = if include_tests {
+        get_uses(items, lockjaw_package, &new_parents, &test_cfg)?
+    } else {
+        HashMap::new()
+    };
+    let declared_types = declared_types(items);
     let prod_mod = Mod {
         crate_name: lockjaw_package.name.clone(),
         name: name.to_owned(),
         parents: parents.clone(),
         uses: prod_uses,
+        declared_types: declared_types.clone(),
         source_file,
         source,
     };
@@ -378,6 +782,7 @@ fn parse_mods(
         name: name.to_owned(),
         parents: parents.clone(),
         uses: test_uses,
+        declared_types,
         source_file: source_file,
         source: source,
     };
@@ -385,9 +790,15 @@ fn parse_mods(
     let mut result = CfgManifest::default();
     for item in items.iter() {
         let attrs = item_attrs(item);
+        let prod_attrs = expand_cfg_attr(&attrs, &prod_cfg)?;
+        let test_attrs = if include_tests {
+            expand_cfg_attr(&attrs, &test_cfg)?
+        } else {
+            Vec::new()
+        };
 
-        let for_prod = for_cfg(&attrs, false)?;
-        let for_test = for_cfg(&attrs, true)?;
+        let for_prod = for_cfg(&prod_attrs, &prod_cfg)?;
+        let for_test = include_tests && for_cfg(&test_attrs, &test_cfg)?;
 
         if !for_prod && !for_test {
             continue;
@@ -395,13 +806,12 @@ fn parse_mods(
 
         if let Item::Mod(item_mod) = item {
             let mod_manifests = &parse_mod_item(
-                src_path,
-                name,
+                mod_source,
                 item_mod,
                 &new_parents,
                 lockjaw_package,
-                source_file,
-                source,
+                &prod_mod,
+                include_tests,
             )?;
             if for_prod {
                 result
@@ -418,17 +828,273 @@ fn parse_mods(
         if for_prod {
             result
                 .prod_manifest
-                .merge_from(&parse_item(item, &attrs, &prod_mod)?);
+                .merge_from(&parse_item(item, &prod_attrs, &prod_mod)?);
         }
         if for_test {
             result
                 .test_manifest
-                .merge_from(&parse_item(item, &attrs, &test_mod)?);
+                .merge_from(&parse_item(item, &test_attrs, &test_mod)?);
+        }
+        if let Some(private_type) = private_struct(item, &prod_attrs, &prod_mod)? {
+            if for_prod {
+                result
+                    .prod_manifest
+                    .private_types
+                    .insert(private_type.clone());
+            }
+            if for_test {
+                result.test_manifest.private_types.insert(private_type);
+            }
+        }
+        for alias in type_aliases(item, &prod_mod)? {
+            if for_prod {
+                result.prod_manifest.type_aliases.insert(alias.clone());
+            }
+            if for_test {
+                result.test_manifest.type_aliases.insert(alias);
+            }
+        }
+        if !matches!(item, Item::Mod(_)) {
+            if for_prod {
+                result
+                    .prod_manifest
+                    .instantiated_components
+                    .extend(find_instantiated_components(
+                        item.to_token_stream(),
+                        &prod_mod,
+                    ));
+            }
+            if for_test {
+                result
+                    .test_manifest
+                    .instantiated_components
+                    .extend(find_instantiated_components(
+                        item.to_token_stream(),
+                        &test_mod,
+                    ));
+            }
+        }
+    }
+    for item in items.iter() {
+        let Item::Struct(item_struct) = item else {
+            continue;
+        };
+        let type_data = type_data::from_local(&item_struct.ident.to_string(), &prod_mod)?;
+        let interior_mutability = item_struct
+            .fields
+            .iter()
+            .any(|field| has_interior_mutability(field.ty.to_token_stream()));
+        for manifest in [&mut result.prod_manifest, &mut result.test_manifest] {
+            for injectable in manifest.injectables.iter_mut() {
+                if injectable.type_data.canonical_string_path_without_args()
+                    == type_data.canonical_string_path_without_args()
+                {
+                    injectable.interior_mutability = Some(interior_mutability);
+                }
+            }
         }
     }
     Ok(result)
 }
 
+/// Types that can be mutated through a shared reference. Scoped bindings holding them are
+/// expected to be shared, so `epilogue!(lint_stateless_scoped)` does not flag them.
+const INTERIOR_MUTABLE_TYPES: &[&str] = &[
+    "Cell",
+    "RefCell",
+    "UnsafeCell",
+    "OnceCell",
+    "Mutex",
+    "RwLock",
+    "Once",
+    "OnceLock",
+    "LazyCell",
+    "LazyLock",
+    "Lazy",
+    "Condvar",
+    "Sender",
+    "Receiver",
+    "SyncSender",
+];
+
+/// Whether a field type mentions any [`INTERIOR_MUTABLE_TYPES`] or atomic, including in generic
+/// arguments such as `Rc<RefCell<T>>`.
+fn has_interior_mutability(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Group(group) => has_interior_mutability(group.stream()),
+        TokenTree::Ident(ident) => {
+            let ident = ident.to_string();
+            ident.starts_with("Atomic") || INTERIOR_MUTABLE_TYPES.contains(&ident.as_str())
+        }
+        _ => false,
+    })
+}
+
+/// Returns the struct if it is declared in a nested mod, and not visible to the crate root.
+///
+/// Only the visibility of the struct itself is checked, not the mods it is declared in.
+fn private_struct(item: &Item, attrs: &Vec<Attribute>, mod_: &Mod) -> Result<Option<TypeData>> {
+    let Item::Struct(item_struct) = item else {
+        return Ok(None);
+    };
+    if mod_.name == "(src)" {
+        return Ok(None);
+    }
+    if attrs
+        .iter()
+        .any(|attr| attr.path().segments.last().unwrap().ident == "component_visible")
+    {
+        return Ok(None);
+    }
+    let visible = match item_struct.vis {
+        Visibility::Public(_) => true,
+        Visibility::Restricted(ref restricted) => restricted.path.is_ident("crate"),
+        Visibility::Inherited => false,
+    };
+    if visible {
+        return Ok(None);
+    }
+    Ok(Some(type_data::from_local(
+        &item_struct.ident.to_string(),
+        mod_,
+    )?))
+}
+
+/// Types `item` names in the mod without declaring them, i.e. `type` aliases and renamed `use`s.
+fn type_aliases(item: &Item, mod_: &Mod) -> Result<Vec<TypeData>> {
+    let names = match item {
+        Item::Type(item_type) => vec![item_type.ident.to_string()],
+        Item::Use(item_use) => use_renames(&item_use.tree),
+        _ => Vec::new(),
+    };
+    names
+        .iter()
+        .filter(|name| name.as_str() != "_")
+        .map(|name| type_data::from_local(name, mod_))
+        .collect()
+}
+
+/// Names of the types declared directly in the mod.
+fn declared_types(items: &Vec<Item>) -> HashSet<String> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Struct(item_struct) => Some(&item_struct.ident),
+            Item::Enum(item_enum) => Some(&item_enum.ident),
+            Item::Union(item_union) => Some(&item_union.ident),
+            Item::Type(item_type) => Some(&item_type.ident),
+            Item::Trait(item_trait) => Some(&item_trait.ident),
+            _ => None,
+        })
+        .map(|ident| ident.to_string())
+        .collect()
+}
+
+fn use_renames(tree: &UseTree) -> Vec<String> {
+    match tree {
+        UseTree::Path(path) => use_renames(&path.tree),
+        UseTree::Rename(rename) => vec![rename.rename.to_string()],
+        UseTree::Group(group) => group.items.iter().flat_map(use_renames).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Drops private structs not used by `#[builder_modules]` or as an `#[injectable]` container,
+/// which are the only ones validated.
+fn retain_validated_private_types(manifest: &mut Manifest) {
+    let validated_types: HashSet<TypeData> = manifest
+        .builder_modules
+        .iter()
+        .flat_map(|builder_modules| builder_modules.builder_modules.iter())
+        .map(|dependency| dependency.type_data.clone())
+        .chain(
+            manifest
+                .injectables
+                .iter()
+                .filter_map(|injectable| injectable.container.clone()),
+        )
+        .collect();
+    manifest
+        .private_types
+        .retain(|private_type| validated_types.contains(private_type));
+}
+
+/// Inlines items hidden behind macros that the build script can still read: files included with
+/// `include!()`, and declarations in `lockjaw::register_manifest!{}`.
+///
+/// Other macros are left as is, and items they generate are not visible to lockjaw.
+fn expand_item_macros(items: &Vec<Item>, source_dir: &Path) -> Result<Vec<Item>> {
+    let mut result = Vec::new();
+    for item in items {
+        let Item::Macro(item_macro) = item else {
+            result.push(item.clone());
+            continue;
+        };
+        let macro_name = item_macro
+            .mac
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+            .unwrap_or_default();
+        match macro_name.as_str() {
+            "include" => {
+                let Some(path) = include_path(item_macro.mac.tokens.clone()) else {
+                    result.push(item.clone());
+                    continue;
+                };
+                let path = source_dir.join(path);
+                track_source_file(&path);
+                let mut src = String::new();
+                File::open(&path)
+                    .with_context(|| format!("included file {} doesn't exist", path.display()))?
+                    .read_to_string(&mut src)
+                    .with_context(|| "unable to read source")?;
+                let items = parse_source(&src, &path)?;
+                result.extend(expand_item_macros(&items, path.parent().unwrap())?);
+            }
+            "register_manifest" => {
+                let file: syn::File = syn::parse2(item_macro.mac.tokens.clone())
+                    .with_context(|| "register_manifest!{} must contain items")?;
+                result.extend(expand_item_macros(&file.items, source_dir)?);
+            }
+            _ => result.push(item.clone()),
+        }
+    }
+    Ok(result)
+}
+
+/// Path in `include!()`, either a string literal or `concat!()` of string literals and `env!()`.
+///
+/// The build script shares the environment of the crate being compiled, so paths like
+/// `concat!(env!("OUT_DIR"), "/generated.rs")` can be resolved.
+fn include_path(tokens: TokenStream) -> Option<String> {
+    let expr: Expr = syn::parse2(tokens).ok()?;
+    include_path_part(&expr)
+}
+
+fn include_path_part(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) => Some(lit.value()),
+        Expr::Macro(expr_macro) => {
+            let args = expr_macro
+                .mac
+                .parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated)
+                .ok()?;
+            if expr_macro.mac.path.is_ident("concat") {
+                args.iter().map(include_path_part).collect()
+            } else if expr_macro.mac.path.is_ident("env") && args.len() == 1 {
+                std::env::var(include_path_part(args.first()?)?).ok()
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
 fn parse_item(item: &Item, attrs: &Vec<Attribute>, mod_: &Mod) -> Result<Manifest> {
     let mut item_result = Manifest::new();
     for attribute in attrs.iter() {
@@ -486,6 +1152,15 @@ fn parse_item(item: &Item, attrs: &Vec<Attribute>, mod_: &Mod) -> Result<Manifes
                     &mod_,
                 )?);
             }
+            "::lockjaw::component_fragment" => {
+                item_result.merge_from(
+                    &attributes::components::handle_component_fragment_attribute(
+                        attribute.parse_args().unwrap_or(TokenStream::new()),
+                        item.to_token_stream(),
+                        &mod_,
+                    )?,
+                );
+            }
             "::lockjaw::builder_modules" => {
                 item_result.merge_from(&attributes::components::handle_builder_modules_attribute(
                     attribute.parse_args().unwrap_or(TokenStream::new()),
@@ -520,10 +1195,67 @@ fn parse_item(item: &Item, attrs: &Vec<Attribute>, mod_: &Mod) -> Result<Manifes
     Ok(item_result)
 }
 
-fn for_cfg(attrs: &Vec<Attribute>, cfg_test: bool) -> Result<bool> {
-    if let Some(cfg) = find_attribute(&attrs, "cfg") {
-        if let Meta::List(meta_list) = &cfg.meta {
-            if !attributes::cfg::handle_cfg(meta_list)?.eval(cfg_test) {
+/// Finds `<dyn Component>::new`/`<dyn Component>::build`/`<dyn Component>::try_build` in the tokens,
+/// so components never created can be pruned.
+fn find_instantiated_components(tokens: TokenStream, mod_: &Mod) -> HashSet<TypeData> {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut result = HashSet::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if let TokenTree::Group(group) = token {
+            result.extend(find_instantiated_components(group.stream(), mod_));
+            continue;
+        }
+        if !is_punct(Some(token), '<') {
+            continue;
+        }
+        if !matches!(tokens.get(i + 1), Some(TokenTree::Ident(ident)) if ident == "dyn") {
+            continue;
+        }
+        // generic components have nested angle brackets, e.g. `<dyn Repo<User>>::new()`.
+        let mut depth = 0;
+        let Some(end) = tokens[i + 2..]
+            .iter()
+            .position(|t| {
+                if is_punct(Some(t), '<') {
+                    depth += 1;
+                } else if is_punct(Some(t), '>') {
+                    if depth == 0 {
+                        return true;
+                    }
+                    depth -= 1;
+                }
+                false
+            })
+            .map(|p| p + i + 2)
+        else {
+            continue;
+        };
+        if !is_punct(tokens.get(end + 1), ':') || !is_punct(tokens.get(end + 2), ':') {
+            continue;
+        }
+        if !matches!(tokens.get(end + 3), Some(TokenTree::Ident(ident))
+            if ident == "new" || ident == "build" || ident == "try_build")
+        {
+            continue;
+        }
+        let path_tokens: TokenStream = tokens[i + 2..end].iter().cloned().collect();
+        if let Ok(path) = syn::parse2::<syn::Path>(path_tokens) {
+            if let Ok(type_) = type_data::from_path(&path, mod_) {
+                result.insert(type_);
+            }
+        }
+    }
+    result
+}
+
+fn is_punct(token: Option<&TokenTree>, c: char) -> bool {
+    matches!(token, Some(TokenTree::Punct(punct)) if punct.as_char() == c)
+}
+
+fn for_cfg(attrs: &Vec<Attribute>, cfg: &Cfg) -> Result<bool> {
+    if let Some(cfg_attr) = find_attribute(&attrs, "cfg") {
+        if let Meta::List(meta_list) = &cfg_attr.meta {
+            if !attributes::cfg::handle_cfg(meta_list)?.eval(cfg) {
                 return Ok(false);
             }
         } else {
@@ -533,6 +1265,36 @@ fn for_cfg(attrs: &Vec<Attribute>, cfg_test: bool) -> Result<bool> {
     Ok(true)
 }
 
+/// Replaces `#[cfg_attr(predicate, attrs...)]` with `attrs` if the predicate holds, or drops it
+/// otherwise, so lockjaw attributes only applied under some cfg are still found.
+fn expand_cfg_attr(attrs: &Vec<Attribute>, cfg: &Cfg) -> Result<Vec<Attribute>> {
+    let mut result = Vec::new();
+    for attr in attrs {
+        if !attr.path().is_ident("cfg_attr") {
+            result.push(attr.clone());
+            continue;
+        }
+        let Meta::List(meta_list) = &attr.meta else {
+            bail!("cfg_attr attribute is not a list");
+        };
+        let cfg_attr = attributes::cfg::handle_cfg_attr(meta_list)?;
+        if !cfg_attr.predicate.eval(cfg) {
+            continue;
+        }
+        let expanded: Vec<Attribute> = cfg_attr
+            .attrs
+            .into_iter()
+            .map(|meta| Attribute {
+                meta,
+                ..attr.clone()
+            })
+            .collect();
+        // cfg_attr can be nested, e.g. `#[cfg_attr(a, cfg_attr(b, injectable))]`
+        result.extend(expand_cfg_attr(&expanded, cfg)?);
+    }
+    Ok(result)
+}
+
 fn item_attrs(item: &Item) -> Vec<Attribute> {
     match item {
         Item::Const(i) => i.attrs.clone(),
@@ -555,64 +1317,119 @@ fn item_attrs(item: &Item) -> Vec<Attribute> {
     }
 }
 
+/// Parses a `mod` declaration, loading the file of the module if it is not inline.
 fn parse_mod_item(
-    src_path: &Path,
-    parent_name: &str,
+    mod_source: &ModSource,
     item_mod: &syn::ItemMod,
     parents: &Vec<String>,
     lockjaw_package: &LockjawPackage,
-    source_file: &str,
-    source: &str,
+    mod_: &Mod,
+    include_tests: bool,
 ) -> Result<CfgManifest> {
     let mut result = CfgManifest::default();
+    let mod_dir = mod_source.mod_dir;
     let mod_name = item_mod.ident.to_string();
+    let path_attribute = mod_path_attribute(item_mod, mod_)?.map(|path| {
+        if mod_source.inline {
+            mod_dir.join(path)
+        } else {
+            Path::new(mod_.source_file).parent().unwrap().join(path)
+        }
+    });
     if let Some((_, items)) = &item_mod.content {
         result.merge_from(&parse_mods(
-            src_path,
+            &ModSource {
+                mod_dir: &path_attribute.unwrap_or_else(|| mod_dir.join(&mod_name)),
+                inline: true,
+                source_file: mod_.source_file,
+                source: mod_.source,
+            },
             &mod_name,
             items,
             &parents,
             lockjaw_package,
-            source_file,
-            source,
+            include_tests,
         )?);
-    } else {
-        let mut dir = Path::new(&lockjaw_package.src_path)
-            .parent()
-            .unwrap()
-            .to_owned();
-        for package in parents {
-            dir = dir.join(package)
-        }
-        let candidates = vec![
-            dir.join(format!("{}.rs", mod_name)),
-            dir.join(format!("{}/{}.rs", parent_name, mod_name)),
-            dir.join(format!("{}/mod.rs", mod_name)),
-        ];
-        let mod_path = candidates
-            .iter()
-            .find(|path| path.exists())
-            .expect(&format!("cannot find any of {:?}", candidates));
-        let mut mod_parents = parents.clone();
-        if parent_name.ne("(src)") {
-            mod_parents.push(parent_name.to_owned());
+    } else if let Some(mod_path) = path_attribute {
+        if !mod_path.exists() {
+            build_script_fatal!(
+                item_mod.span(),
+                mod_,
+                "file not found for module `{}`, expected {}",
+                mod_name,
+                mod_path.display()
+            )
         }
+        result.merge_from(&parse_file(
+            &mod_path,
+            mod_path.parent().unwrap(),
+            &mod_name,
+            parents,
+            lockjaw_package,
+            include_tests,
+        )?);
+    } else {
+        let file = mod_dir.join(format!("{}.rs", mod_name));
+        let mod_rs = mod_dir.join(&mod_name).join("mod.rs");
+        let mod_path = match (file.exists(), mod_rs.exists()) {
+            (true, false) => file,
+            (false, true) => mod_rs,
+            (true, true) => build_script_fatal!(
+                item_mod.span(),
+                mod_,
+                "file for module `{}` found at both {} and {}",
+                mod_name,
+                file.display(),
+                mod_rs.display()
+            ),
+            (false, false) => build_script_fatal!(
+                item_mod.span(),
+                mod_,
+                "file not found for module `{}`, expected {} or {}",
+                mod_name,
+                file.display(),
+                mod_rs.display()
+            ),
+        };
 
         result.merge_from(&parse_file(
-            mod_path,
+            &mod_path,
+            &mod_dir.join(&mod_name),
             &mod_name,
-            &mod_parents,
+            parents,
             lockjaw_package,
+            include_tests,
         )?);
     }
     Ok(result)
 }
 
+/// Value of `#[path = "..."]` on the `mod` declaration.
+fn mod_path_attribute(item_mod: &syn::ItemMod, mod_: &Mod) -> Result<Option<String>> {
+    let Some(attr) = find_attribute(&item_mod.attrs, "path") else {
+        return Ok(None);
+    };
+    if let Meta::NameValue(name_value) = &attr.meta {
+        if let Expr::Lit(ExprLit {
+            lit: Lit::Str(path),
+            ..
+        }) = &name_value.value
+        {
+            return Ok(Some(path.value()));
+        }
+    }
+    build_script_fatal!(
+        attr.span(),
+        mod_,
+        "#[path] attribute on mod expects a string literal"
+    )
+}
+
 fn get_uses(
     items: &Vec<Item>,
     lockjaw_package: &LockjawPackage,
     parents: &Vec<String>,
-    cfg_test: bool,
+    cfg: &Cfg,
 ) -> Result<HashMap<String, UsePath>> {
     let mut deps = HashSet::new();
 
@@ -620,7 +1437,7 @@ fn get_uses(
         deps.insert(dep.clone());
     }
 
-    if cfg_test {
+    if cfg.test {
         for dep in &lockjaw_package.direct_test_crate_deps {
             deps.insert(dep.clone());
         }
@@ -630,10 +1447,10 @@ fn get_uses(
     deps.insert("core".to_owned());
     let mut result = HashMap::<String, UsePath>::new();
     for item in items.iter() {
-        let attrs = item_attrs(item);
-        if let Some(cfg) = find_attribute(&attrs, "cfg") {
-            if let Meta::List(meta_list) = &cfg.meta {
-                if !attributes::cfg::handle_cfg(meta_list)?.eval(cfg_test) {
+        let attrs = expand_cfg_attr(&item_attrs(item), cfg)?;
+        if let Some(cfg_attr) = find_attribute(&attrs, "cfg") {
+            if let Meta::List(meta_list) = &cfg_attr.meta {
+                if !attributes::cfg::handle_cfg(meta_list)?.eval(cfg) {
                     continue;
                 }
             }
@@ -666,6 +1483,8 @@ pub struct Mod<'a> {
     pub name: String,
     pub parents: Vec<String>,
     pub uses: HashMap<String, UsePath>,
+    /// Types declared directly in the mod, see [`declared_types()`].
+    pub declared_types: HashSet<String>,
     pub source_file: &'a str,
     pub source: &'a str,
 }
@@ -17,8 +17,8 @@ limitations under the License.
 use crate::manifest_parser::Mod;
 use crate::type_data::TypeData;
 use anyhow::{bail, Context, Result};
-use proc_macro2::TokenStream;
-use std::collections::HashMap;
+use proc_macro2::{TokenStream, TokenTree};
+use std::collections::{HashMap, HashSet};
 use syn::parse::Parser;
 #[allow(unused_imports)] // somehow rust think this is unused.
 use syn::spanned::Spanned;
@@ -51,6 +51,48 @@ pub fn has_attribute(attrs: &Vec<Attribute>, attr: &str) -> bool {
     attrs.iter().find(|a| is_attribute(a, attr)).is_some()
 }
 
+/// Names of the type and const parameters of `generics`, which lockjaw cannot choose a type for.
+pub fn generic_type_params(generics: &syn::Generics) -> HashSet<String> {
+    generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            syn::GenericParam::Type(type_param) => Some(type_param.ident.to_string()),
+            syn::GenericParam::Const(const_param) => Some(const_param.ident.to_string()),
+            syn::GenericParam::Lifetime(_) => None,
+        })
+        .collect()
+}
+
+/// Whether any identifier in `tokens` is one of `idents`.
+pub fn mentions_idents(tokens: TokenStream, idents: &HashSet<String>) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => idents.contains(&ident.to_string()),
+        TokenTree::Group(group) => mentions_idents(group.stream(), idents),
+        _ => false,
+    })
+}
+
+/// Doc comments (`#[doc = "..."]`) of an item, one line each.
+pub fn get_doc(attrs: &Vec<Attribute>) -> String {
+    attrs
+        .iter()
+        .filter_map(|attr| match attr.meta {
+            Meta::NameValue(ref name_value) if name_value.path.is_ident("doc") => {
+                match name_value.value {
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(ref lit),
+                        ..
+                    }) => Some(lit.value()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
 pub fn get_parenthesized_field_values(meta: &Meta) -> Result<HashMap<String, FieldValue>> {
     match meta {
         Meta::Path(_) => Ok(HashMap::new()),
@@ -88,6 +130,8 @@ pub enum FieldValue {
     Path(syn::Path),
     Array(Vec<FieldValue>),
     FieldValues(HashMap<String, FieldValue>),
+    /// Any other expression, e.g. `key: MyKey::new("a", 3)`.
+    Expr(syn::Expr),
 }
 
 impl FieldValue {
@@ -146,6 +190,51 @@ fn parse_punctuated_field_values(
     Ok(result)
 }
 
+/// Removes `key: [Type, ...]` from the `key: value` pairs of an attribute and parses the types,
+/// since types like `dyn Foo` are not expressions and cannot be parsed as a `FieldValue`.
+pub fn take_type_array(attr: TokenStream, key: &str) -> Result<(TokenStream, Vec<syn::Type>)> {
+    let tokens: Vec<TokenTree> = attr.into_iter().collect();
+    for i in 0..tokens.len() {
+        let TokenTree::Ident(ref ident) = tokens[i] else {
+            continue;
+        };
+        if ident != key || (i > 0 && !is_comma(&tokens[i - 1])) {
+            continue;
+        }
+        let Some(TokenTree::Punct(colon)) = tokens.get(i + 1) else {
+            continue;
+        };
+        if colon.as_char() != ':' {
+            continue;
+        }
+        let Some(TokenTree::Group(group)) = tokens.get(i + 2) else {
+            bail!("[type, ...] expected for {}", key);
+        };
+        if group.delimiter() != proc_macro2::Delimiter::Bracket {
+            bail!("[type, ...] expected for {}", key);
+        }
+        let types = syn::punctuated::Punctuated::<syn::Type, syn::Token![,]>::parse_terminated
+            .parse2(group.stream())
+            .with_context(|| format!("[type, ...] expected for {}", key))?;
+        let end = if tokens.get(i + 3).is_some_and(is_comma) {
+            i + 4
+        } else {
+            i + 3
+        };
+        let remaining: TokenStream = tokens[..i]
+            .iter()
+            .chain(tokens[end..].iter())
+            .cloned()
+            .collect();
+        return Ok((remaining, types.into_iter().collect()));
+    }
+    Ok((tokens.into_iter().collect(), Vec::new()))
+}
+
+fn is_comma(token: &TokenTree) -> bool {
+    matches!(token, TokenTree::Punct(punct) if punct.as_char() == ',')
+}
+
 fn parse_field_value(expr: &syn::Expr) -> Result<FieldValue> {
     match expr {
         syn::Expr::Lit(ref lit) => match lit.lit {
@@ -162,6 +251,15 @@ fn parse_field_value(expr: &syn::Expr) -> Result<FieldValue> {
             )),
             _ => bail!("unable to handle literal value {:?}", lit),
         },
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr: ref inner,
+            ..
+        }) => match parse_field_value(inner)? {
+            FieldValue::IntLiteral(value) => Ok(FieldValue::IntLiteral(-value)),
+            FieldValue::FloatLiteral(value) => Ok(FieldValue::FloatLiteral(-value)),
+            _ => Ok(FieldValue::Expr(expr.clone())),
+        },
         syn::Expr::Path(ref path) => Ok(FieldValue::Path(path.path.clone())),
         syn::Expr::Array(ref array) => {
             let mut values: Vec<FieldValue> = Vec::new();
@@ -173,7 +271,7 @@ fn parse_field_value(expr: &syn::Expr) -> Result<FieldValue> {
         syn::Expr::Struct(ref struct_) => Ok(FieldValue::FieldValues(
             parse_punctuated_field_values(&struct_.fields)?,
         )),
-        _ => bail!("invalid field value {:?}", expr),
+        _ => Ok(FieldValue::Expr(expr.clone())),
     }
 }
 
@@ -0,0 +1,269 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Read-only view of the bindings gathered by `lockjaw::build_script()`, for writing custom
+//! architecture checks in `build.rs` or CI binaries.
+//!
+//! Types are reported in their canonical form (`::crate_name::path::Type`), which is stable across
+//! lockjaw versions unlike the manifest file itself.
+
+use crate::manifest::{
+    BindingType, ComponentType, DepManifests, Manifest, MultibindingType, MANIFEST_SCHEMA_VERSION,
+};
+use crate::type_data::TypeData;
+use anyhow::{bail, Context, Result};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// Merged manifest of a cargo target, as seen by its `epilogue!()`.
+#[derive(Debug, Clone)]
+pub struct ManifestQuery {
+    crate_name: String,
+    manifest: Manifest,
+}
+
+/// A `#[component]`, `#[subcomponent]` or their `#[define_...]` counterparts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentInfo {
+    pub path: String,
+    pub subcomponent: bool,
+    /// Declared with `#[define_component]`/`#[define_subcomponent]`.
+    pub definition_only: bool,
+    /// Modules listed in the component, and modules `install_in` it.
+    pub modules: Vec<String>,
+    /// The component itself and the scopes it claims with `scope_groups`.
+    pub scopes: Vec<String>,
+    /// Types returned by the provisions of the component.
+    pub provisions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BindingKind {
+    Injectable,
+    Provides,
+    Binds,
+    BindsOptionOf,
+    Multibinds,
+    Decorator,
+}
+
+/// A binding declared by an `#[injectable]` or a `#[module]` method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindingInfo {
+    /// The bound type, including its qualifier as `#[qualified(Q)] Type`.
+    pub type_: String,
+    pub kind: BindingKind,
+    /// Whether the binding contributes to a `Vec` or `HashMap` multibinding.
+    pub multibinding: bool,
+    /// The injectable type or the module declaring the binding.
+    pub declared_in: String,
+    pub scopes: Vec<String>,
+    pub dependencies: Vec<String>,
+    pub source_file: String,
+}
+
+impl ManifestQuery {
+    /// Gathers the bindings of the current package like `lockjaw::build_script()` does. Can only be
+    /// called inside `build.rs`.
+    ///
+    /// `target` is the cargo target name, `for_test` selects the `#[cfg(test)]` graph.
+    pub fn from_build_script(target: &str, for_test: bool) -> Result<ManifestQuery> {
+        let dep_manifests = crate::manifest_parser::build_manifest(for_test);
+        ManifestQuery::from_dep_manifests(&dep_manifests, target, for_test)
+    }
+
+    /// Loads the manifest written by `lockjaw::build_script()`. Its path is passed to the crate as
+    /// the `LOCKJAW_DEP_MANIFEST` environment variable, i.e. `env!("LOCKJAW_DEP_MANIFEST")`.
+    pub fn load(path: impl AsRef<Path>, target: &str, for_test: bool) -> Result<ManifestQuery> {
+        let path = path.as_ref();
+        let reader = BufReader::new(
+            File::open(path).with_context(|| format!("cannot open {}", path.display()))?,
+        );
+        let json: serde_json::Value =
+            serde_json::from_reader(reader).with_context(|| "cannot read manifest")?;
+        let schema_version = json.get("schema_version").and_then(|v| v.as_u64());
+        if schema_version != Some(MANIFEST_SCHEMA_VERSION as u64) {
+            bail!(
+                "{} is written by lockjaw {}, which is not readable by lockjaw {}",
+                path.display(),
+                json.get("lockjaw_version")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("(unknown)"),
+                crate::manifest::LOCKJAW_VERSION
+            );
+        }
+        let dep_manifests: DepManifests = serde_json::from_value(json)?;
+        ManifestQuery::from_dep_manifests(&dep_manifests, target, for_test)
+    }
+
+    fn from_dep_manifests(
+        dep_manifests: &DepManifests,
+        target: &str,
+        for_test: bool,
+    ) -> Result<ManifestQuery> {
+        let use_test_manifest = for_test && dep_manifests.include_tests;
+        let normalized = target.replace('-', "_");
+        let Some(root_manifest) = dep_manifests
+            .root_manifests
+            .iter()
+            .find(|(key, _)| {
+                let name = key.strip_prefix("bin:").unwrap_or(key);
+                name.replace('-', "_") == normalized
+            })
+            .map(|(_, manifest)| manifest)
+        else {
+            bail!(
+                "target {} not found in the lockjaw manifest of {}",
+                target,
+                dep_manifests.crate_name
+            );
+        };
+        let mut manifest = Manifest::new();
+        if use_test_manifest {
+            for dep in &dep_manifests.test_manifest {
+                manifest.merge_from(dep);
+            }
+            manifest.merge_from(&root_manifest.test_manifest);
+        } else {
+            for dep in &dep_manifests.prod_manifest {
+                manifest.merge_from(dep);
+            }
+            manifest.merge_from(&root_manifest.prod_manifest);
+        }
+        Ok(ManifestQuery {
+            crate_name: dep_manifests.crate_name.clone(),
+            manifest,
+        })
+    }
+
+    pub fn crate_name(&self) -> &str {
+        &self.crate_name
+    }
+
+    pub fn components(&self) -> Vec<ComponentInfo> {
+        self.manifest
+            .components
+            .iter()
+            .map(|component| {
+                let mut modules: Vec<String> = component
+                    .modules
+                    .iter()
+                    .chain(
+                        self.manifest
+                            .modules
+                            .iter()
+                            .filter(|module| module.install_in.contains(&component.type_data))
+                            .map(|module| &module.type_data),
+                    )
+                    .map(TypeData::canonical_string_path)
+                    .collect();
+                modules.sort();
+                modules.dedup();
+                ComponentInfo {
+                    path: component.type_data.canonical_string_path(),
+                    subcomponent: component.component_type == ComponentType::Subcomponent,
+                    definition_only: component.definition_only,
+                    modules,
+                    scopes: std::iter::once(&component.type_data)
+                        .chain(component.scope_groups.iter())
+                        .map(TypeData::canonical_string_path)
+                        .collect(),
+                    provisions: component
+                        .provisions
+                        .iter()
+                        .map(|provision| provision.type_data.readable())
+                        .collect(),
+                }
+            })
+            .collect()
+    }
+
+    pub fn component(&self, path: &str) -> Option<ComponentInfo> {
+        self.components()
+            .into_iter()
+            .find(|component| component.path == path)
+    }
+
+    /// All bindings in the manifest, regardless of the components they are installed in.
+    pub fn bindings(&self) -> Vec<BindingInfo> {
+        let injectables = self
+            .manifest
+            .injectables
+            .iter()
+            .map(|injectable| BindingInfo {
+                type_: injectable.type_data.readable(),
+                kind: BindingKind::Injectable,
+                multibinding: false,
+                declared_in: injectable.type_data.canonical_string_path(),
+                scopes: scopes(&injectable.type_data),
+                dependencies: injectable
+                    .dependencies
+                    .iter()
+                    .map(|dependency| dependency.type_data.readable())
+                    .collect(),
+                source_file: injectable.source_file.clone(),
+            });
+        let module_bindings = self.manifest.modules.iter().flat_map(|module| {
+            module.bindings.iter().map(|binding| BindingInfo {
+                type_: binding.type_data.readable(),
+                kind: match binding.binding_type {
+                    BindingType::Provides => BindingKind::Provides,
+                    BindingType::Binds => BindingKind::Binds,
+                    BindingType::BindsOptionOf => BindingKind::BindsOptionOf,
+                    BindingType::Multibinds => BindingKind::Multibinds,
+                    BindingType::Decorator => BindingKind::Decorator,
+                },
+                multibinding: binding.multibinding_type != MultibindingType::None,
+                declared_in: module.type_data.canonical_string_path(),
+                scopes: scopes(&binding.type_data),
+                dependencies: binding
+                    .dependencies
+                    .iter()
+                    .map(|dependency| dependency.type_data.readable())
+                    .collect(),
+                source_file: module.source_file.clone(),
+            })
+        });
+        injectables.chain(module_bindings).collect()
+    }
+
+    /// Bindings of the modules installed in the component, and all injectables.
+    ///
+    /// Bindings inherited from parent components are not included.
+    pub fn bindings_in(&self, component: &str) -> Vec<BindingInfo> {
+        let Some(component) = self.component(component) else {
+            return Vec::new();
+        };
+        self.bindings()
+            .into_iter()
+            .filter(|binding| {
+                binding.kind == BindingKind::Injectable
+                    || component.modules.contains(&binding.declared_in)
+            })
+            .collect()
+    }
+}
+
+fn scopes(type_data: &TypeData) -> Vec<String> {
+    let mut scopes: Vec<String> = type_data
+        .scopes
+        .iter()
+        .map(TypeData::canonical_string_path)
+        .collect();
+    scopes.sort();
+    scopes
+}
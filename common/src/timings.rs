@@ -0,0 +1,45 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Phase by phase timing breakdown of the build script and `epilogue!()`, to attribute slow builds.
+
+use std::time::{Duration, Instant};
+
+/// Env var enabling the timing report when set to anything other than `0`.
+pub const LOCKJAW_TIMINGS_ENV: &str = "LOCKJAW_TIMINGS";
+
+/// Whether `LOCKJAW_TIMINGS` is set.
+pub fn enabled() -> bool {
+    std::env::var(LOCKJAW_TIMINGS_ENV)
+        .map(|value| !value.is_empty() && value != "0")
+        .unwrap_or(false)
+}
+
+/// Runs `f`, and returns its result with how long it took.
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Formats a line of the timing report.
+pub fn format(phase: &str, duration: Duration) -> String {
+    format!(
+        "lockjaw timings: {:>10.3}ms  {}",
+        duration.as_secs_f64() * 1000.0,
+        phase
+    )
+}
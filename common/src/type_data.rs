@@ -92,9 +92,17 @@ impl TypeData {
     /// Full path of the type in local from (use crate:: within the same crate).
     ///
     /// Modifiers like & are omitted
+    ///
+    /// `std` types are referred to through `::lockjaw::__private::std`, which only contains `core`
+    /// and `alloc` when lockjaw is built without the `std` feature.
     pub fn local_string_path(&self) -> String {
         let prefix = self.get_prefix();
         match self.root {
+            TypeRoot::GLOBAL if self.path.starts_with("std::") => format!(
+                "{}::lockjaw::__private::{}",
+                prefix,
+                self.path_with_args(true)
+            ),
             TypeRoot::GLOBAL => format!("{}::{}", prefix, self.path_with_args(true)),
             TypeRoot::CRATE => {
                 if environment::current_package().eq(&self.field_crate) {
@@ -163,6 +171,35 @@ impl TypeData {
         format!("{}{}", prefix, self.canonical_string_path())
     }
 
+    /// Replaces the occurrences of `params` in the type with the corresponding `args`, keeping the
+    /// modifiers of the occurrence.
+    pub fn substitute(&mut self, params: &[TypeData], args: &[TypeData]) {
+        if self.args.is_empty() {
+            if let Some(arg) = params
+                .iter()
+                .position(|param| {
+                    param.root == self.root
+                        && param.path == self.path
+                        && param.field_crate == self.field_crate
+                })
+                .map(|index| &args[index])
+            {
+                self.root = arg.root.clone();
+                self.path = arg.path.clone();
+                self.field_crate = arg.field_crate.clone();
+                self.args = arg.args.clone();
+                self.trait_object = arg.trait_object;
+                return;
+            }
+        }
+        for arg in &mut self.args {
+            arg.substitute(params, args);
+        }
+        if let Some(ref mut qualifier) = self.qualifier {
+            qualifier.substitute(params, args);
+        }
+    }
+
     fn path_with_args(&self, local: bool) -> String {
         if self.args.is_empty() {
             return self.path.clone();
@@ -199,6 +236,16 @@ lazy_static! {
     };
 }
 
+/// Lock based containers that `#[injectable(container: ..)]` resolves to `std::sync` without being
+/// imported.
+pub fn sync_container(identifier: &str) -> Option<&'static str> {
+    match identifier {
+        "Mutex" => Some("std::sync::Mutex"),
+        "RwLock" => Some("std::sync::RwLock"),
+        _ => None,
+    }
+}
+
 lazy_static! {
     /// primitive data types with no path
     static ref PRIMITIVES: HashSet<String> = {
@@ -219,6 +266,7 @@ lazy_static! {
         m.insert("f64".to_owned());
         m.insert("bool".to_owned());
         m.insert("char".to_owned());
+        m.insert("str".to_owned());
         m
     };
 }
@@ -318,7 +366,7 @@ pub fn from_path(syn_path: &syn::Path, mod_: &Mod) -> anyhow::Result<TypeData> {
         let first = segment_iter
             .next()
             .with_context(|| "path segment expected")?;
-        if segment_iter.peek().is_none() {
+        if segment_iter.peek().is_none() && !shadows_prelude(&first.ident.to_string(), mod_) {
             if let Some(prelude) = PRELUDE_V1.get(&first.ident.to_string()) {
                 result.path = prelude.clone();
                 result.root = TypeRoot::GLOBAL;
@@ -340,7 +388,7 @@ pub fn from_path(syn_path: &syn::Path, mod_: &Mod) -> anyhow::Result<TypeData> {
             result.path.push_str("::");
         } else {
             result.args.extend(get_args(first, mod_)?);
-            return Ok(result);
+            return Ok(normalize_std(result));
         }
     }
     if segment_iter.peek().is_some() {
@@ -356,7 +404,36 @@ pub fn from_path(syn_path: &syn::Path, mod_: &Mod) -> anyhow::Result<TypeData> {
             }
         }
     }
-    Ok(result)
+    Ok(normalize_std(result))
+}
+
+/// Maps `core::` and `alloc::` paths to the `std::` path re-exporting them, so the same type is
+/// recognized no matter which crate it is named through.
+/// Whether the mod declares `identifier`, or imports it from somewhere other than the prelude.
+fn shadows_prelude(identifier: &str, mod_: &Mod) -> bool {
+    if mod_.declared_types.contains(identifier) {
+        return true;
+    }
+    let (Some(prelude), true) = (
+        PRELUDE_V1.get(identifier),
+        mod_.uses.contains_key(identifier),
+    ) else {
+        return false;
+    };
+    let used = normalize_std(mod_.resolve_path(identifier).unwrap());
+    used.root != TypeRoot::GLOBAL || &used.path != prelude
+}
+
+fn normalize_std(mut type_data: TypeData) -> TypeData {
+    if type_data.root == TypeRoot::GLOBAL {
+        for krate in ["core::", "alloc::"] {
+            if let Some(rest) = type_data.path.strip_prefix(krate) {
+                type_data.path = format!("std::{}", rest);
+                break;
+            }
+        }
+    }
+    type_data
 }
 
 fn get_args(segment: &syn::PathSegment, mod_: &Mod) -> anyhow::Result<Vec<TypeData>> {
@@ -14,7 +14,7 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use compile_tests::set_src_path;
+use lockjaw::testing::set_src_path;
 
 #[test]
 fn builder_modules() {
@@ -32,4 +32,11 @@ fn builder_modules() {
             vec!["#[builder_modules] cannot be tuples"],
         )
     }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/builder_modules/builder_modules_private_module.rs"),
+            vec!["add #[component_visible] to it"],
+        )
+    }
 }
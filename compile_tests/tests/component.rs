@@ -13,7 +13,7 @@ WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 See the License for the specific language governing permissions and
 limitations under the License.
 */
-use compile_tests::set_src_path;
+use lockjaw::testing::set_src_path;
 
 #[test]
 fn component() {
@@ -87,7 +87,115 @@ fn component() {
             vec![
                 "#[entry_point]",
                 "the component is not annotated with #[define_component] or #[define_subcomponent]",
+                "change #[component] to #[define_component]",
             ],
         )
     }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/component/component_auto_default_zst_not_zst.rs"),
+            vec!["cannot be auto provided by `auto_default_zst` since it is not zero-sized"],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/component/component_thread_safe_not_send.rs"),
+            vec![
+                "::Foo is not Send + Sync, but is bound in thread_safe component",
+                "requested by: ",
+            ],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/component/component_get_not_provisioned.rs"),
+            vec!["`i32` is not provisioned by `dyn S`"],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/component/component_unused_module_denied.rs"),
+            vec![
+                "::UnusedModule is listed in the modules of",
+                "but none of its bindings are used",
+            ],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/component/component_provision_parameters_no_factory.rs"),
+            vec!["missing bindings for ::compile_tests_tests::FooFactory"],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/component/component_provision_mut_duplicated.rs"),
+            vec!["found multiple &mut provisions for ::compile_tests_tests::Foo"],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/component/component_provision_mut_not_scoped.rs"),
+            vec!["returns &mut ::compile_tests_tests::Foo, which must be scoped to the component"],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/component/component_provision_mut_shared.rs"),
+            vec![
+                "returns &mut ::compile_tests_tests::Foo, but the scoped binding",
+                "depends on it and may hold a reference to it",
+            ],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/component/entry_point_optional_not_option.rs"),
+            vec!["#[optional] provisions must return Option<T> or have a default implementation"],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/component/entry_point_optional_user_option.rs"),
+            vec!["#[optional] provisions must return Option<T> or have a default implementation"],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/component/component_fragment_with_items.rs"),
+            vec!["#[component_fragment] cannot have items"],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/component/component_fragment_not_fragment.rs"),
+            vec!["::compile_tests_tests::NotFragment is not a #[component_fragment], required by ::compile_tests_tests::MyComponent"],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/component/component_provision_reserved_name.rs"),
+            vec!["`build` conflicts with `<dyn S>::build()` generated by lockjaw. Rename the provision, e.g. to `foo_build`."],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/component/component_provision_lockjaw_prefix.rs"),
+            vec!["`lockjaw_foo` conflicts with methods generated by lockjaw, names starting with `lockjaw_` are reserved. Rename the provision."],
+        )
+    }
 }
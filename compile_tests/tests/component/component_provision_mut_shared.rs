@@ -0,0 +1,51 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+extern crate lockjaw;
+extern crate lockjaw;
+
+use lockjaw::{component, injectable};
+
+pub struct Foo {}
+
+#[injectable(scope: crate::MyComponent)]
+impl Foo {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+pub struct Bar<'a> {
+    foo: &'a Foo,
+}
+
+#[injectable(scope: crate::MyComponent)]
+impl<'a> Bar<'a> {
+    #[inject]
+    pub fn new(foo: &'a crate::Foo) -> Self {
+        Self { foo }
+    }
+}
+
+#[component]
+pub trait MyComponent {
+    fn foo(&mut self) -> &mut crate::Foo;
+    fn bar(&self) -> &crate::Bar;
+}
+
+fn main() {}
+
+lockjaw::epilogue!();
@@ -13,7 +13,7 @@ WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 See the License for the specific language governing permissions and
 limitations under the License.
 */
-use compile_tests::set_src_path;
+use lockjaw::testing::set_src_path;
 
 #[test]
 fn graph() {
@@ -24,6 +24,17 @@ fn graph() {
             vec!["missing bindings for ::compile_tests_tests::Foo"],
         )
     }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/graph/graph_missing_binding_qualified.rs"),
+            vec![
+                "missing bindings for ::std::string::String",
+                "bindings with a different qualifier exist:",
+                "#[qualified(::compile_tests_tests::Q)] ::std::string::String",
+            ],
+        )
+    }
     {
         let t = trybuild::TestCases::new();
         t.compile_failed_with(
@@ -58,4 +69,24 @@ fn graph() {
             ],
         )
     }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/graph/graph_singleton_depends_on_narrower_scope.rs"),
+            vec![
+                "Singleton scoped binding cannot depend on",
+                "scoped to the narrower ::compile_tests_tests::Sub",
+            ],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/graph/graph_identifier_collision.rs"),
+            vec![
+                "lockjaw is unable to distinguish",
+                "which are both mangled into the identifier",
+            ],
+        )
+    }
 }
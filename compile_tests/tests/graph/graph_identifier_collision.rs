@@ -0,0 +1,52 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+#![allow(uncommon_codepoints)]
+#![allow(non_camel_case_types)]
+extern crate lockjaw;
+
+use lockjaw::{component, injectable};
+
+mod foo {
+    pub struct Bar {}
+
+    #[lockjaw::injectable]
+    impl Bar {
+        #[inject]
+        pub fn new() -> Self {
+            Self {}
+        }
+    }
+}
+
+// mangled the same as crate::foo::Bar
+pub struct fooⵆBar {}
+
+#[injectable]
+impl fooⵆBar {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[component]
+trait S {
+    fn bar(&self) -> crate::foo::Bar;
+}
+
+fn main() {}
+
+lockjaw::epilogue!();
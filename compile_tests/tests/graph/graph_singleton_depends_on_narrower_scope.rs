@@ -0,0 +1,59 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+extern crate lockjaw;
+
+use lockjaw::{component, injectable, module, subcomponent, Cl, Singleton};
+
+struct Foo {}
+
+#[injectable(scope: crate::Sub)]
+impl Foo {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+struct Bar<'a> {
+    foo: &'a crate::Foo,
+}
+
+#[injectable(scope: Singleton)]
+impl Bar<'_> {
+    #[inject]
+    pub fn new(foo: &'_ crate::Foo) -> Bar<'_> {
+        Bar { foo }
+    }
+}
+
+#[subcomponent]
+trait Sub<'a> {
+    fn bar(&self) -> &crate::Bar;
+}
+
+struct M {}
+
+#[module(subcomponents: [crate::Sub])]
+impl M {}
+
+#[component(modules: [crate::M])]
+trait S {
+    fn sub(&'_ self) -> Cl<dyn crate::SubBuilder<'_>>;
+}
+
+fn main() {}
+
+lockjaw::epilogue!();
@@ -13,7 +13,7 @@ WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 See the License for the specific language governing permissions and
 limitations under the License.
 */
-use compile_tests::set_src_path;
+use lockjaw::testing::set_src_path;
 
 #[test]
 fn injectable() {
@@ -80,6 +80,13 @@ fn injectable() {
             vec!["path expected for 'implementing'"],
         )
     }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/injectable/injectable_factory_into_vec_without_implementing.rs"),
+            vec!["'into_vec' requires 'implementing'"],
+        )
+    }
     {
         let t = trybuild::TestCases::new();
         t.compile_failed_with(
@@ -121,4 +128,63 @@ fn injectable() {
             ],
         )
     }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/injectable/injectable_container_not_thread_safe.rs"),
+            vec![
+                "container ::std::cell::RefCell of",
+                "::Foo is not Send + Sync, but it is scoped to thread_safe component",
+            ],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/injectable/injectable_duplicate_scope.rs"),
+            vec!["duplicate scope `S`"],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/injectable/injectable_default_non_struct.rs"),
+            vec!["struct or enum expected for #[injectable(default)]"],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/injectable/injectable_inject_generic.rs"),
+            vec!["generic type parameters are not supported on #[inject] constructors"],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/injectable/injectable_inject_return_type_mismatch.rs"),
+            vec!["#[inject] constructor must return Self (S), found T"],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/injectable/injectable_inject_fallible_not_result.rs"),
+            vec!["#[inject(fallible)] constructor must return Result<Self, E> (Result<S, E>), found Self"],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/injectable/injectable_inject_fallible_requested_directly.rs"),
+            vec!["has an #[inject(fallible)] constructor, request Result<&"],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/injectable/injectable_factory_generic_not_runtime.rs"),
+            vec!["generic type parameters can only be used by #[runtime] parameters"],
+        )
+    }
 }
@@ -13,7 +13,7 @@ WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 See the License for the specific language governing permissions and
 limitations under the License.
 */
-use compile_tests::set_src_path;
+use lockjaw::testing::set_src_path;
 
 #[test]
 fn module() {
@@ -157,6 +157,7 @@ fn module() {
             vec![
                 "#[module]",
                 "but the component is not annotated with #[define_component] or #[define_subcomponent]",
+                "change #[component] to #[define_component]",
             ],
         )
     }
@@ -186,8 +187,67 @@ fn module() {
         t.compile_failed_with(set_src_path(
             "tests/module/module_multibinds_duplicated_binds.rs"),
                               vec![
-                                  "#[module] methods can only be annotated by one of #[provides]/#[binds]/#[binds_option_of]/#[multibinds]",
+                                  "#[module] methods can only be annotated by one of #[provides]/#[binds]/#[binds_option_of]/#[binds_decorator]/#[multibinds]",
                               ],
         )
     }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/module/into_map_cached_unscoped.rs"),
+            vec!["requires every #[into_map] binding of"],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/module/provides_scoped_to_some_installed_components.rs"),
+            vec![
+                "is scoped to",
+                "but the module is also installed in",
+                "Use `scope: install`",
+            ],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/module/binds_scope_conflicts_with_implementation.rs"),
+            vec![
+                "::compile_tests_tests::M.bind_my_trait is scoped to ::compile_tests_tests::BarComponent",
+                "but its implementation ::compile_tests_tests::MyTraitImpl is scoped to ::compile_tests_tests::FooComponent",
+            ],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/module/provides_ref_scoped.rs"),
+            vec!["#[provides] returning a reference cannot be scoped"],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/module/provides_scope_install_combined.rs"),
+            vec!["`scope: install` cannot be combined with other scopes"],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/module/binds_decorator_no_inner.rs"),
+            vec!["#[binds_decorator] method must take exactly one parameter of the decorated type"],
+        )
+    }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/module/binds_decorator_nothing_to_decorate.rs"),
+            vec![
+                "no binding of ::std::string::String to decorate",
+                "::compile_tests_tests::S.decorate_string",
+            ],
+        )
+    }
 }
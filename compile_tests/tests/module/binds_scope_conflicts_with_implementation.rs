@@ -0,0 +1,52 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+extern crate lockjaw;
+
+use lockjaw::{component, injectable, module, Cl};
+
+pub trait MyTrait {}
+
+pub struct MyTraitImpl {}
+
+#[injectable(scope: crate::FooComponent)]
+impl MyTraitImpl {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl MyTrait for MyTraitImpl {}
+
+pub struct M {}
+
+#[module]
+impl M {
+    #[binds(scope: crate::BarComponent)]
+    pub fn bind_my_trait(_impl: &crate::MyTraitImpl) -> Cl<dyn crate::MyTrait> {}
+}
+
+#[component]
+pub trait FooComponent {}
+
+#[component(modules: [M])]
+pub trait BarComponent {
+    fn my_trait(&self) -> Cl<dyn crate::MyTrait>;
+}
+
+fn main() {}
+
+lockjaw::epilogue!();
@@ -0,0 +1,37 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+extern crate lockjaw;
+
+use lockjaw::{component, module};
+
+pub struct M {
+    s: String,
+}
+
+#[module]
+impl M {
+    #[provides(scope: crate::S)]
+    pub fn provide_str(&self) -> &str {
+        &self.s
+    }
+}
+
+#[component]
+pub trait S {}
+
+fn main() {}
+
+lockjaw::epilogue!();
@@ -0,0 +1,35 @@
+/*
+Copyright 2021 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+extern crate lockjaw;
+
+use lockjaw::{define_component, module};
+
+struct S;
+
+#[module(install_in: FooComponent)]
+impl S {
+    #[provides(scope: [install, crate::FooComponent])]
+    pub fn provide_string() -> String {
+        "string".to_owned()
+    }
+}
+
+#[define_component]
+pub trait FooComponent {}
+
+fn main() {}
+
+lockjaw::epilogue!();
@@ -13,7 +13,7 @@ WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 See the License for the specific language governing permissions and
 limitations under the License.
 */
-use compile_tests::set_src_path;
+use lockjaw::testing::set_src_path;
 
 #[test]
 fn subcomponent() {
@@ -40,7 +40,15 @@ fn subcomponent() {
             vec![
                 "#[subcomponent]",
                 "but the component is not annotated with #[define_component] or #[define_subcomponent]",
+                "change #[component] to #[define_component]",
             ],
         )
     }
+    {
+        let t = trybuild::TestCases::new();
+        t.compile_failed_with(
+            set_src_path("tests/subcomponent/subcomponent_generic.rs"),
+            vec!["subcomponents cannot be generic"],
+        )
+    }
 }
@@ -27,8 +27,6 @@ use std::error::Error;
 use std::rc::Rc;
 use std::time::{Duration, Instant};
 
-lockjaw::prologue!("src/main.rs");
-
 struct GameState<'a> {
     frame_counter: i32,
     time_start: Instant,
@@ -31,8 +31,6 @@ use winapi::um::winuser::{
     DispatchMessageW, PeekMessageW, TranslateMessage, MSG, PM_REMOVE, WM_QUIT,
 };
 
-lockjaw::prologue!("src/application/mod.rs");
-
 #[component_visible]
 pub(in crate::application) struct ApplicationImpl<'a> {
     hinstance: HINSTANCE,
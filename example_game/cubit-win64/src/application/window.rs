@@ -37,8 +37,6 @@ use winapi::um::winuser::{
     WNDCLASSEXW, WS_OVERLAPPEDWINDOW,
 };
 
-lockjaw::prologue!("src/application/window.rs");
-
 #[component_visible]
 pub(in crate::application) struct WindowImpl<'a> {
     handle: HWND,
@@ -21,8 +21,6 @@ use std::cell::RefCell;
 use winapi::um::d3d12::{ID3D12CommandAllocator, D3D12_COMMAND_LIST_TYPE_DIRECT};
 use winapi::Interface;
 
-lockjaw::prologue!("src/directx/command_allocator.rs");
-
 pub struct CommandAllocator {
     command_allocator: ComPtr<ID3D12CommandAllocator>,
 }
@@ -27,8 +27,6 @@ use cubit::graphics::Color;
 use crate::directx::command_allocator::CommandAllocator;
 use std::mem::zeroed;
 
-lockjaw::prologue!("src/directx/command_list.rs");
-
 pub struct CommandList {
     command_list: ComPtr<ID3D12GraphicsCommandList>,
 }
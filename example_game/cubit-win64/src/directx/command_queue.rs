@@ -25,8 +25,6 @@ use winapi::um::unknwnbase::IUnknown;
 use winapi::Interface;
 use crate::directx::command_list::CommandList;
 
-lockjaw::prologue!("src/directx/command_queue.rs");
-
 pub struct DxCommandQueue<'c> {
     command_queue: ComPtr<ID3D12CommandQueue>,
     fence: &'c RefCell<Fence>,
@@ -21,8 +21,6 @@ use std::cell::RefCell;
 use winapi::um::d3d12::{ID3D12DescriptorHeap, D3D12_DESCRIPTOR_HEAP_DESC, D3D12_DESCRIPTOR_HEAP_TYPE, D3D12_CPU_DESCRIPTOR_HANDLE};
 use winapi::Interface;
 
-lockjaw::prologue!("src/directx/descriptor_heap.rs");
-
 pub struct DescriptorHeap {
     descriptor_heap: ComPtr<ID3D12DescriptorHeap>,
 }
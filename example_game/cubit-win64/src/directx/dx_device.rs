@@ -34,8 +34,6 @@ use winapi::um::d3dcommon::D3D_FEATURE_LEVEL_11_0;
 use winapi::um::unknwnbase::IUnknown;
 use winapi::Interface;
 
-lockjaw::prologue!("src/directx/dx_device.rs");
-
 pub struct DxDevice {
     device: ComPtr<ID3D12Device>,
 }
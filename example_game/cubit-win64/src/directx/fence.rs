@@ -26,8 +26,6 @@ use winapi::um::synchapi::{CreateEventW, WaitForSingleObject};
 use winapi::um::winnt::HANDLE;
 use winapi::Interface;
 
-lockjaw::prologue!("src/directx/fence.rs");
-
 pub struct Fence {
     fence: ComPtr<ID3D12Fence>,
     fence_event: HANDLE,
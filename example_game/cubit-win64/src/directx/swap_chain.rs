@@ -48,8 +48,6 @@ use cubit::graphics::Color;
 
 const BACK_BUFFER_SIZE: u32 = 3;
 
-lockjaw::prologue!("src/directx/swap_chain.rs");
-
 pub struct Frame {
     command_allocator: CommandAllocator,
     back_buffer: ComPtr<ID3D12Resource>,
@@ -26,8 +26,6 @@ use std::cell::RefCell;
 use std::ops::DerefMut;
 use winapi::shared::windef::HWND;
 
-lockjaw::prologue!("src/graphics/renderer.rs");
-
 #[component_visible]
 pub(crate) struct Renderer<'app_component> {
     component: Cl<'app_component, dyn RendererComponent<'app_component>>,
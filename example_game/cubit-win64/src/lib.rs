@@ -6,6 +6,4 @@ pub mod graphics;
 pub mod marshal;
 pub mod os;
 
-lockjaw::prologue!("src/lib.rs");
-
 lockjaw::epilogue!();
@@ -21,8 +21,6 @@ use cubit::ApplicationComponent;
 use lockjaw::{component_visible, injectable, module, Cl};
 use winapi::um::winuser;
 
-lockjaw::prologue!("src/os.rs");
-
 #[component_visible]
 struct OsImpl {}
 
@@ -23,8 +23,6 @@ use lockjaw::{component_visible, define_component, entry_point, module, Cl};
 use std::borrow::Borrow;
 use std::error::Error;
 
-lockjaw::prologue!("src/lib.rs");
-
 #[define_component]
 pub trait ApplicationComponent {}
 
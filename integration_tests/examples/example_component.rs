@@ -0,0 +1,44 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+// Examples are built by `cargo test`, and are root targets that must initialize the components.
+
+use lockjaw::{component, epilogue, injectable};
+
+pub struct Greeter {}
+
+#[injectable]
+impl Greeter {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn greet(&self) -> String {
+        "hello".to_owned()
+    }
+}
+
+#[component]
+pub trait MyComponent {
+    fn greeter(&self) -> crate::Greeter;
+}
+
+fn main() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    println!("{}", component.greeter().greet());
+}
+epilogue!();
@@ -0,0 +1,83 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::testing::assert_binding_exists;
+use lockjaw::{component, epilogue, injectable, module, qualifier};
+
+pub struct Database {}
+
+#[injectable(scope: crate::ArchitectureComponent)]
+impl Database {
+    #[inject]
+    pub fn new() -> Database {
+        Database {}
+    }
+}
+
+pub struct Cache {}
+
+#[qualifier]
+pub struct Primary;
+
+pub struct StorageModule {}
+
+#[module]
+impl StorageModule {
+    #[provides]
+    #[qualified(crate::Primary)]
+    pub fn provide_cache() -> Cache {
+        Cache {}
+    }
+
+    #[provides]
+    pub fn provide_names() -> Vec<String> {
+        vec![]
+    }
+}
+
+#[component(modules: [StorageModule])]
+pub trait ArchitectureComponent {
+    fn database(&self) -> &Database;
+    #[qualified(Primary)]
+    fn cache(&self) -> Cache;
+    fn names(&self) -> Vec<String>;
+}
+
+// ANCHOR: assert_binding_exists
+#[test]
+pub fn architecture() {
+    assert_binding_exists!(ArchitectureComponent, Database, scope: ArchitectureComponent);
+    assert_binding_exists!(crate::ArchitectureComponent, &crate::Database);
+    assert_binding_exists!(ArchitectureComponent, Cache, qualified: Primary);
+    assert_binding_exists!(ArchitectureComponent, Vec<String>);
+}
+// ANCHOR_END: assert_binding_exists
+
+#[test]
+#[should_panic(expected = "does not provide a binding for Cache")]
+pub fn unqualified_missing() {
+    assert_binding_exists!(ArchitectureComponent, Cache);
+}
+
+#[test]
+#[should_panic(expected = "is not scoped to Primary")]
+pub fn wrong_scope() {
+    assert_binding_exists!(ArchitectureComponent, Database, scope: Primary);
+}
+
+epilogue!();
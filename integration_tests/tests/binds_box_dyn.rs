@@ -0,0 +1,67 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{define_component, epilogue, injectable, module, Cl};
+
+pub trait Greeter {
+    fn greet(&self) -> String;
+}
+
+pub struct GreeterImpl {}
+
+#[injectable]
+impl GreeterImpl {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Greeter for GreeterImpl {
+    fn greet(&self) -> String {
+        "hello".to_owned()
+    }
+}
+
+pub struct MyModule {}
+
+// ANCHOR: binds
+#[module(install_in: crate::MyComponent)]
+impl MyModule {
+    #[binds]
+    pub fn bind_greeter(impl_: crate::GreeterImpl) -> Cl<dyn crate::Greeter> {}
+}
+
+#[define_component]
+pub trait MyComponent {
+    fn greeter(&self) -> Box<dyn crate::Greeter>;
+    fn dep_greeter(&self) -> Box<dyn test_dep::DepGreeter>;
+}
+// ANCHOR_END: binds
+
+#[test]
+pub fn box_bound_trait() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert_eq!(component.greeter().greet(), "hello");
+}
+
+#[test]
+pub fn box_bound_trait_from_dep() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert_eq!(component.dep_greeter().greet(), "dep");
+}
+
+epilogue!();
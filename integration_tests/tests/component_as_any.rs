@@ -0,0 +1,75 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{component, define_component, downcast_component, entry_point, module};
+use std::any::Any;
+
+struct MyModule {}
+
+#[module(install_in: MyComponent)]
+impl MyModule {
+    #[provides]
+    pub fn provide_i(&self) -> i32 {
+        42
+    }
+}
+
+// ANCHOR: as_any
+#[define_component]
+pub trait MyComponent {
+    fn as_any(&self) -> &dyn Any;
+}
+
+#[entry_point(install_in: MyComponent)]
+pub trait MyEntryPoint {
+    fn i(&self) -> i32;
+}
+
+#[test]
+pub fn downcast_entry_point() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let any: &dyn Any = component.as_any();
+
+    assert_eq!(downcast_component::<dyn MyEntryPoint>(any).unwrap().i(), 42);
+}
+// ANCHOR_END: as_any
+
+#[component]
+pub trait OtherComponent {
+    fn as_any(&self) -> &dyn Any;
+}
+
+#[test]
+pub fn downcast_component_trait() {
+    let component: Box<dyn OtherComponent> = <dyn OtherComponent>::new();
+
+    assert!(downcast_component::<dyn OtherComponent>(component.as_any()).is_some());
+}
+
+#[test]
+pub fn downcast_wrong_component() {
+    let component: Box<dyn OtherComponent> = <dyn OtherComponent>::new();
+
+    assert!(downcast_component::<dyn MyEntryPoint>(component.as_any()).is_none());
+    assert!(downcast_component::<dyn MyComponent>(component.as_any()).is_none());
+}
+
+#[test]
+pub fn downcast_non_component() {
+    assert!(downcast_component::<dyn MyEntryPoint>(&1).is_none());
+}
+
+lockjaw::epilogue!();
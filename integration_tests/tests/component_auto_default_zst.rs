@@ -0,0 +1,50 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, injectable};
+use std::marker::PhantomData;
+
+#[derive(Default)]
+pub struct Production;
+
+pub struct Foo {
+    config: PhantomData<crate::Production>,
+}
+
+#[injectable]
+impl Foo {
+    #[inject]
+    pub fn new(config: PhantomData<crate::Production>) -> Self {
+        Foo { config }
+    }
+}
+
+#[component(auto_default_zst: true)]
+pub trait MyComponent {
+    fn foo(&self) -> crate::Foo;
+    fn production(&self) -> crate::Production;
+}
+
+#[test]
+pub fn main() {
+    let component = <dyn MyComponent>::new();
+    component.foo();
+    component.production();
+}
+
+lockjaw::epilogue!();
@@ -0,0 +1,59 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{component, epilogue, injectable, module};
+use std::fmt::Debug;
+
+pub struct Foo {}
+
+#[injectable(scope: crate::MyComponent)]
+impl Foo {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+pub struct MyModule {}
+
+#[module]
+impl MyModule {
+    #[provides]
+    pub fn provide_i32() -> i32 {
+        42
+    }
+}
+
+// ANCHOR: debug
+#[component(modules: [MyModule])]
+pub trait MyComponent: Debug {
+    fn foo(&self) -> &crate::Foo;
+    fn i(&self) -> i32;
+}
+// ANCHOR_END: debug
+
+#[test]
+pub fn debug() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let debug = format!("{:?}", component);
+    assert!(debug.contains("MyComponent { modules: ["));
+    assert!(debug.contains("MyModule\"]"));
+    assert!(debug.contains("Foo\": \"uninitialized\""));
+    component.foo();
+    assert!(format!("{:?}", component).contains("Foo\": \"initialized\""));
+}
+
+epilogue!();
@@ -0,0 +1,106 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{component, component_fragment, epilogue, injectable, module};
+use std::cell::Cell;
+
+// ANCHOR: fragment
+pub struct Counter {
+    count: Cell<i32>,
+}
+
+#[injectable(scope: crate::CounterFragment)]
+impl Counter {
+    #[inject]
+    pub fn new() -> Self {
+        Self {
+            count: Cell::new(0),
+        }
+    }
+
+    pub fn increment(&self) -> i32 {
+        self.count.set(self.count.get() + 1);
+        self.count.get()
+    }
+}
+
+pub struct CounterModule {}
+
+#[module]
+impl CounterModule {
+    #[provides]
+    pub fn provide_step() -> i32 {
+        1
+    }
+}
+
+#[component_fragment(modules: [CounterModule])]
+pub trait CounterFragment {}
+// ANCHOR_END: fragment
+
+pub struct LabelModule {}
+
+#[module(install_in: crate::LabelFragment)]
+impl LabelModule {
+    #[provides]
+    pub fn provide_label() -> String {
+        "label".to_owned()
+    }
+}
+
+// ANCHOR: nested
+#[component_fragment(fragments: [CounterFragment])]
+pub trait LabelFragment {}
+// ANCHOR_END: nested
+
+// ANCHOR: component
+#[component(fragments: [CounterFragment])]
+pub trait MyComponent {
+    fn counter(&self) -> &crate::Counter;
+    fn step(&self) -> i32;
+}
+
+#[component(fragments: [LabelFragment])]
+pub trait OtherComponent {
+    fn counter(&self) -> &crate::Counter;
+    fn label(&self) -> String;
+}
+// ANCHOR_END: component
+
+#[test]
+pub fn modules_embedded() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert_eq!(component.step(), 1);
+}
+
+#[test]
+pub fn scoped_per_component() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let other: Box<dyn OtherComponent> = <dyn OtherComponent>::new();
+
+    assert_eq!(component.counter().increment(), 1);
+    assert_eq!(component.counter().increment(), 2);
+    assert_eq!(other.counter().increment(), 1);
+}
+
+#[test]
+pub fn nested_fragment() {
+    let other: Box<dyn OtherComponent> = <dyn OtherComponent>::new();
+    assert_eq!(other.label(), "label");
+    assert_eq!(other.counter().increment(), 1);
+}
+
+epilogue!();
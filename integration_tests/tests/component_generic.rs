@@ -0,0 +1,116 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{define_component, module};
+use std::marker::PhantomData;
+
+// ANCHOR: component
+pub trait Entity: 'static {
+    fn table() -> &'static str;
+}
+
+pub struct User {}
+
+impl Entity for User {
+    fn table() -> &'static str {
+        "users"
+    }
+}
+
+pub struct Order {}
+
+impl Entity for Order {
+    fn table() -> &'static str {
+        "orders"
+    }
+}
+
+pub struct Store<T: Entity> {
+    database: String,
+    _entity: PhantomData<T>,
+}
+
+impl<T: Entity> Store<T> {
+    pub fn location(&self) -> String {
+        format!("{}/{}", self.database, T::table())
+    }
+}
+
+#[define_component]
+pub trait Repo<T: Entity> {
+    fn store(&self) -> Store<T>;
+
+    fn database(&self) -> String;
+}
+// ANCHOR_END: component
+
+// ANCHOR: modules
+pub struct DatabaseModule {}
+
+#[module(install_in: [Repo::<User>, Repo::<Order>])]
+impl DatabaseModule {
+    #[provides]
+    pub fn provide_database(&self) -> String {
+        "db".to_owned()
+    }
+}
+
+pub struct UserModule {}
+
+#[module(install_in: Repo::<User>)]
+impl UserModule {
+    #[provides]
+    pub fn provide_store(&self, database: String) -> Store<User> {
+        Store {
+            database,
+            _entity: PhantomData,
+        }
+    }
+}
+
+pub struct OrderModule {}
+
+#[module(install_in: Repo::<Order>)]
+impl OrderModule {
+    #[provides]
+    pub fn provide_store(&self, database: String) -> Store<Order> {
+        Store {
+            database: format!("{}-archive", database),
+            _entity: PhantomData,
+        }
+    }
+}
+// ANCHOR_END: modules
+
+#[test]
+pub fn instantiations() {
+    // ANCHOR: instantiate
+    let users: Box<dyn Repo<User>> = <dyn Repo<User>>::new();
+    let orders: Box<dyn Repo<Order>> = <dyn Repo<Order>>::new();
+    // ANCHOR_END: instantiate
+
+    assert_eq!(users.store().location(), "db/users");
+    assert_eq!(orders.store().location(), "db-archive/orders");
+}
+
+#[test]
+pub fn get() {
+    let users: Box<dyn Repo<User>> = <dyn Repo<User>>::new();
+
+    assert_eq!(users.get::<String>(), "db");
+}
+
+lockjaw::epilogue!(root);
@@ -0,0 +1,70 @@
+/*
+Copyright 2021 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, epilogue, module, qualifier, Provisioned};
+
+#[qualifier]
+pub struct Q;
+
+pub struct MyModule {}
+
+#[module]
+impl MyModule {
+    #[provides]
+    pub fn provide_string() -> String {
+        "string".to_owned()
+    }
+
+    #[provides]
+    pub fn provide_i32() -> i32 {
+        42
+    }
+
+    #[provides]
+    #[qualified(Q)]
+    pub fn provide_q_i32() -> i32 {
+        1
+    }
+}
+
+#[component(modules: [MyModule])]
+pub trait MyComponent {
+    fn string(&self) -> String;
+    fn i(&self) -> i32;
+    #[qualified(Q)]
+    fn q_i(&self) -> i32;
+}
+
+fn lookup<T, C: ?Sized + Provisioned<T>>(component: &C) -> T {
+    component.provision()
+}
+
+#[test]
+pub fn get() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert_eq!(component.get::<String>(), "string");
+    assert_eq!(component.get::<i32>(), 42);
+}
+
+#[test]
+pub fn generic_lookup() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert_eq!(lookup::<String, _>(component.as_ref()), "string");
+}
+
+epilogue!();
@@ -0,0 +1,83 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, epilogue, injectable, Cl, Instances};
+
+pub struct Config {
+    name: String,
+}
+
+pub trait Greeter {
+    fn greet(&self) -> String;
+}
+
+struct FrameworkGreeter {}
+
+impl Greeter for FrameworkGreeter {
+    fn greet(&self) -> String {
+        "framework".to_owned()
+    }
+}
+
+pub struct Foo<'a> {
+    config: &'a Config,
+}
+
+#[injectable]
+impl Foo<'_> {
+    #[inject]
+    pub fn new(config: &'_ crate::Config) -> Foo<'_> {
+        Foo { config }
+    }
+}
+
+#[component(instance_bindings: [crate::Config, dyn crate::Greeter])]
+pub trait MyComponent {
+    fn config(&self) -> &crate::Config;
+    fn greeter(&'_ self) -> &'_ dyn crate::Greeter;
+    fn greeter_cl(&'_ self) -> Cl<'_, dyn crate::Greeter>;
+    fn foo(&'_ self) -> crate::Foo<'_>;
+}
+
+#[test]
+fn build_with() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::build_with(
+        Instances::new()
+            .with(Config {
+                name: "foo".to_owned(),
+            })
+            .with_boxed::<dyn Greeter>(Box::new(FrameworkGreeter {})),
+    );
+    assert_eq!(component.config().name, "foo");
+    assert_eq!(component.greeter().greet(), "framework");
+    assert_eq!(component.greeter_cl().greet(), "framework");
+    assert!(std::ptr::eq(component.foo().config, component.config()));
+}
+
+#[test]
+fn missing_instance() {
+    let result = <dyn MyComponent>::try_build_with(Instances::new().with(Config {
+        name: "foo".to_owned(),
+    }));
+    assert!(matches!(
+        result.err().unwrap(),
+        lockjaw::Error::MissingInstance { .. }
+    ));
+}
+
+epilogue!();
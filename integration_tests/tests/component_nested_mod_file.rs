@@ -0,0 +1,33 @@
+/*
+Copyright 2020 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+mod nested_mod;
+
+#[test]
+pub fn file_mod() {
+    let component: Box<dyn nested_mod::NestedComponent> = <dyn nested_mod::NestedComponent>::new();
+    component.leaf();
+}
+
+#[test]
+pub fn file_mod_in_inline_mod() {
+    let component: Box<dyn nested_mod::NestedComponent> = <dyn nested_mod::NestedComponent>::new();
+    component.grouped();
+}
+
+lockjaw::epilogue!();
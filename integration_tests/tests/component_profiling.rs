@@ -0,0 +1,82 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{component, epilogue, injectable, module};
+
+pub struct Foo {}
+
+#[injectable]
+impl Foo {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+pub struct Bar {}
+
+#[injectable(scope: crate::MyComponent)]
+impl Bar {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+pub struct MyModule {}
+
+#[module]
+impl MyModule {
+    #[provides]
+    pub fn provide_i32() -> i32 {
+        42
+    }
+}
+
+#[component(modules: [MyModule])]
+pub trait MyComponent {
+    fn foo(&self) -> crate::Foo;
+    fn bar(&self) -> &crate::Bar;
+    fn i(&self) -> i32;
+}
+
+fn count(component: &dyn MyComponent, name: &str) -> u64 {
+    component
+        .lockjaw_stats()
+        .iter()
+        .find(|(binding, _)| binding.contains(name))
+        .unwrap_or_else(|| panic!("no stats for {}", name))
+        .1
+}
+
+// ANCHOR: stats
+#[test]
+pub fn stats() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert_eq!(count(component.as_ref(), "::Foo"), 0);
+    for _ in 0..3 {
+        component.foo();
+        component.bar();
+    }
+    component.i();
+    assert_eq!(count(component.as_ref(), "::Foo"), 3);
+    // scoped bindings are only constructed once.
+    assert_eq!(count(component.as_ref(), "::Bar"), 1);
+    assert_eq!(count(component.as_ref(), "provide_i32"), 1);
+}
+// ANCHOR_END: stats
+
+epilogue!();
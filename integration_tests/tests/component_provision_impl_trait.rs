@@ -0,0 +1,98 @@
+/*
+Copyright 2020 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, epilogue, injectable, module, Cl};
+
+pub trait Logger {
+    fn log(&self, message: &str) -> String;
+}
+
+pub struct StdoutLogger {}
+
+#[injectable]
+impl StdoutLogger {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Logger for StdoutLogger {
+    fn log(&self, message: &str) -> String {
+        format!("stdout: {}", message)
+    }
+}
+
+pub trait Counter {
+    fn name(&self) -> String;
+}
+
+pub struct ScopedCounter {}
+
+#[injectable(scope: crate::MyComponent)]
+impl ScopedCounter {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Counter for ScopedCounter {
+    fn name(&self) -> String {
+        "scoped".to_owned()
+    }
+}
+
+pub struct MyModule {}
+
+#[module]
+impl MyModule {
+    #[binds]
+    pub fn bind_logger(_impl: StdoutLogger) -> Cl<dyn Logger> {}
+
+    #[binds]
+    pub fn bind_counter(_impl: &ScopedCounter) -> Cl<dyn Counter> {}
+}
+
+#[component(modules: MyModule)]
+pub trait MyComponent {
+    fn logger(&self) -> impl Logger + '_;
+    fn counter(&self) -> impl Counter + '_;
+}
+
+#[test]
+pub fn impl_trait() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert_eq!(component.logger().log("foo"), "stdout: foo");
+}
+
+#[test]
+pub fn impl_trait_returns_implementation() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let _logger: StdoutLogger = component.logger();
+}
+
+#[test]
+pub fn impl_trait_scoped() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let counter: &ScopedCounter = component.counter();
+    assert_eq!(counter.name(), "scoped");
+    assert!(std::ptr::eq(counter, component.counter()));
+}
+
+epilogue!();
@@ -0,0 +1,71 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, injectable};
+
+pub struct Counter {
+    count: u32,
+}
+
+#[injectable(scope: crate::MyComponent)]
+impl Counter {
+    #[inject]
+    pub fn new() -> Self {
+        Counter { count: 0 }
+    }
+}
+
+pub struct Reader<'a> {
+    counter: &'a Counter,
+}
+
+#[injectable]
+impl<'a> Reader<'a> {
+    #[inject]
+    pub fn new(counter: &'a crate::Counter) -> Self {
+        Reader { counter }
+    }
+}
+
+#[component]
+pub trait MyComponent {
+    fn counter(&self) -> &crate::Counter;
+    fn counter_mut(&mut self) -> &mut crate::Counter;
+    fn reader(&self) -> crate::Reader;
+}
+
+#[test]
+pub fn mutate_before_read() {
+    let mut component = <dyn MyComponent>::new();
+    component.counter_mut().count += 1;
+    component.counter_mut().count += 1;
+
+    assert_eq!(component.counter().count, 2);
+    assert_eq!(component.reader().counter.count, 2);
+}
+
+#[test]
+pub fn mutate_after_read() {
+    let mut component = <dyn MyComponent>::new();
+    assert_eq!(component.counter().count, 0);
+    component.counter_mut().count += 1;
+
+    assert_eq!(component.counter().count, 1);
+}
+
+lockjaw::epilogue!();
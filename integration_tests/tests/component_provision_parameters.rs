@@ -0,0 +1,68 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{component, epilogue, injectable, module};
+
+struct MyModule;
+
+#[module]
+impl MyModule {
+    #[provides]
+    pub fn provide_string() -> String {
+        "handler".to_owned()
+    }
+}
+
+pub struct Route {
+    pub path: String,
+}
+
+pub struct Handler {
+    pub route: Route,
+    pub port: u16,
+    pub name: String,
+}
+
+#[injectable]
+impl Handler {
+    #[factory]
+    fn create(#[runtime] route: Route, name: String, #[runtime] port: u16) -> Self {
+        Self { route, port, name }
+    }
+}
+
+// ANCHOR: parameters
+#[component(modules: MyModule)]
+pub trait MyComponent {
+    fn handler(&self, route: crate::Route, port: u16) -> crate::Handler;
+}
+// ANCHOR_END: parameters
+
+#[test]
+pub fn main() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::build();
+    let handler = component.handler(
+        Route {
+            path: "/index".to_owned(),
+        },
+        8080,
+    );
+    assert_eq!(handler.route.path, "/index");
+    assert_eq!(handler.port, 8080);
+    assert_eq!(handler.name, "handler");
+}
+
+epilogue!();
@@ -0,0 +1,64 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, epilogue, injectable};
+
+// ANCHOR: scope_group
+pub trait UiScope {}
+
+pub struct Foo {
+    pub i: ::std::cell::RefCell<u32>,
+}
+
+#[injectable(scope: crate::UiScope)]
+impl Foo {
+    #[inject]
+    pub fn new() -> Self {
+        Self {
+            i: Default::default(),
+        }
+    }
+
+    pub fn count(&self) -> u32 {
+        let v: u32 = self.i.borrow().clone();
+        self.i.replace(v + 1);
+        v
+    }
+}
+
+#[component(scope_group: crate::UiScope)]
+pub trait MyComponent {
+    fn foo(&self) -> &crate::Foo;
+}
+
+#[component(scope_group: [crate::UiScope])]
+pub trait OtherComponent {
+    fn foo(&self) -> &crate::Foo;
+}
+// ANCHOR_END: scope_group
+
+#[test]
+pub fn scoped_in_each_component() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let other_component: Box<dyn OtherComponent> = <dyn OtherComponent>::new();
+
+    assert_eq!(component.foo().count(), 0);
+    assert_eq!(component.foo().count(), 1);
+    assert_eq!(other_component.foo().count(), 0);
+}
+epilogue!();
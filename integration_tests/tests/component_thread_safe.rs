@@ -0,0 +1,53 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{component, epilogue, injectable, module};
+use std::sync::Arc;
+
+pub struct Foo {
+    bar: Arc<String>,
+}
+
+#[injectable(scope: crate::MyComponent)]
+impl Foo {
+    #[inject]
+    pub fn new(bar: Arc<String>) -> Self {
+        Self { bar }
+    }
+}
+
+pub struct MyModule {}
+
+#[module]
+impl MyModule {
+    #[provides]
+    pub fn provide_bar() -> Arc<String> {
+        Arc::new("bar".to_owned())
+    }
+}
+
+#[component(modules: crate::MyModule, thread_safe: true)]
+pub trait MyComponent {
+    fn foo(&self) -> &crate::Foo;
+}
+
+#[test]
+pub fn main() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert_eq!(component.foo().bar.as_str(), "bar");
+}
+
+epilogue!();
@@ -0,0 +1,98 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{component, epilogue, injectable, module, Cl};
+use std::sync::Arc;
+use std::thread;
+
+pub trait Greeter: Send + Sync {
+    fn greet(&self) -> String;
+}
+
+pub struct Foo {}
+
+#[injectable(scope: crate::MyComponent)]
+impl Foo {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Greeter for Foo {
+    fn greet(&self) -> String {
+        "hello".to_owned()
+    }
+}
+
+pub struct MyModule {}
+
+#[module]
+impl MyModule {
+    #[binds]
+    pub fn bind_greeter(_impl: &crate::Foo) -> Cl<dyn crate::Greeter> {}
+}
+
+// ANCHOR: to_arc
+#[component(modules: crate::MyModule, thread_safe: true)]
+pub trait MyComponent {
+    fn foo(&self) -> Cl<crate::Foo>;
+    fn foo_ref(&self) -> &crate::Foo;
+    fn greeter(&self) -> Cl<dyn crate::Greeter>;
+}
+
+#[test]
+pub fn to_arc() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let foo: Arc<Foo> = component.foo().to_arc().unwrap();
+    assert!(std::ptr::eq(foo.as_ref(), component.foo_ref()));
+    // the handle outlives the component.
+    drop(component);
+
+    let greeting = thread::spawn(move || foo.greet()).join().unwrap();
+    assert_eq!(greeting, "hello");
+}
+
+#[test]
+pub fn to_arc_binds() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let greeter = component.greeter().to_arc().unwrap();
+    assert_eq!(greeter.greet(), "hello");
+}
+// ANCHOR_END: to_arc
+
+pub struct Bar {}
+
+#[injectable(scope: crate::OtherComponent)]
+impl Bar {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[component]
+pub trait OtherComponent {
+    fn bar(&self) -> Cl<crate::Bar>;
+}
+
+#[test]
+pub fn not_thread_safe() {
+    let component: Box<dyn OtherComponent> = <dyn OtherComponent>::new();
+    assert!(component.bar().to_arc().is_none());
+}
+
+epilogue!();
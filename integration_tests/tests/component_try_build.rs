@@ -0,0 +1,72 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{builder_modules, component, epilogue, module};
+
+pub struct MyModule {
+    string: String,
+}
+
+#[module]
+impl MyModule {
+    #[provides]
+    pub fn provide_string(&self) -> String {
+        self.string.clone()
+    }
+}
+
+#[builder_modules]
+pub struct MyModuleManifest {
+    my_module: crate::MyModule,
+}
+
+#[component(builder_modules: crate::MyModuleManifest)]
+pub trait MyComponent {
+    fn string(&self) -> String;
+}
+
+#[component]
+pub trait MyOtherComponent {}
+
+#[test]
+pub fn try_build() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::try_build(MyModuleManifest {
+        my_module: MyModule {
+            string: "foo".to_owned(),
+        },
+    })
+    .unwrap();
+    assert_eq!(component.string(), "foo");
+}
+
+#[test]
+pub fn try_build_without_modules() {
+    assert!(<dyn MyOtherComponent>::try_build().is_ok());
+}
+
+#[test]
+pub fn error_message() {
+    let error = lockjaw::Error::NotGenerated {
+        component: "MyComponent",
+    };
+    assert_eq!(
+        error.to_string(),
+        "MyComponent was not generated by lockjaw::epilogue!(), it may have been pruned by \
+        `prune_components` or built inside a doctest"
+    );
+}
+
+epilogue!();
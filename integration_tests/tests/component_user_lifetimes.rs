@@ -0,0 +1,74 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, epilogue, injectable, module, subcomponent, Cl};
+
+struct MyModule {}
+
+#[module(subcomponents: [MySubcomponent])]
+impl MyModule {
+    #[provides(scope: MyComponent)]
+    pub fn provide_string() -> String {
+        "foo".to_owned()
+    }
+}
+
+pub struct Foo<'b> {
+    pub i: i32,
+    pub phrase: &'b String,
+}
+
+#[injectable]
+impl<'b> Foo<'b> {
+    #[factory]
+    fn create(#[runtime] i: i32, phrase: &'b String) -> Foo<'b> {
+        Foo { i, phrase }
+    }
+}
+
+#[subcomponent]
+pub trait MySubcomponent<'b> {
+    fn phrase<'a>(&'a self) -> &'a String;
+    fn foo_factory<'a>(&'a self) -> FooFactory<'a>;
+}
+
+#[component(modules: MyModule)]
+pub trait MyComponent {
+    fn phrase<'a>(&'a self) -> &'a String;
+    fn sub<'a>(&'a self) -> Cl<'a, dyn MySubcomponentBuilder<'a>>;
+}
+
+#[test]
+pub fn component() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+
+    assert_eq!(component.phrase(), "foo");
+}
+
+#[test]
+pub fn subcomponent() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let sub = component.sub().build();
+
+    assert_eq!(sub.phrase(), "foo");
+    let foo = sub.foo_factory().create(42);
+    assert_eq!(foo.i, 42);
+    assert_eq!(foo.phrase, "foo");
+}
+
+epilogue!();
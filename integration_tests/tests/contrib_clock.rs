@@ -0,0 +1,71 @@
+/*
+Copyright 2020 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+#![allow(dead_code)]
+
+use lockjaw::contrib::clock::{
+    Clock, ManualClock, ManualClockModule, SystemClock, SystemClockModule,
+};
+use lockjaw::{component, epilogue, Cl};
+use std::time::{Duration, SystemTime};
+
+// ANCHOR: prod
+#[component(modules: [SystemClockModule])]
+pub trait ProdComponent {
+    fn clock(&self) -> Cl<dyn Clock>;
+}
+// ANCHOR_END: prod
+
+// ANCHOR: test
+#[component(modules: [ManualClockModule])]
+pub trait TestComponent {
+    fn clock(&self) -> Cl<dyn Clock>;
+    fn manual_clock(&self) -> &ManualClock;
+}
+// ANCHOR_END: test
+
+#[test]
+pub fn system_clock() {
+    let component: Box<dyn ProdComponent> = <dyn ProdComponent>::new();
+    let before = SystemTime::now();
+    let now = component.clock().now();
+    assert!(now >= before);
+    assert!(now <= SystemTime::now());
+}
+
+#[test]
+pub fn manual_clock() {
+    let component: Box<dyn TestComponent> = <dyn TestComponent>::new();
+    assert_eq!(component.clock().now(), SystemTime::UNIX_EPOCH);
+    component.manual_clock().advance(Duration::from_secs(60));
+    assert_eq!(
+        component.clock().now(),
+        SystemTime::UNIX_EPOCH + Duration::from_secs(60)
+    );
+    component
+        .manual_clock()
+        .set(SystemTime::UNIX_EPOCH + Duration::from_secs(3600));
+    assert_eq!(
+        component.clock().now(),
+        SystemTime::UNIX_EPOCH + Duration::from_secs(3600)
+    );
+}
+
+#[test]
+pub fn system_clock_direct() {
+    assert!(SystemClock::new().now() >= SystemTime::UNIX_EPOCH);
+}
+
+epilogue!();
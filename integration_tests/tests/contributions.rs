@@ -0,0 +1,87 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{component, epilogue, module, qualifier, Contributions};
+
+#[qualifier]
+pub struct Q;
+
+pub struct MyModule {}
+
+#[module]
+impl MyModule {
+    #[provides]
+    #[into_vec]
+    pub fn provide_string1() -> String {
+        "string1".to_owned()
+    }
+
+    #[provides]
+    #[elements_into_vec]
+    pub fn provide_strings() -> Vec<String> {
+        vec!["string2".to_owned(), "string3".to_owned()]
+    }
+
+    #[provides]
+    #[qualified(Q)]
+    #[into_vec]
+    pub fn provide_q_string() -> String {
+        "q_string".to_owned()
+    }
+}
+
+#[component(modules: [MyModule])]
+pub trait MyComponent {
+    // ANCHOR: component
+    fn strings(&self) -> Contributions<'_, String>;
+    // ANCHOR_END: component
+    #[qualified(Q)]
+    fn q_strings(&self) -> Contributions<'_, String>;
+    fn vec_strings(&self) -> Vec<String>;
+}
+
+#[test]
+pub fn contributions() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let strings = component.strings();
+    assert_eq!(strings.len(), 3);
+    let mut collected: Vec<&String> = strings.iter().collect();
+    collected.sort();
+    assert_eq!(collected, vec!["string1", "string2", "string3"]);
+}
+
+#[test]
+pub fn contributions_qualified() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let strings: Vec<&String> = component.q_strings().into_iter().collect();
+    assert_eq!(strings, vec!["q_string"]);
+}
+
+#[test]
+pub fn contributions_cached() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let first = component.strings().as_slice();
+    let second = component.strings().as_slice();
+    assert!(std::ptr::eq(first, second));
+}
+
+#[test]
+pub fn vec_not_affected() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert_eq!(component.vec_strings().len(), 3);
+}
+
+epilogue!();
@@ -0,0 +1,84 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{
+    define_component, entry_point, module, register_deferred, submit_deferred, Deferred,
+};
+
+pub trait Plugin {
+    fn name(&self) -> String;
+}
+
+struct MyModule {}
+
+#[module(install_in: MyComponent)]
+impl MyModule {
+    #[provides]
+    pub fn provide_prefix(&self) -> String {
+        "plugin:".to_owned()
+    }
+}
+
+#[entry_point(install_in: MyComponent)]
+pub trait PluginEntryPoint {
+    fn prefix(&self) -> String;
+}
+
+#[define_component]
+pub trait MyComponent {
+    fn plugins(&self) -> Deferred<Box<dyn Plugin>>;
+}
+
+// Pretend these are in crates the component does not know about.
+struct FooPlugin {
+    prefix: String,
+}
+
+impl Plugin for FooPlugin {
+    fn name(&self) -> String {
+        format!("{}foo", self.prefix)
+    }
+}
+
+struct BarPlugin {}
+
+impl Plugin for BarPlugin {
+    fn name(&self) -> String {
+        "bar".to_owned()
+    }
+}
+
+// ANCHOR: deferred
+submit_deferred!(dyn MyComponent, Box<dyn Plugin>, |component| {
+    Box::new(FooPlugin {
+        prefix: <dyn PluginEntryPoint>::get(component).prefix(),
+    })
+});
+// ANCHOR_END: deferred
+
+#[test]
+pub fn deferred() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let names: Vec<String> = component.plugins().iter().map(|p| p.name()).collect();
+    assert_eq!(names, vec!["plugin:foo"]);
+
+    register_deferred::<dyn MyComponent, Box<dyn Plugin>>(|_| Box::new(BarPlugin {}));
+
+    let names: Vec<String> = component.plugins().iter().map(|p| p.name()).collect();
+    assert_eq!(names, vec!["plugin:foo", "bar"]);
+}
+
+lockjaw::epilogue!();
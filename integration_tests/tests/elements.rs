@@ -0,0 +1,98 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, epilogue, module, qualifier, Elements};
+use std::cell::Cell;
+
+thread_local! {
+    static CREATED: Cell<usize> = Cell::new(0);
+}
+
+fn created() -> usize {
+    CREATED.with(|created| created.get())
+}
+
+fn create(name: &str) -> String {
+    CREATED.with(|created| created.set(created.get() + 1));
+    name.to_owned()
+}
+
+#[qualifier]
+pub struct Q;
+
+pub struct MyModule {}
+
+#[module]
+impl MyModule {
+    #[provides]
+    #[into_vec]
+    pub fn provide_foo() -> String {
+        create("foo")
+    }
+
+    #[provides]
+    #[elements_into_vec]
+    pub fn provide_bar_baz() -> Vec<String> {
+        vec![create("bar"), create("baz")]
+    }
+
+    #[provides]
+    #[qualified(Q)]
+    #[into_vec]
+    pub fn provide_q_string() -> String {
+        "q_string".to_owned()
+    }
+}
+
+#[component(modules: [MyModule])]
+pub trait MyComponent {
+    // ANCHOR: component
+    fn strings(&self) -> impl Iterator<Item = String> + '_;
+    // ANCHOR_END: component
+    fn elements(&self) -> Elements<'_, String>;
+    #[qualified(Q)]
+    fn q_strings(&self) -> impl Iterator<Item = String> + '_;
+}
+
+#[test]
+pub fn all_elements() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let mut strings: Vec<String> = component.strings().collect();
+    strings.sort();
+    assert_eq!(strings, vec!["bar", "baz", "foo"]);
+    assert_eq!(component.elements().count(), 3);
+}
+
+#[test]
+pub fn lazy() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let mut strings = component.strings();
+    assert_eq!(created(), 0);
+    strings.next().unwrap();
+    // only the first contribution is created, which is either "foo" or both "bar" and "baz".
+    assert!(created() <= 2);
+}
+
+#[test]
+pub fn qualified() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let strings: Vec<String> = component.q_strings().collect();
+    assert_eq!(strings, vec!["q_string"]);
+}
+
+epilogue!();
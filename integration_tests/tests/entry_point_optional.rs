@@ -0,0 +1,87 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{define_component, entry_point, module};
+
+struct MyModule {}
+
+#[module(install_in: MyComponent)]
+impl MyModule {
+    #[provides]
+    pub fn provide_i(&self) -> i32 {
+        42
+    }
+}
+
+// ANCHOR: optional
+#[entry_point(install_in: MyComponent)]
+pub trait MyEntryPoint {
+    #[optional]
+    fn i(&self) -> Option<i32>;
+
+    #[optional]
+    fn s(&self) -> Option<String>;
+
+    #[optional]
+    fn i_or_default(&self) -> i32 {
+        1
+    }
+
+    #[optional]
+    fn s_or_default(&self) -> String {
+        "default".to_owned()
+    }
+}
+// ANCHOR_END: optional
+
+#[define_component]
+pub trait MyComponent {}
+
+#[test]
+pub fn bound_returns_some() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+
+    assert_eq!(<dyn MyEntryPoint>::get(component.as_ref()).i(), Some(42));
+}
+
+#[test]
+pub fn unbound_returns_none() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+
+    assert_eq!(<dyn MyEntryPoint>::get(component.as_ref()).s(), None);
+}
+
+#[test]
+pub fn bound_overrides_default() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+
+    assert_eq!(
+        <dyn MyEntryPoint>::get(component.as_ref()).i_or_default(),
+        42
+    );
+}
+
+#[test]
+pub fn unbound_uses_default() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+
+    assert_eq!(
+        <dyn MyEntryPoint>::get(component.as_ref()).s_or_default(),
+        "default"
+    );
+}
+
+lockjaw::epilogue!();
@@ -0,0 +1,50 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{define_component, entry_point, module};
+
+struct MyModule {}
+
+#[module(install_in: MyComponent)]
+impl MyModule {
+    #[provides]
+    pub fn provide_i(&self) -> i32 {
+        42
+    }
+}
+
+#[entry_point(install_in: MyComponent)]
+pub trait MyEntryPoint {
+    fn i(&self) -> i32;
+}
+
+#[define_component]
+pub trait MyComponent {}
+
+#[test]
+pub fn main() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+
+    // ANCHOR: get_unchecked
+    let entry_point = unsafe { <dyn MyEntryPoint>::get_unchecked(component.as_ref()) };
+    // ANCHOR_END: get_unchecked
+    assert_eq!(entry_point.i(), 42);
+    assert_eq!(<dyn MyEntryPoint>::get(component.as_ref()).i(), 42);
+}
+
+lockjaw::epilogue!();
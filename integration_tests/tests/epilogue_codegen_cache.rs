@@ -0,0 +1,52 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{component, epilogue, injectable, module};
+
+pub struct Foo {
+    i: i32,
+}
+
+#[injectable]
+impl Foo {
+    #[inject]
+    pub fn new(i: i32) -> Self {
+        Self { i }
+    }
+}
+
+pub struct MyModule {}
+
+#[module]
+impl MyModule {
+    #[provides]
+    pub fn provide_i32() -> i32 {
+        42
+    }
+}
+
+#[component(modules: [MyModule])]
+pub trait MyComponent {
+    fn foo(&self) -> crate::Foo;
+}
+
+#[test]
+fn cached_component() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert_eq!(component.foo().i, 42);
+}
+
+epilogue!(codegen_cache);
@@ -0,0 +1,75 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{component, epilogue, injectable};
+
+pub struct Counter {
+    pub i: ::std::cell::RefCell<u32>,
+}
+
+#[injectable(scope: crate::MyComponent)]
+impl Counter {
+    #[inject]
+    pub fn new() -> Self {
+        Self {
+            i: Default::default(),
+        }
+    }
+
+    pub fn count(&self) -> u32 {
+        let v: u32 = self.i.borrow().clone();
+        self.i.replace(v + 1);
+        v
+    }
+}
+
+pub struct Foo {
+    bar: Bar,
+}
+
+#[injectable]
+impl Foo {
+    #[inject]
+    pub fn new(bar: Bar) -> Foo {
+        Foo { bar }
+    }
+}
+
+pub struct Bar {}
+
+#[injectable]
+impl Bar {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[component]
+pub trait MyComponent {
+    fn foo(&self) -> crate::Foo;
+    fn counter(&self) -> &crate::Counter;
+}
+
+#[test]
+pub fn main() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert_eq!(component.counter().count(), 0);
+    assert_eq!(component.counter().count(), 1);
+    let _bar = component.foo().bar;
+}
+
+epilogue!(inline_threshold(0));
@@ -0,0 +1,65 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(deprecated)]
+
+use lockjaw::{component, epilogue, injectable};
+use std::cell::RefCell;
+
+pub struct Stateless {}
+
+#[injectable(scope: crate::MyComponent)]
+impl Stateless {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+pub struct Stateful {
+    count: RefCell<i32>,
+}
+
+#[injectable(scope: crate::MyComponent)]
+impl Stateful {
+    #[inject]
+    pub fn new() -> Self {
+        Self {
+            count: RefCell::new(0),
+        }
+    }
+
+    pub fn increment(&self) -> i32 {
+        *self.count.borrow_mut() += 1;
+        *self.count.borrow()
+    }
+}
+
+#[component]
+pub trait MyComponent {
+    fn stateless(&self) -> &crate::Stateless;
+    fn stateful(&self) -> &crate::Stateful;
+}
+
+#[test]
+fn lint_does_not_change_bindings() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert!(std::ptr::eq(component.stateless(), component.stateless()));
+    component.stateful().increment();
+    assert_eq!(component.stateful().increment(), 2);
+}
+
+epilogue!(lint_stateless_scoped);
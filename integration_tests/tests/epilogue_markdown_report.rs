@@ -0,0 +1,58 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{define_component, define_subcomponent, epilogue, injectable, module, Cl};
+
+pub struct Counter {}
+
+#[injectable(scope: crate::MyComponent)]
+impl Counter {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+pub struct Submodule {}
+
+#[module(install_in: MySubcomponent)]
+impl Submodule {
+    #[provides]
+    pub fn provide_i32() -> i32 {
+        11
+    }
+}
+
+#[define_subcomponent(parent: MyComponent)]
+pub trait MySubcomponent<'a> {
+    fn i32(&self) -> i32;
+}
+
+#[define_component]
+pub trait MyComponent {
+    fn counter(&self) -> &crate::Counter;
+    fn sub(&'_ self) -> Cl<dyn MySubcomponentBuilder<'_>>;
+}
+
+#[test]
+pub fn main() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let _counter = component.counter();
+    let sub: Cl<dyn MySubcomponent> = component.sub().build();
+    assert_eq!(sub.i32(), 11);
+}
+
+epilogue!(markdown_report);
@@ -0,0 +1,47 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{component, epilogue, module};
+
+pub struct MyModule {}
+
+#[module]
+impl MyModule {
+    #[provides]
+    pub fn provide_i() -> i32 {
+        42
+    }
+}
+
+#[component(modules: MyModule)]
+pub trait UsedComponent {
+    fn i(&self) -> i32;
+}
+
+// Never instantiated, so no code is generated for it. The missing String binding would otherwise
+// fail the build.
+#[component]
+pub trait UnusedComponent {
+    fn s(&self) -> String;
+}
+
+#[test]
+pub fn main() {
+    let component: Box<dyn UsedComponent> = <dyn UsedComponent>::new();
+    assert_eq!(component.i(), 42);
+}
+
+epilogue!(prune_components);
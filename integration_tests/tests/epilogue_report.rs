@@ -0,0 +1,42 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{component, epilogue, injectable};
+
+pub struct Buffer {
+    pub data: [u8; 1024],
+}
+
+#[injectable(scope: crate::MyComponent)]
+impl Buffer {
+    #[inject]
+    pub fn new() -> Self {
+        Self { data: [0; 1024] }
+    }
+}
+
+#[component]
+pub trait MyComponent {
+    fn buffer(&self) -> &crate::Buffer;
+}
+
+#[test]
+pub fn main() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert_eq!(component.buffer().data.len(), 1024);
+}
+
+epilogue!(report);
@@ -0,0 +1,44 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{epilogue, injectable};
+
+pub struct Foo {
+    dep: ::test_dep::DepInjectable,
+}
+
+#[injectable]
+impl Foo {
+    #[inject]
+    pub fn new(dep: ::test_dep::DepInjectable) -> Self {
+        Self { dep }
+    }
+}
+
+// test_dep::DepModule is installed in every #[define_component], so the #[injectable]s of test_dep
+// are available even in strict mode.
+#[lockjaw::define_component]
+pub trait MyComponent {
+    fn foo(&self) -> crate::Foo;
+}
+
+#[test]
+fn main() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let _dep = component.foo().dep;
+}
+
+epilogue!(strict);
@@ -0,0 +1,110 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{component, epilogue, injectable, module, Cl, EventBus, Subscriber};
+use std::cell::RefCell;
+
+pub struct Started {
+    pub name: String,
+}
+
+pub struct Log {
+    pub entries: RefCell<Vec<String>>,
+}
+
+#[injectable(scope: crate::MyComponent)]
+impl Log {
+    #[inject]
+    pub fn new() -> Self {
+        Self {
+            entries: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+pub struct StartupLogger {
+    pub names: RefCell<Vec<String>>,
+}
+
+#[injectable(scope: crate::MyComponent)]
+impl StartupLogger {
+    #[inject]
+    pub fn new() -> Self {
+        Self {
+            names: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl Subscriber<Started> for StartupLogger {
+    fn on_event(&self, event: &Started) {
+        self.names.borrow_mut().push(event.name.clone());
+    }
+}
+
+pub struct MyModule {}
+
+// ANCHOR: module
+#[module]
+impl MyModule {
+    #[binds]
+    #[into_vec]
+    pub fn bind_startup_logger(impl_: &crate::StartupLogger) -> Cl<dyn Subscriber<Started>> {}
+
+    #[provides]
+    #[into_vec]
+    pub fn provide_closure_subscriber<'a>(log: &'a crate::Log) -> Cl<'a, dyn Subscriber<Started>> {
+        Cl::Val(Box::new(move |event: &Started| {
+            log.entries.borrow_mut().push(event.name.clone())
+        }))
+    }
+}
+// ANCHOR_END: module
+
+// ANCHOR: component
+#[component(modules: [MyModule])]
+pub trait MyComponent {
+    fn event_bus(&self) -> &EventBus<Started>;
+    // ANCHOR_END: component
+    fn new_event_bus(&self) -> EventBus<Started>;
+    fn log(&self) -> &crate::Log;
+    fn startup_logger(&self) -> &crate::StartupLogger;
+}
+
+#[test]
+pub fn publish() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    component.event_bus().publish(&Started {
+        name: "foo".to_owned(),
+    });
+    assert_eq!(*component.log().entries.borrow(), vec!["foo"]);
+    assert_eq!(*component.startup_logger().names.borrow(), vec!["foo"]);
+}
+
+#[test]
+pub fn event_bus_cached() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert!(std::ptr::eq(component.event_bus(), component.event_bus()));
+    assert_eq!(component.event_bus().len(), 2);
+}
+
+#[test]
+pub fn event_bus_by_value() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert_eq!(component.new_event_bus().len(), 2);
+}
+
+epilogue!();
@@ -0,0 +1,78 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, injectable, module};
+
+pub struct Foo {}
+
+#[cfg_attr(test, injectable)]
+impl Foo {
+    #[inject]
+    pub fn new() -> Foo {
+        Foo {}
+    }
+}
+
+pub struct Bar {}
+
+#[cfg_attr(test, allow(dead_code), injectable)]
+impl Bar {
+    #[inject]
+    pub fn new() -> Bar {
+        Bar {}
+    }
+}
+
+pub struct Baz {}
+
+#[cfg_attr(test, cfg_attr(any(unix, windows), injectable))]
+impl Baz {
+    #[inject]
+    pub fn new() -> Baz {
+        Baz {}
+    }
+}
+
+pub struct MyModule {}
+
+#[cfg_attr(test, module)]
+impl MyModule {
+    #[provides]
+    pub fn provide_string() -> String {
+        "string".to_owned()
+    }
+}
+
+#[component(modules: [MyModule])]
+pub trait MyComponent {
+    fn foo(&self) -> crate::Foo;
+    fn bar(&self) -> crate::Bar;
+    fn baz(&self) -> crate::Baz;
+    fn string(&self) -> String;
+}
+
+#[test]
+pub fn cfg_attr() {
+    let component = <dyn MyComponent>::new();
+    component.foo();
+    component.bar();
+    component.baz();
+    assert_eq!(component.string(), "string");
+}
+
+lockjaw::epilogue!();
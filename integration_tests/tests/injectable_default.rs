@@ -0,0 +1,52 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, injectable};
+
+#[injectable(default)]
+#[derive(Default)]
+pub struct Foo {
+    i: i32,
+}
+
+#[injectable(default: true, scope: crate::MyComponent)]
+#[derive(Default)]
+pub struct Bar {
+    s: String,
+}
+
+#[component]
+pub trait MyComponent {
+    fn foo(&self) -> crate::Foo;
+    fn bar(&self) -> &crate::Bar;
+}
+
+#[test]
+pub fn default() {
+    let component = <dyn MyComponent>::new();
+    assert_eq!(component.foo().i, 0);
+}
+
+#[test]
+pub fn scoped() {
+    let component = <dyn MyComponent>::new();
+    assert_eq!(component.bar().s, "");
+    assert!(std::ptr::eq(component.bar(), component.bar()));
+}
+
+lockjaw::epilogue!();
@@ -0,0 +1,106 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, injectable, module};
+
+pub enum Strategy {
+    Fast(i32),
+    Slow,
+}
+
+#[injectable]
+impl Strategy {
+    #[inject]
+    pub fn new(i: i32) -> Self {
+        Strategy::Fast(i)
+    }
+}
+
+#[lockjaw::component_visible]
+enum PrivateStrategy {
+    Fast,
+    Slow,
+}
+
+#[injectable]
+impl PrivateStrategy {
+    #[inject]
+    pub fn new() -> Self {
+        PrivateStrategy::Slow
+    }
+}
+
+pub struct Runner {
+    strategy: PrivateStrategy,
+}
+
+#[injectable]
+impl Runner {
+    #[inject]
+    pub fn new(strategy: crate::PrivateStrategy) -> Self {
+        Runner { strategy }
+    }
+}
+
+#[injectable(default)]
+#[derive(Default, PartialEq, Debug)]
+pub enum DefaultStrategy {
+    Fast,
+    #[default]
+    Slow,
+}
+
+struct MyModule;
+
+#[module]
+impl MyModule {
+    #[provides]
+    pub fn provide_i32() -> i32 {
+        32
+    }
+}
+
+#[component(modules: [MyModule])]
+pub trait MyComponent {
+    fn strategy(&self) -> crate::Strategy;
+    fn runner(&self) -> crate::Runner;
+    fn default_strategy(&self) -> crate::DefaultStrategy;
+}
+
+#[test]
+pub fn enum_injectable() {
+    let component = <dyn MyComponent>::new();
+    match component.strategy() {
+        Strategy::Fast(i) => assert_eq!(i, 32),
+        Strategy::Slow => panic!("unexpected variant"),
+    }
+}
+
+#[test]
+pub fn component_visible() {
+    let component = <dyn MyComponent>::new();
+    assert!(matches!(component.runner().strategy, PrivateStrategy::Slow));
+}
+
+#[test]
+pub fn default() {
+    let component = <dyn MyComponent>::new();
+    assert_eq!(component.default_strategy(), DefaultStrategy::Slow);
+}
+
+lockjaw::epilogue!();
@@ -0,0 +1,63 @@
+/*
+Copyright 2020 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+#![allow(dead_code)]
+
+use lockjaw::{component, epilogue, injectable, module};
+
+struct MyModule;
+
+#[module]
+impl MyModule {
+    #[provides]
+    pub fn provide_i32() -> i32 {
+        42
+    }
+}
+
+pub struct Foo {
+    pub i: i32,
+    pub s: String,
+}
+
+// ANCHOR: factory_generic
+#[injectable]
+impl Foo {
+    #[factory]
+    fn create<T>(i: i32, #[runtime] s: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self { i, s: s.into() }
+    }
+}
+// ANCHOR_END: factory_generic
+
+#[component(modules: [MyModule])]
+pub trait MyComponent {
+    fn foo_factory(&self) -> FooFactory;
+}
+
+#[test]
+pub fn main() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let foo = component.foo_factory().create("foo");
+    assert_eq!(foo.i, 42);
+    assert_eq!(foo.s, "foo");
+    let foo = component.foo_factory().create(String::from("bar"));
+    assert_eq!(foo.s, "bar");
+}
+
+epilogue!();
@@ -0,0 +1,97 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, epilogue, injectable, module, Cl};
+
+pub struct MyModule;
+
+#[module]
+impl MyModule {
+    #[provides]
+    pub fn provide_string() -> String {
+        "helloworld".to_owned()
+    }
+}
+
+pub trait Shape {
+    fn name(&self) -> String;
+}
+
+// ANCHOR: into_vec
+pub trait ShapeCreator {
+    fn create(&self, size: i32) -> Box<dyn Shape>;
+}
+
+pub struct Circle {
+    radius: i32,
+}
+
+impl Shape for Circle {
+    fn name(&self) -> String {
+        format!("circle {}", self.radius)
+    }
+}
+
+#[injectable]
+impl Circle {
+    #[factory(implementing: ShapeCreator, into_vec)]
+    fn create(#[runtime] size: i32) -> Box<dyn Shape> {
+        Box::new(Circle { radius: size })
+    }
+}
+// ANCHOR_END: into_vec
+
+pub struct Square {
+    side: i32,
+    label: String,
+}
+
+impl Shape for Square {
+    fn name(&self) -> String {
+        format!("{} square {}", self.label, self.side)
+    }
+}
+
+#[injectable]
+impl Square {
+    #[factory(implementing: ShapeCreator, into_vec)]
+    fn create(#[runtime] size: i32, label: String) -> Box<dyn Shape> {
+        Box::new(Square { side: size, label })
+    }
+}
+
+#[component(modules: MyModule)]
+pub trait MyComponent {
+    fn shape_creators(&'_ self) -> Vec<Cl<'_, dyn ShapeCreator>>;
+}
+
+#[test]
+pub fn main() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::build();
+
+    let mut names: Vec<String> = component
+        .shape_creators()
+        .iter()
+        .map(|creator| creator.create(2).name())
+        .collect();
+    names.sort();
+
+    assert_eq!(names, vec!["circle 2", "helloworld square 2"]);
+}
+
+epilogue!();
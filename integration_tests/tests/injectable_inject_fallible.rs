@@ -0,0 +1,104 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, epilogue, injectable};
+use std::cell::Cell;
+
+pub struct Attempts {
+    count: Cell<u32>,
+}
+
+#[injectable(scope: crate::MyComponent)]
+impl Attempts {
+    #[inject]
+    pub fn new() -> Self {
+        Self {
+            count: Cell::new(0),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ConnectError {
+    attempt: u32,
+}
+
+// ANCHOR: fallible
+pub struct Connection {
+    attempt: u32,
+}
+
+#[injectable(scope: crate::MyComponent)]
+impl Connection {
+    #[inject(fallible)]
+    pub fn new(attempts: &crate::Attempts) -> Result<Self, crate::ConnectError> {
+        let attempt = attempts.count.get() + 1;
+        attempts.count.set(attempt);
+        if attempt < 2 {
+            return Err(ConnectError { attempt });
+        }
+        Ok(Self { attempt })
+    }
+}
+// ANCHOR_END: fallible
+
+pub struct Config {
+    name: String,
+}
+
+#[injectable]
+impl Config {
+    #[inject(fallible)]
+    pub fn new() -> Result<Config, String> {
+        Ok(Config {
+            name: "config".to_owned(),
+        })
+    }
+}
+
+// ANCHOR: component
+#[component]
+pub trait MyComponent {
+    fn connection(&self) -> Result<&crate::Connection, crate::ConnectError>;
+
+    fn config(&self) -> Result<crate::Config, String>;
+
+    fn attempts(&self) -> &crate::Attempts;
+}
+// ANCHOR_END: component
+
+#[test]
+pub fn retry_after_failure() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert_eq!(
+        component.connection().err(),
+        Some(ConnectError { attempt: 1 })
+    );
+    let connection = component.connection().unwrap();
+    assert_eq!(connection.attempt, 2);
+    assert!(std::ptr::eq(connection, component.connection().unwrap()));
+    assert_eq!(component.attempts().count.get(), 2);
+}
+
+#[test]
+pub fn unscoped() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert_eq!(component.config().unwrap().name, "config");
+}
+
+epilogue!();
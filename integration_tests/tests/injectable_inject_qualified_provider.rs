@@ -0,0 +1,73 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{component, epilogue, injectable, module, qualifier, Lazy, Provider};
+
+#[qualifier]
+pub struct Q;
+
+pub struct Foo<'a> {
+    pub provider: Provider<'a, String>,
+    pub lazy: Lazy<'a, String>,
+    pub unqualified: Provider<'a, String>,
+}
+
+#[injectable]
+impl<'a> Foo<'a> {
+    #[inject]
+    pub fn new(
+        #[qualified(Q)] provider: Provider<'a, String>,
+        #[qualified(Q)] lazy: Lazy<'a, String>,
+        unqualified: Provider<'a, String>,
+    ) -> Foo<'a> {
+        Foo {
+            provider,
+            lazy,
+            unqualified,
+        }
+    }
+}
+
+pub struct MyModule {}
+
+#[module]
+impl MyModule {
+    #[provides]
+    #[qualified(Q)]
+    pub fn provide_q_string() -> String {
+        "q_string".to_owned()
+    }
+
+    #[provides]
+    pub fn provide_string() -> String {
+        "string".to_owned()
+    }
+}
+
+#[component(modules: [MyModule])]
+pub trait MyComponent {
+    fn foo(&self) -> crate::Foo;
+}
+
+#[test]
+pub fn main() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let foo = component.foo();
+    assert_eq!(foo.provider.get(), "q_string");
+    assert_eq!(foo.lazy.get(), "q_string");
+    assert_eq!(foo.unqualified.get(), "string");
+}
+epilogue!();
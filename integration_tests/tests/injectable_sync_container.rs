@@ -0,0 +1,77 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{component, epilogue, injectable};
+
+pub struct Counter {
+    i: u32,
+}
+
+#[injectable(scope: crate::MyComponent, container: Mutex)]
+impl Counter {
+    #[inject]
+    pub fn new() -> Self {
+        Self { i: 0 }
+    }
+
+    pub fn count(&mut self) -> u32 {
+        self.i += 1;
+        self.i
+    }
+}
+
+pub struct Config {
+    name: String,
+}
+
+#[injectable(scope: crate::MyComponent, container: RwLock)]
+impl Config {
+    #[inject]
+    pub fn new() -> Self {
+        Self {
+            name: "default".to_owned(),
+        }
+    }
+}
+
+#[component(thread_safe: true)]
+pub trait MyComponent {
+    fn counter(&self) -> &std::sync::Mutex<crate::Counter>;
+    fn config(&self) -> &std::sync::RwLock<crate::Config>;
+}
+
+#[test]
+pub fn mutex() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert_eq!(component.counter().lock().unwrap().count(), 1);
+    let counter = component.counter();
+    std::thread::scope(|scope| {
+        scope.spawn(move || {
+            assert_eq!(counter.lock().unwrap().count(), 2);
+        });
+    });
+    assert_eq!(component.counter().lock().unwrap().count(), 3);
+}
+
+#[test]
+pub fn rw_lock() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert_eq!(component.config().read().unwrap().name, "default");
+    component.config().write().unwrap().name = "changed".to_owned();
+    assert_eq!(component.config().read().unwrap().name, "changed");
+}
+
+epilogue!();
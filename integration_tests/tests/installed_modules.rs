@@ -0,0 +1,90 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{builder_modules, component, epilogue, module, InstalledModules};
+
+pub struct FooModule {}
+
+#[module]
+impl FooModule {
+    #[provides]
+    pub fn provide_i32() -> i32 {
+        42
+    }
+}
+
+pub struct BarModule {}
+
+#[module]
+impl BarModule {
+    #[provides]
+    pub fn provide_string() -> String {
+        "bar".to_owned()
+    }
+}
+
+pub struct BuilderModule {
+    value: u32,
+}
+
+#[module]
+impl BuilderModule {
+    #[provides]
+    pub fn provide_u32(&self) -> u32 {
+        self.value
+    }
+}
+
+#[builder_modules]
+pub struct MyBuilderModules {
+    builder_module: crate::BuilderModule,
+}
+
+// ANCHOR: component
+#[component(modules: [FooModule, BarModule], builder_modules: crate::MyBuilderModules)]
+pub trait MyComponent {
+    fn installed_modules(&self) -> InstalledModules;
+
+    fn i(&self) -> i32;
+    fn s(&self) -> String;
+    fn u(&self) -> u32;
+}
+// ANCHOR_END: component
+
+#[test]
+pub fn installed_modules() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::build(MyBuilderModules {
+        builder_module: BuilderModule { value: 1 },
+    });
+    let installed_modules = component.installed_modules();
+    assert_eq!(installed_modules.len(), 3);
+    assert!(installed_modules.names()[0].ends_with("::BarModule"));
+    assert!(installed_modules.names()[1].ends_with("::BuilderModule"));
+    assert!(installed_modules.names()[2].ends_with("::FooModule"));
+}
+
+#[component]
+pub trait EmptyComponent {
+    fn installed_modules(&self) -> InstalledModules;
+}
+
+#[test]
+pub fn no_modules() {
+    let component: Box<dyn EmptyComponent> = <dyn EmptyComponent>::new();
+    assert!(component.installed_modules().is_empty());
+}
+
+epilogue!();
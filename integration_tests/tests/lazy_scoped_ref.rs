@@ -0,0 +1,80 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{component, epilogue, injectable, Cl, Lazy};
+use std::cell::Cell;
+
+thread_local! {
+    static CREATED: Cell<i32> = Cell::new(0);
+}
+
+pub struct Expensive {
+    pub i: i32,
+}
+
+#[injectable(scope: crate::MyComponent)]
+impl Expensive {
+    #[inject]
+    pub fn new() -> Self {
+        CREATED.with(|created| created.set(created.get() + 1));
+        Self { i: 42 }
+    }
+}
+
+pub struct Foo<'a> {
+    expensive: Lazy<'a, &'a Expensive>,
+    expensive_cl: Lazy<'a, Cl<'a, Expensive>>,
+}
+
+#[injectable]
+impl<'a> Foo<'a> {
+    #[inject]
+    pub fn new(
+        expensive: Lazy<'a, &'a Expensive>,
+        expensive_cl: Lazy<'a, Cl<'a, Expensive>>,
+    ) -> Foo<'a> {
+        Foo {
+            expensive,
+            expensive_cl,
+        }
+    }
+
+    pub fn get(&self) -> i32 {
+        self.expensive.get().i
+    }
+
+    pub fn get_cl(&self) -> i32 {
+        self.expensive_cl.get().i
+    }
+}
+
+#[component]
+pub trait MyComponent {
+    fn foo(&self) -> crate::Foo;
+}
+
+#[test]
+pub fn main() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let foo = component.foo();
+    assert_eq!(CREATED.with(|created| created.get()), 0);
+
+    assert_eq!(foo.get(), 42);
+    assert_eq!(foo.get_cl(), 42);
+    assert_eq!(CREATED.with(|created| created.get()), 1);
+}
+
+epilogue!();
@@ -0,0 +1,88 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, injectable, module, Singleton};
+use lockjaw_common::query::{BindingKind, ManifestQuery};
+
+pub struct Foo {}
+
+#[injectable(scope: Singleton)]
+impl Foo {
+    #[inject]
+    pub fn new() -> Foo {
+        Foo {}
+    }
+}
+
+pub struct MyModule {}
+
+#[module]
+impl MyModule {
+    #[provides]
+    pub fn provide_string(_foo: &crate::Foo) -> String {
+        "string".to_owned()
+    }
+}
+
+#[component(modules: [MyModule])]
+pub trait MyComponent {
+    fn string(&self) -> String;
+}
+
+// ANCHOR: query
+fn query() -> ManifestQuery {
+    ManifestQuery::load(env!("LOCKJAW_DEP_MANIFEST"), env!("CARGO_CRATE_NAME"), true).unwrap()
+}
+
+#[test]
+pub fn component() {
+    let component = query()
+        .component("::lockjaw_integration_tests::MyComponent")
+        .unwrap();
+    assert!(!component.subcomponent);
+    assert_eq!(
+        component.modules,
+        vec!["::lockjaw_integration_tests::MyModule"]
+    );
+    assert_eq!(component.provisions, vec!["::std::string::String"]);
+}
+// ANCHOR_END: query
+
+#[test]
+pub fn bindings() {
+    let bindings = query().bindings_in("::lockjaw_integration_tests::MyComponent");
+    let foo = bindings
+        .iter()
+        .find(|binding| binding.type_ == "::lockjaw_integration_tests::Foo")
+        .unwrap();
+    assert_eq!(foo.kind, BindingKind::Injectable);
+    assert_eq!(foo.scopes, vec!["::lockjaw::Singleton"]);
+
+    let string = bindings
+        .iter()
+        .find(|binding| binding.type_ == "::std::string::String")
+        .unwrap();
+    assert_eq!(string.kind, BindingKind::Provides);
+    assert_eq!(string.declared_in, "::lockjaw_integration_tests::MyModule");
+    assert_eq!(
+        string.dependencies,
+        vec!["ref & ::lockjaw_integration_tests::Foo"]
+    );
+}
+
+lockjaw::epilogue!();
@@ -0,0 +1,49 @@
+/*
+Copyright 2020 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, epilogue};
+
+#[path = "path_attribute/renamed.rs"]
+mod foo;
+
+mod path_attribute_inline {
+    #[path = "child.rs"]
+    pub mod bar;
+}
+
+#[component]
+pub trait MyComponent {
+    fn foo(&self) -> crate::foo::Foo;
+    fn child(&self) -> crate::foo::child::Child;
+    fn bar(&self) -> crate::path_attribute_inline::bar::Bar;
+}
+
+#[test]
+pub fn path_attribute() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    component.foo();
+    component.child();
+}
+
+#[test]
+pub fn path_attribute_in_inline_mod() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    component.bar();
+}
+
+epilogue!();
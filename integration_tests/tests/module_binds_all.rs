@@ -0,0 +1,84 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, epilogue, injectable, module, Cl};
+use std::cell::RefCell;
+
+pub trait Logger {
+    fn log(&self, message: &str);
+}
+
+pub trait Flushable {
+    fn flush(&self) -> Vec<String>;
+}
+
+pub struct BufferedLogger {
+    buffer: RefCell<Vec<String>>,
+}
+
+#[injectable(scope: crate::MyComponent)]
+impl BufferedLogger {
+    #[inject]
+    pub fn new() -> Self {
+        Self {
+            buffer: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl Logger for BufferedLogger {
+    fn log(&self, message: &str) {
+        self.buffer.borrow_mut().push(message.to_owned());
+    }
+}
+
+impl Flushable for BufferedLogger {
+    fn flush(&self) -> Vec<String> {
+        self.buffer.borrow_mut().drain(..).collect()
+    }
+}
+
+pub struct MyModule {}
+
+// ANCHOR: binds_all
+#[module]
+impl MyModule {
+    #[binds_all(crate::Logger, crate::Flushable)]
+    pub fn bind_buffered_logger(_impl: &crate::BufferedLogger) {}
+}
+// ANCHOR_END: binds_all
+
+#[component(modules: [MyModule])]
+pub trait MyComponent {
+    fn logger(&'_ self) -> Cl<'_, dyn Logger>;
+    fn flushable(&'_ self) -> Cl<'_, dyn Flushable>;
+}
+
+#[test]
+pub fn same_instance() {
+    let component = <dyn MyComponent>::new();
+    component.logger().log("foo");
+    component.logger().log("bar");
+    assert_eq!(
+        component.flushable().flush(),
+        vec!["foo".to_owned(), "bar".to_owned()]
+    );
+    assert!(component.flushable().flush().is_empty());
+}
+
+epilogue!();
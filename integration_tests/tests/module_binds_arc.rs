@@ -0,0 +1,97 @@
+/*
+Copyright 2020 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, epilogue, injectable, module};
+use std::sync::Arc;
+
+pub trait Greeter: Send + Sync {
+    fn greet(&self) -> String;
+}
+
+pub struct GreeterImpl {}
+
+#[injectable]
+impl GreeterImpl {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Greeter for GreeterImpl {
+    fn greet(&self) -> String {
+        "hello".to_owned()
+    }
+}
+
+pub trait Counter {
+    fn name(&self) -> String;
+}
+
+pub struct CounterImpl {}
+
+#[injectable]
+impl CounterImpl {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Counter for CounterImpl {
+    fn name(&self) -> String {
+        "counter".to_owned()
+    }
+}
+
+pub struct MyModule {}
+
+#[module]
+impl MyModule {
+    #[binds]
+    pub fn bind_greeter(_impl: crate::GreeterImpl) -> Arc<dyn crate::Greeter> {}
+
+    #[binds(scope: crate::MyComponent)]
+    pub fn bind_counter(_impl: crate::CounterImpl) -> Arc<dyn crate::Counter> {}
+}
+
+#[component(modules: [MyModule])]
+pub trait MyComponent {
+    fn greeter(&self) -> Arc<dyn crate::Greeter>;
+    fn counter(&self) -> Arc<dyn crate::Counter>;
+}
+
+#[test]
+pub fn arc_binds() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let greeter = component.greeter();
+    let handle = std::thread::spawn(move || greeter.greet());
+    assert_eq!(handle.join().unwrap(), "hello");
+}
+
+#[test]
+pub fn scoped_arc_binds_shared() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let counter1 = component.counter();
+    let counter2 = component.counter();
+    assert!(Arc::ptr_eq(&counter1, &counter2));
+    drop(component);
+    assert_eq!(counter1.name(), "counter");
+}
+
+epilogue!();
@@ -0,0 +1,95 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{component, epilogue, module};
+
+pub trait Logger {
+    fn log(&self, message: &str) -> String;
+}
+
+pub struct ConsoleLogger {}
+
+impl Logger for ConsoleLogger {
+    fn log(&self, message: &str) -> String {
+        message.to_owned()
+    }
+}
+
+pub struct LoggerModule {}
+
+#[module]
+impl LoggerModule {
+    #[provides]
+    pub fn provide_logger() -> Box<dyn Logger> {
+        Box::new(ConsoleLogger {})
+    }
+
+    #[provides]
+    pub fn provide_prefix() -> String {
+        "app".to_owned()
+    }
+}
+
+// ANCHOR: decorator
+pub struct PrefixLogger {
+    inner: Box<dyn Logger>,
+    prefix: String,
+}
+
+impl Logger for PrefixLogger {
+    fn log(&self, message: &str) -> String {
+        format!("{}: {}", self.prefix, self.inner.log(message))
+    }
+}
+
+pub struct LevelLogger {
+    inner: Box<dyn Logger>,
+}
+
+impl Logger for LevelLogger {
+    fn log(&self, message: &str) -> String {
+        self.inner.log(&format!("[INFO] {}", message))
+    }
+}
+
+pub struct DecoratorModule {}
+
+#[module]
+impl DecoratorModule {
+    #[binds_decorator]
+    pub fn decorate_prefix(inner: Box<dyn Logger>, prefix: String) -> Box<dyn Logger> {
+        Box::new(PrefixLogger { inner, prefix })
+    }
+
+    #[binds_decorator(priority: -1)]
+    pub fn decorate_level(inner: Box<dyn Logger>) -> Box<dyn Logger> {
+        Box::new(LevelLogger { inner })
+    }
+}
+// ANCHOR_END: decorator
+
+#[component(modules: [LoggerModule, DecoratorModule])]
+pub trait MyComponent {
+    fn logger(&self) -> Box<dyn Logger>;
+}
+
+#[test]
+pub fn main() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert_eq!(component.logger().log("hello"), "app: [INFO] hello");
+}
+
+epilogue!();
@@ -16,10 +16,13 @@ limitations under the License.
 
 #![allow(dead_code)]
 
-use lockjaw::{component, epilogue, module};
+use lockjaw::{component, epilogue, module, qualifier};
 
 pub use String as NamedString;
 
+#[qualifier]
+pub struct Backup;
+
 pub struct MyModule {}
 
 #[module]
@@ -36,6 +39,22 @@ impl MyModule {
 
     #[binds_option_of]
     pub fn binds_option_of_i32() -> i32 {}
+
+    #[provides]
+    #[qualified(Backup)]
+    pub fn provide_backup_string() -> String {
+        "backup".to_owned()
+    }
+
+    // ANCHOR: qualified
+    #[binds_option_of]
+    #[qualified(Backup)]
+    pub fn binds_option_of_backup_string() -> String {}
+    // ANCHOR_END: qualified
+
+    #[binds_option_of]
+    #[qualified(Backup)]
+    pub fn binds_option_of_backup_i32() -> i32 {}
 }
 
 #[component(modules: [MyModule])]
@@ -44,6 +63,12 @@ pub trait MyComponent {
     fn option_string(&self) -> Option<String>;
     // ANCHOR_END: component
     fn option_i32(&self) -> Option<i32>;
+    // ANCHOR: qualified_component
+    #[qualified(Backup)]
+    fn option_backup_string(&self) -> Option<String>;
+    // ANCHOR_END: qualified_component
+    #[qualified(Backup)]
+    fn option_backup_i32(&self) -> Option<i32>;
 }
 
 #[test]
@@ -57,4 +82,17 @@ pub fn not_provided_empty() {
     let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
     assert_eq!(component.option_i32(), None);
 }
+
+#[test]
+pub fn qualified_provided_value_returned() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert_eq!(component.option_backup_string(), Some("backup".to_owned()));
+    assert_eq!(component.option_string(), Some("string".to_owned()));
+}
+
+#[test]
+pub fn qualified_not_provided_empty() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert_eq!(component.option_backup_i32(), None);
+}
 epilogue!();
@@ -0,0 +1,84 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, epilogue, module};
+use std::collections::HashMap;
+
+// ANCHOR: keys
+pub mod keys {
+    pub const HANDLER_A: &str = "a";
+    pub const HANDLER_B: &str = "b";
+    pub const PRIORITY_HIGH: i32 = 10;
+}
+// ANCHOR_END: keys
+
+pub struct MyModule {}
+
+#[module]
+impl MyModule {
+    // ANCHOR: const_key
+    #[provides]
+    #[into_map(const_key: crate::keys::HANDLER_A, key_type: String)]
+    pub fn provide_handler_a() -> String {
+        "handler_a".to_owned()
+    }
+    // ANCHOR_END: const_key
+
+    #[provides]
+    #[into_map(const_key: crate::keys::HANDLER_B, key_type: String)]
+    pub fn provide_handler_b() -> String {
+        "handler_b".to_owned()
+    }
+
+    #[provides]
+    #[into_map(string_key: "c")]
+    pub fn provide_handler_c() -> String {
+        "handler_c".to_owned()
+    }
+
+    #[provides]
+    #[into_map(const_key: crate::keys::PRIORITY_HIGH, key_type: i32)]
+    pub fn provide_priority_high() -> String {
+        "high".to_owned()
+    }
+}
+
+#[component(modules: [MyModule])]
+pub trait MyComponent {
+    fn handlers(&self) -> HashMap<String, String>;
+    fn priorities(&self) -> HashMap<i32, String>;
+}
+
+#[test]
+pub fn string_const_key() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let map = component.handlers();
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.get(keys::HANDLER_A).unwrap(), "handler_a");
+    assert_eq!(map.get(keys::HANDLER_B).unwrap(), "handler_b");
+    assert_eq!(map.get("c").unwrap(), "handler_c");
+}
+
+#[test]
+pub fn i32_const_key() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let map = component.priorities();
+    assert_eq!(map.get(&keys::PRIORITY_HIGH).unwrap(), "high");
+}
+
+epilogue!();
@@ -0,0 +1,106 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, epilogue, module};
+use std::collections::HashMap;
+
+// ANCHOR: key
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MyKey {
+    pub name: &'static str,
+    pub id: i32,
+}
+
+impl MyKey {
+    pub const fn new(name: &'static str, id: i32) -> Self {
+        MyKey { name, id }
+    }
+}
+
+pub const fn make_key(name: &'static str, id: i32) -> MyKey {
+    MyKey { name, id }
+}
+// ANCHOR_END: key
+
+pub const DEFAULT_ID: i32 = 0;
+
+pub struct MyModule {}
+
+#[module]
+impl MyModule {
+    // ANCHOR: expr_key
+    #[provides]
+    #[into_map(key: MyKey::new("a", 3))]
+    pub fn provide_a() -> String {
+        "a".to_owned()
+    }
+    // ANCHOR_END: expr_key
+
+    #[provides]
+    #[into_map(key: crate::MyKey::new("b", crate::DEFAULT_ID))]
+    pub fn provide_b() -> String {
+        "b".to_owned()
+    }
+}
+
+#[component(modules: [MyModule])]
+pub trait MyComponent {
+    fn map(&self) -> HashMap<MyKey, String>;
+}
+
+#[test]
+pub fn expr_key() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let map = component.map();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(&MyKey::new("a", 3)).unwrap(), "a");
+    assert_eq!(map.get(&MyKey::new("b", DEFAULT_ID)).unwrap(), "b");
+}
+
+pub struct DuplicatedModule {}
+
+#[module]
+impl DuplicatedModule {
+    #[provides]
+    #[into_map(key: MyKey::new("a", 3))]
+    pub fn provide_a() -> i32 {
+        1
+    }
+
+    #[provides]
+    // ANCHOR: key_type
+    #[into_map(key: make_key("a", 3), key_type: MyKey)]
+    pub fn provide_also_a() -> i32 {
+        2
+    }
+    // ANCHOR_END: key_type
+}
+
+#[component(modules: [DuplicatedModule])]
+pub trait DuplicatedComponent {
+    fn map(&self) -> HashMap<MyKey, i32>;
+}
+
+#[test]
+#[should_panic(expected = "found duplicated key")]
+pub fn expr_key_duplicated_at_runtime() {
+    let component: Box<dyn DuplicatedComponent> = <dyn DuplicatedComponent>::new();
+    component.map();
+}
+
+epilogue!();
@@ -0,0 +1,98 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{component, epilogue, injectable, module, Cl};
+use std::collections::HashMap;
+use std::ops::Deref;
+
+pub trait Handler {
+    fn handle(&self) -> String;
+}
+
+pub struct FooHandler {}
+
+#[injectable(scope: crate::MyComponent)]
+impl FooHandler {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Handler for FooHandler {
+    fn handle(&self) -> String {
+        "foo".to_owned()
+    }
+}
+
+pub struct BarHandler {}
+
+#[injectable(scope: crate::MyComponent)]
+impl BarHandler {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Handler for BarHandler {
+    fn handle(&self) -> String {
+        "bar".to_owned()
+    }
+}
+
+pub struct MyModule {}
+
+// ANCHOR: module
+#[module]
+impl MyModule {
+    #[binds]
+    #[into_map(string_key: "foo")]
+    pub fn bind_foo_handler(_impl: &crate::FooHandler) -> Cl<dyn crate::Handler> {}
+
+    #[binds]
+    #[into_map(string_key: "bar")]
+    pub fn bind_bar_handler(_impl: &crate::BarHandler) -> Cl<dyn crate::Handler> {}
+}
+// ANCHOR_END: module
+
+// ANCHOR: component
+#[component(modules: [MyModule])]
+pub trait MyComponent {
+    fn handlers(&'_ self) -> &'_ HashMap<String, Cl<'_, dyn crate::Handler>>;
+}
+// ANCHOR_END: component
+
+#[test]
+pub fn scoped_map() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let handlers = component.handlers();
+    assert_eq!(handlers.len(), 2);
+    assert_eq!(handlers.get("foo").unwrap().handle(), "foo");
+    assert_eq!(handlers.get("bar").unwrap().handle(), "bar");
+}
+
+#[test]
+pub fn scoped_map_cached() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert!(std::ptr::eq(component.handlers(), component.handlers()));
+    assert!(std::ptr::eq(
+        component.handlers().get("foo").unwrap().deref() as *const dyn Handler as *const u8,
+        component.handlers().get("foo").unwrap().deref() as *const dyn Handler as *const u8
+    ));
+}
+
+epilogue!();
@@ -0,0 +1,81 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, epilogue, module};
+
+pub struct Subsystem {
+    name: String,
+}
+
+pub struct SubsystemModule {}
+
+#[module]
+impl SubsystemModule {
+    #[provides]
+    pub fn provide_subsystem() -> Subsystem {
+        Subsystem {
+            name: "subsystem".to_owned(),
+        }
+    }
+}
+
+pub struct AdapterModule {}
+
+#[module]
+impl AdapterModule {
+    #[provides]
+    #[into_vec]
+    pub fn provide_core() -> String {
+        "core".to_owned()
+    }
+
+    // ANCHOR: if_present
+    #[provides]
+    #[into_vec(if_present: crate::Subsystem)]
+    pub fn provide_subsystem_adapter(subsystem: Subsystem) -> String {
+        format!("{} adapter", subsystem.name)
+    }
+    // ANCHOR_END: if_present
+}
+
+#[component(modules: [AdapterModule, SubsystemModule])]
+pub trait WithSubsystemComponent {
+    fn vec_string(&self) -> Vec<String>;
+}
+
+#[test]
+pub fn contributes_if_present() {
+    let component = <dyn WithSubsystemComponent>::new();
+    let v = component.vec_string();
+    assert_eq!(v.len(), 2);
+    assert!(v.contains(&"core".to_owned()));
+    assert!(v.contains(&"subsystem adapter".to_owned()));
+}
+
+#[component(modules: [AdapterModule])]
+pub trait WithoutSubsystemComponent {
+    fn vec_string(&self) -> Vec<String>;
+}
+
+#[test]
+pub fn skipped_if_absent() {
+    let component = <dyn WithoutSubsystemComponent>::new();
+    assert_eq!(component.vec_string(), vec!["core".to_owned()]);
+}
+
+epilogue!();
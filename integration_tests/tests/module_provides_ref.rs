@@ -0,0 +1,105 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{builder_modules, component, epilogue, injectable, module, Cl};
+
+pub struct Config {
+    pub name: String,
+    pub retries: u32,
+}
+
+// ANCHOR: provides_ref
+pub struct ConfigModule {
+    config: Config,
+}
+
+#[module]
+impl ConfigModule {
+    #[provides]
+    pub fn provide_config(&self) -> &crate::Config {
+        &self.config
+    }
+
+    #[provides]
+    pub fn provide_name(config: &crate::Config) -> &str {
+        &config.name
+    }
+}
+// ANCHOR_END: provides_ref
+
+// ANCHOR: inject_ref
+pub struct Greeter {
+    greeting: String,
+}
+
+#[injectable]
+impl Greeter {
+    #[inject]
+    pub fn new(name: &str) -> Greeter {
+        Greeter {
+            greeting: format!("hello {}", name),
+        }
+    }
+}
+// ANCHOR_END: inject_ref
+
+#[builder_modules]
+pub struct MyBuilderModules {
+    config: crate::ConfigModule,
+}
+
+#[component(builder_modules: crate::MyBuilderModules)]
+pub trait MyComponent {
+    fn config(&self) -> &crate::Config;
+    fn config_cl(&'_ self) -> Cl<'_, crate::Config>;
+    fn greeter(&self) -> crate::Greeter;
+}
+
+fn component() -> Box<dyn MyComponent> {
+    <dyn MyComponent>::build(MyBuilderModules {
+        config: ConfigModule {
+            config: Config {
+                name: "world".to_owned(),
+                retries: 3,
+            },
+        },
+    })
+}
+
+#[test]
+pub fn provides_ref() {
+    let component = component();
+    assert_eq!(component.config().retries, 3);
+    assert!(std::ptr::eq(component.config(), component.config()));
+}
+
+#[test]
+pub fn cl_ref() {
+    let component = component();
+    assert!(std::ptr::eq(
+        &*component.config_cl() as *const Config,
+        component.config()
+    ));
+}
+
+#[test]
+pub fn inject_ref() {
+    assert_eq!(component().greeter().greeting, "hello world");
+}
+
+epilogue!();
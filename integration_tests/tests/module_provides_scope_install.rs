@@ -0,0 +1,66 @@
+/*
+Copyright 2020 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{define_component, epilogue, module};
+use std::cell::RefCell;
+
+pub struct Counter {
+    i: RefCell<u32>,
+}
+
+impl Counter {
+    pub fn count(&self) -> u32 {
+        let v = *self.i.borrow();
+        self.i.replace(v + 1);
+        v
+    }
+}
+
+pub struct MyModule {}
+
+// ANCHOR: scope_install
+#[module(install_in: [FooComponent, BarComponent])]
+impl MyModule {
+    #[provides(scope: install)]
+    pub fn provide_counter() -> Counter {
+        Counter {
+            i: Default::default(),
+        }
+    }
+}
+
+#[define_component]
+pub trait FooComponent {
+    fn counter(&self) -> &Counter;
+}
+
+#[define_component]
+pub trait BarComponent {
+    fn counter(&self) -> &Counter;
+}
+// ANCHOR_END: scope_install
+
+#[test]
+pub fn main() {
+    let foo: Box<dyn FooComponent> = <dyn FooComponent>::new();
+    let bar: Box<dyn BarComponent> = <dyn BarComponent>::new();
+    assert_eq!(foo.counter().count(), 0);
+    assert_eq!(foo.counter().count(), 1);
+    assert_eq!(bar.counter().count(), 0);
+}
+epilogue!();
@@ -0,0 +1,81 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::Once;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
+
+#[test]
+pub fn get_or_init_once() {
+    let once = Once::new();
+    let mut calls = 0;
+    assert_eq!(once.try_get(), None);
+    assert_eq!(
+        *once.get_or_init(|| {
+            calls += 1;
+            "value".to_owned()
+        }),
+        "value"
+    );
+    assert_eq!(*once.get_or_init(|| "other".to_owned()), "value");
+    assert_eq!(calls, 1);
+    assert_eq!(once.try_get().map(String::as_str), Some("value"));
+    assert_eq!(once.into_inner(), Some("value".to_owned()));
+}
+
+#[test]
+pub fn get_mut() {
+    let mut once = Once::<i32>::default();
+    assert_eq!(once.get_mut(), None);
+    once.get_or_init(|| 1);
+    *once.get_mut().unwrap() += 1;
+    assert_eq!(once.try_get(), Some(&2));
+}
+
+#[test]
+pub fn poisoned() {
+    let once = Once::<i32>::new();
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        once.get_or_init(|| panic!("initializer panicked"));
+    }));
+    assert!(result.is_err());
+    assert_eq!(once.try_get(), None);
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| *once.get_or_init(|| 1)));
+    assert!(result.is_err());
+}
+
+#[test]
+pub fn thread_safe() {
+    let once = Arc::new(Once::new());
+    let calls = Arc::new(AtomicI32::new(0));
+    let threads: Vec<_> = (0..8)
+        .map(|_| {
+            let once = once.clone();
+            let calls = calls.clone();
+            std::thread::spawn(move || {
+                *once.get_or_init(|| {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    42
+                })
+            })
+        })
+        .collect();
+    for thread in threads {
+        assert_eq!(thread.join().unwrap(), 42);
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
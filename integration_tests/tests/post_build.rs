@@ -0,0 +1,105 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{component, epilogue, injectable, module, Cl, PostBuildListener};
+use std::cell::RefCell;
+
+pub struct Log {
+    entries: RefCell<Vec<String>>,
+}
+
+#[injectable(scope: crate::MyComponent)]
+impl Log {
+    #[inject]
+    pub fn new() -> Self {
+        Self {
+            entries: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn add(&self, entry: &str) {
+        self.entries.borrow_mut().push(entry.to_owned());
+    }
+}
+
+// ANCHOR: listener
+pub struct EarlyListener<'a> {
+    log: &'a Log,
+}
+
+impl PostBuildListener for EarlyListener<'_> {
+    fn on_post_build(&self) {
+        self.log.add("early");
+    }
+
+    fn priority(&self) -> i32 {
+        -1
+    }
+}
+// ANCHOR_END: listener
+
+pub struct MyModule {}
+
+// ANCHOR: module
+#[module]
+impl MyModule {
+    #[provides]
+    #[into_vec]
+    pub fn provide_listener<'a>(log: &'a crate::Log) -> Cl<'a, dyn PostBuildListener> {
+        Cl::Val(Box::new(move || log.add("default")))
+    }
+
+    #[provides]
+    #[into_vec]
+    pub fn provide_early_listener<'a>(log: &'a crate::Log) -> Cl<'a, dyn PostBuildListener> {
+        Cl::Val(Box::new(EarlyListener { log }))
+    }
+}
+// ANCHOR_END: module
+
+#[component(modules: [MyModule])]
+pub trait MyComponent {
+    fn log(&self) -> &crate::Log;
+}
+
+#[test]
+pub fn listeners_run_by_priority() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert_eq!(*component.log().entries.borrow(), vec!["early", "default"]);
+}
+
+pub struct Foo {}
+
+#[injectable]
+impl Foo {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[component]
+pub trait NoListenerComponent {
+    fn foo(&self) -> crate::Foo;
+}
+
+#[test]
+pub fn no_listeners() {
+    let component: Box<dyn NoListenerComponent> = <dyn NoListenerComponent>::new();
+    component.foo();
+}
+
+epilogue!();
@@ -0,0 +1,76 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, epilogue, injectable, module, qualifier};
+
+//ANCHOR: decl
+#[qualifier]
+pub enum Bucket {
+    Primary,
+    Secondary,
+}
+//ANCHOR_END: decl
+
+pub struct Foo {
+    pub s: String,
+}
+
+#[injectable]
+impl Foo {
+    #[inject]
+    pub fn new(#[qualified(Bucket::Secondary)] s: String) -> Foo {
+        Foo { s }
+    }
+}
+
+pub struct MyModule {}
+
+//ANCHOR: module
+#[module]
+impl MyModule {
+    #[provides]
+    #[qualified(Bucket::Primary)]
+    pub fn provide_primary_string() -> String {
+        "primary".to_owned()
+    }
+
+    #[provides]
+    #[qualified(Bucket::Secondary)]
+    pub fn provide_secondary_string() -> String {
+        "secondary".to_owned()
+    }
+}
+//ANCHOR_END: module
+
+#[component(modules: [MyModule])]
+pub trait MyComponent {
+    #[qualified(Bucket::Primary)]
+    fn primary(&self) -> String;
+    #[qualified(Bucket::Secondary)]
+    fn secondary(&self) -> String;
+    fn foo(&self) -> Foo;
+}
+
+#[test]
+pub fn main() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    assert_eq!(component.primary(), "primary");
+    assert_eq!(component.secondary(), "secondary");
+    assert_eq!(component.foo().s, "secondary");
+}
+epilogue!();
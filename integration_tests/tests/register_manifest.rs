@@ -0,0 +1,65 @@
+/*
+Copyright 2020 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, epilogue, injectable};
+
+macro_rules! injectable_unit {
+    ($name:ident) => {
+        pub struct $name {}
+
+        #[injectable]
+        impl $name {
+            #[inject]
+            pub fn new() -> Self {
+                Self {}
+            }
+        }
+    };
+}
+
+injectable_unit!(Generated);
+
+lockjaw::register_manifest! {
+    #[injectable]
+    impl Generated {
+        #[inject]
+        pub fn new() -> Self {}
+    }
+}
+
+include!("register_manifest/included.rs");
+
+#[component]
+pub trait MyComponent {
+    fn generated(&self) -> crate::Generated;
+    fn included(&self) -> crate::Included;
+}
+
+#[test]
+pub fn macro_generated() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    component.generated();
+}
+
+#[test]
+pub fn included() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    component.included();
+}
+
+epilogue!();
@@ -0,0 +1,89 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{component, epilogue, injectable};
+use std::sync::Mutex;
+
+static DROPS: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+pub struct Database {}
+
+#[injectable(scope: crate::MyComponent)]
+impl Database {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Drop for Database {
+    fn drop(&mut self) {
+        DROPS.lock().unwrap().push("Database");
+    }
+}
+
+pub struct Repository {}
+
+#[injectable(scope: crate::MyComponent)]
+impl Repository {
+    #[inject]
+    pub fn new(_database: &crate::Database) -> Self {
+        Self {}
+    }
+}
+
+impl Drop for Repository {
+    fn drop(&mut self) {
+        DROPS.lock().unwrap().push("Repository");
+    }
+}
+
+pub struct Service {}
+
+#[injectable(scope: crate::MyComponent)]
+impl Service {
+    #[inject]
+    pub fn new(_repository: &crate::Repository, _database: &crate::Database) -> Self {
+        Self {}
+    }
+}
+
+impl Drop for Service {
+    fn drop(&mut self) {
+        DROPS.lock().unwrap().push("Service");
+    }
+}
+
+// the dependencies are requested before the bindings depending on them.
+#[component]
+pub trait MyComponent {
+    fn database(&self) -> &crate::Database;
+    fn repository(&self) -> &crate::Repository;
+    fn service(&self) -> &crate::Service;
+}
+
+#[test]
+pub fn dependents_dropped_first() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    component.service();
+    drop(component);
+    assert_eq!(
+        *DROPS.lock().unwrap(),
+        vec!["Service", "Repository", "Database"]
+    );
+}
+
+epilogue!();
@@ -0,0 +1,83 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+#![allow(dead_code)]
+
+use lockjaw::{component, epilogue, injectable, Cl, WeakCl};
+use std::rc::Rc;
+
+// ANCHOR: weak
+pub struct Counter {}
+
+#[injectable(scope: crate::MyComponent, container: Rc)]
+impl Counter {
+    #[inject]
+    pub fn new() -> Counter {
+        Counter {}
+    }
+}
+
+#[component]
+pub trait MyComponent {
+    fn counter(&self) -> &Rc<crate::Counter>;
+    fn weak_counter(&self) -> std::rc::Weak<crate::Counter>;
+}
+// ANCHOR_END: weak
+
+#[test]
+fn rc_container() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let weak = component.weak_counter();
+    assert!(Rc::ptr_eq(&weak.upgrade().unwrap(), component.counter()));
+    drop(component);
+    assert!(weak.upgrade().is_none());
+}
+
+pub struct Registry {}
+
+#[injectable(scope: crate::ThreadSafeComponent)]
+impl Registry {
+    #[inject]
+    pub fn new() -> Registry {
+        Registry {}
+    }
+}
+
+#[component(thread_safe: true)]
+pub trait ThreadSafeComponent {
+    fn registry(&'_ self) -> Cl<'_, crate::Registry>;
+    fn weak_registry(&self) -> WeakCl<crate::Registry>;
+    fn sync_weak_registry(&self) -> std::sync::Weak<crate::Registry>;
+}
+
+#[test]
+fn arc_backed() {
+    let component: Box<dyn ThreadSafeComponent> = <dyn ThreadSafeComponent>::new();
+    let weak = component.weak_registry();
+    let downgraded = component.registry().downgrade().unwrap();
+    let sync_weak = component.sync_weak_registry();
+    assert!(std::ptr::eq(
+        &*weak.upgrade().unwrap(),
+        &*component.registry()
+    ));
+    assert!(downgraded.upgrade().is_some());
+    drop(component);
+    assert!(weak.upgrade().is_none());
+    assert!(downgraded.upgrade().is_none());
+    assert!(sync_weak.upgrade().is_none());
+}
+
+epilogue!();
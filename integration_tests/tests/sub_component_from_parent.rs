@@ -0,0 +1,74 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use lockjaw::{component, injectable, module, subcomponent, Cl, FromParent};
+
+// ANCHOR: decorator
+pub struct Decorator {
+    parent: String,
+}
+
+#[injectable]
+impl Decorator {
+    #[inject]
+    pub fn new(#[qualified(FromParent)] parent: String) -> Decorator {
+        Decorator { parent }
+    }
+}
+
+struct Submodule {}
+
+#[module]
+impl Submodule {
+    #[provides]
+    pub fn provide_string(decorator: Decorator) -> String {
+        format!("[{}]", decorator.parent)
+    }
+}
+
+#[subcomponent(modules: [Submodule])]
+pub trait MySubcomponent<'a> {
+    fn string(&self) -> String;
+
+    #[qualified(FromParent)]
+    fn parent_string(&self) -> String;
+}
+// ANCHOR_END: decorator
+
+struct MyModule {}
+
+#[module(subcomponents: [MySubcomponent])]
+impl MyModule {
+    #[provides]
+    pub fn provide_string() -> String {
+        "parent".to_owned()
+    }
+}
+
+#[component(modules: [MyModule])]
+pub trait MyComponent {
+    fn sub(&'_ self) -> Cl<dyn MySubcomponentBuilder<'_>>;
+}
+
+#[test]
+pub fn main() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let sub: Cl<dyn MySubcomponent> = component.sub().build();
+
+    assert_eq!(sub.string(), "[parent]");
+    assert_eq!(sub.parent_string(), "parent");
+}
+
+lockjaw::epilogue!();
@@ -0,0 +1,153 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw::{component, module, subcomponent, Cl};
+use std::collections::HashMap;
+
+struct GreatGrandchildModule {}
+
+#[module]
+impl GreatGrandchildModule {
+    #[provides]
+    #[into_vec]
+    pub fn provide_string() -> String {
+        "great grandchild".to_owned()
+    }
+
+    #[provides]
+    #[into_map(i32_key: 4)]
+    pub fn provide_string_map() -> String {
+        "great grandchild".to_owned()
+    }
+}
+
+#[subcomponent(modules: [GreatGrandchildModule])]
+pub trait MyGreatGrandchild<'a> {
+    fn vec(&self) -> Vec<String>;
+    fn map(&self) -> HashMap<i32, String>;
+}
+
+// the grandchild has no contributions, and does not request the multibindings itself.
+struct GrandchildModule {}
+
+#[module(subcomponents: [MyGreatGrandchild])]
+impl GrandchildModule {}
+
+#[subcomponent(modules: [GrandchildModule])]
+pub trait MyGrandchild<'a> {
+    fn great_grandchild(&'_ self) -> Cl<dyn MyGreatGrandchildBuilder<'_>>;
+}
+
+struct ChildModule {}
+
+#[module(subcomponents: [MyGrandchild])]
+impl ChildModule {
+    #[provides]
+    #[into_vec]
+    pub fn provide_string() -> String {
+        "child".to_owned()
+    }
+
+    #[provides]
+    #[into_map(i32_key: 2)]
+    pub fn provide_string_map() -> String {
+        "child".to_owned()
+    }
+}
+
+#[subcomponent(modules: [ChildModule])]
+pub trait MyChild<'a> {
+    fn grandchild(&'_ self) -> Cl<dyn MyGrandchildBuilder<'_>>;
+    fn vec(&self) -> Vec<String>;
+}
+
+struct MyModule {}
+
+#[module(subcomponents: [MyChild])]
+impl MyModule {
+    #[provides]
+    #[into_vec]
+    pub fn provide_string() -> String {
+        "component".to_owned()
+    }
+
+    #[provides]
+    #[elements_into_vec]
+    pub fn provide_strings() -> Vec<String> {
+        vec!["component elements".to_owned()]
+    }
+
+    #[provides]
+    #[into_map(i32_key: 1)]
+    pub fn provide_string_map() -> String {
+        "component".to_owned()
+    }
+}
+
+#[component(modules: [MyModule])]
+pub trait MyComponent {
+    fn child(&'_ self) -> Cl<dyn MyChildBuilder<'_>>;
+}
+
+fn sorted(mut v: Vec<String>) -> Vec<String> {
+    v.sort();
+    v
+}
+
+#[test]
+pub fn into_vec_inherited_by_child() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let child: Cl<dyn MyChild> = component.child().build();
+
+    assert_eq!(
+        sorted(child.vec()),
+        vec!["child", "component", "component elements"]
+    );
+}
+
+#[test]
+pub fn into_vec_inherited_through_every_level() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let child: Cl<dyn MyChild> = component.child().build();
+    let grandchild: Cl<dyn MyGrandchild> = child.grandchild().build();
+    let great_grandchild: Cl<dyn MyGreatGrandchild> = grandchild.great_grandchild().build();
+
+    assert_eq!(
+        sorted(great_grandchild.vec()),
+        vec![
+            "child",
+            "component",
+            "component elements",
+            "great grandchild"
+        ]
+    );
+}
+
+#[test]
+pub fn into_map_inherited_through_every_level() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let child: Cl<dyn MyChild> = component.child().build();
+    let grandchild: Cl<dyn MyGrandchild> = child.grandchild().build();
+    let great_grandchild: Cl<dyn MyGreatGrandchild> = grandchild.great_grandchild().build();
+
+    let map = great_grandchild.map();
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.get(&1).unwrap(), "component");
+    assert_eq!(map.get(&2).unwrap(), "child");
+    assert_eq!(map.get(&4).unwrap(), "great grandchild");
+}
+
+lockjaw::epilogue!();
@@ -0,0 +1,72 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use lockjaw::{component, module, subcomponent, Cl};
+
+struct GrandchildModule {}
+
+#[module]
+impl GrandchildModule {
+    #[provides]
+    pub fn provide_string(i: i32, u: u32) -> String {
+        format!("{} {}", i, u)
+    }
+}
+
+#[subcomponent(modules: [GrandchildModule])]
+pub trait MyGrandchild<'a> {
+    fn string(&self) -> String;
+}
+
+struct ChildModule {}
+
+#[module(subcomponents: [MyGrandchild])]
+impl ChildModule {
+    #[provides]
+    pub fn provide_u32() -> u32 {
+        22
+    }
+}
+
+#[subcomponent(modules: [ChildModule])]
+pub trait MyChild<'a> {
+    fn grandchild(&'_ self) -> Cl<dyn MyGrandchildBuilder<'_>>;
+}
+
+struct MyModule {}
+
+#[module(subcomponents: [MyChild])]
+impl MyModule {
+    #[provides]
+    pub fn provide_i32() -> i32 {
+        11
+    }
+}
+
+#[component(modules: [MyModule])]
+pub trait MyComponent {
+    fn child(&'_ self) -> Cl<dyn MyChildBuilder<'_>>;
+}
+
+#[test]
+pub fn main() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let child: Cl<dyn MyChild> = component.child().build();
+    let grandchild: Cl<dyn MyGrandchild> = child.grandchild().build();
+
+    assert_eq!(grandchild.string(), "11 22");
+}
+
+lockjaw::epilogue!();
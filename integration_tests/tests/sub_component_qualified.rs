@@ -0,0 +1,101 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use lockjaw::{component, module, qualifier, subcomponent, Cl};
+
+#[qualifier]
+pub enum Renderer {
+    Main,
+    Preview,
+}
+
+pub struct RendererConfig {
+    pub width: i32,
+}
+
+#[subcomponent]
+pub trait RendererComponent<'a> {
+    fn config(&self) -> crate::RendererConfig;
+    fn name(&self) -> String;
+}
+
+// ANCHOR: modules
+struct MainRendererModule {}
+
+#[module(subcomponents: [RendererComponent], subcomponent_qualifier: Renderer::Main)]
+impl MainRendererModule {}
+
+struct PreviewRendererModule {}
+
+#[module(subcomponents: [RendererComponent], subcomponent_qualifier: Renderer::Preview)]
+impl PreviewRendererModule {}
+// ANCHOR_END: modules
+
+struct ParentModule {}
+
+// ANCHOR: config
+#[module]
+impl ParentModule {
+    #[provides]
+    #[qualified(Renderer::Main)]
+    pub fn provide_main_config() -> crate::RendererConfig {
+        crate::RendererConfig { width: 1920 }
+    }
+
+    #[provides]
+    #[qualified(Renderer::Preview)]
+    pub fn provide_preview_config() -> crate::RendererConfig {
+        crate::RendererConfig { width: 320 }
+    }
+    // ANCHOR_END: config
+
+    #[provides]
+    pub fn provide_name() -> String {
+        "renderer".to_owned()
+    }
+}
+
+// ANCHOR: component
+#[component(modules: [ParentModule, MainRendererModule, PreviewRendererModule])]
+pub trait MyComponent {
+    #[qualified(Renderer::Main)]
+    fn main_renderer(&'_ self) -> Cl<dyn RendererComponentBuilder<'_>>;
+
+    #[qualified(Renderer::Preview)]
+    fn preview_renderer(&'_ self) -> Cl<dyn RendererComponentBuilder<'_>>;
+}
+// ANCHOR_END: component
+
+#[test]
+pub fn qualified_parent_binding() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let main: Cl<dyn RendererComponent> = component.main_renderer().build();
+    let preview: Cl<dyn RendererComponent> = component.preview_renderer().build();
+
+    assert_eq!(main.config().width, 1920);
+    assert_eq!(preview.config().width, 320);
+}
+
+#[test]
+pub fn unqualified_parent_binding() {
+    let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+    let main: Cl<dyn RendererComponent> = component.main_renderer().build();
+    let preview: Cl<dyn RendererComponent> = component.preview_renderer().build();
+
+    assert_eq!(main.name(), "renderer");
+    assert_eq!(preview.name(), "renderer");
+}
+
+lockjaw::epilogue!();
@@ -0,0 +1,104 @@
+/*
+Copyright 2021 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use lockjaw::{builder_modules, component, module, subcomponent, Cl};
+
+pub struct Request {
+    path: String,
+}
+
+#[module]
+impl Request {
+    #[provides]
+    pub fn provide_path(&self) -> String {
+        self.path.clone()
+    }
+}
+
+#[subcomponent(builder_modules: crate::RequestModules)]
+pub trait RequestComponent<'a> {
+    fn path(&self) -> String;
+}
+
+#[builder_modules]
+pub struct RequestModules {
+    request: crate::Request,
+}
+
+struct AppModule {}
+
+#[module(subcomponents: [RequestComponent])]
+impl AppModule {}
+
+#[component(modules: [AppModule])]
+pub trait AppComponent {
+    fn request_component(&'_ self) -> Cl<dyn RequestComponentBuilder<'_>>;
+}
+
+// ANCHOR: scope
+async fn handle() -> String {
+    tokio::task::yield_now().await;
+    lockjaw::current::<dyn RequestComponent<'static>, _>(|component| component.path())
+}
+
+#[tokio::test]
+async fn current() {
+    let app: &'static dyn AppComponent = Box::leak(<dyn AppComponent>::new());
+    let component = app.request_component().build(RequestModules {
+        request: Request {
+            path: "/index".to_owned(),
+        },
+    });
+
+    let path = lockjaw::task_local::scope(&*component, handle()).await;
+    // ANCHOR_END: scope
+
+    assert_eq!(path, "/index");
+}
+
+#[tokio::test]
+async fn nested() {
+    let app: &'static dyn AppComponent = Box::leak(<dyn AppComponent>::new());
+    let outer = app.request_component().build(RequestModules {
+        request: Request {
+            path: "/outer".to_owned(),
+        },
+    });
+    let inner = app.request_component().build(RequestModules {
+        request: Request {
+            path: "/inner".to_owned(),
+        },
+    });
+
+    let paths = lockjaw::task_local::scope(&*outer, async {
+        let before = handle().await;
+        let nested = lockjaw::task_local::scope(&*inner, handle()).await;
+        let after = handle().await;
+        vec![before, nested, after]
+    })
+    .await;
+
+    assert_eq!(paths, vec!["/outer", "/inner", "/outer"]);
+}
+
+#[tokio::test]
+async fn outside_scope() {
+    assert_eq!(
+        lockjaw::task_local::try_current::<dyn RequestComponent<'static>, _>(|c| c.path()),
+        None
+    );
+}
+
+lockjaw::epilogue!();
@@ -31,6 +31,16 @@ trait DepTrait {}
 
 impl DepTrait for DepPrivate {}
 
+pub trait DepGreeter {
+    fn greet(&self) -> String;
+}
+
+impl DepGreeter for DepPrivate {
+    fn greet(&self) -> String {
+        "dep".to_owned()
+    }
+}
+
 pub struct DepInjectable {}
 
 #[lockjaw::injectable]
@@ -55,13 +65,39 @@ impl DepModule {
 
     #[binds]
     pub fn bind_dep_trait(_impl: DepPrivate) -> Cl<dyn DepTrait> {}
+
+    #[binds]
+    pub fn bind_dep_greeter(_impl: DepPrivate) -> Cl<dyn crate::DepGreeter> {}
 }
 
+/// ```
+/// use test_dep::DepComponent;
+///
+/// fn main() {
+///     assert!(<dyn DepComponent>::try_build().is_err());
+/// }
+///
+/// lockjaw::epilogue!();
+/// ```
 #[lockjaw::component(modules: DepModule)]
 pub trait DepComponent {
     fn dep(&self) -> crate::DepInjectable;
 }
 
+/// Only generated by crates depending on `test_dep` with the `extra` feature.
+#[cfg(feature = "extra")]
+#[lockjaw::component(modules: DepModule)]
+pub trait DepExtraComponent {
+    fn dep(&self) -> crate::DepInjectable;
+}
+
+/// Never enabled, generating it fails to compile since the trait does not exist.
+#[cfg(feature = "unused")]
+#[lockjaw::component(modules: DepModule)]
+pub trait DepUnusedComponent {
+    fn dep(&self) -> crate::DepInjectable;
+}
+
 #[lockjaw::define_component]
 pub trait DepDefinedComponent {}
 
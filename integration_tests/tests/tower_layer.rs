@@ -0,0 +1,90 @@
+/*
+Copyright 2021 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use lockjaw::tower::ComponentLayer;
+use lockjaw::{builder_modules, component, injectable, module, subcomponent, Cl};
+use tower::{service_fn, Layer, ServiceExt};
+
+pub struct RequestModule {
+    path: String,
+}
+
+#[module]
+impl RequestModule {
+    #[provides]
+    pub fn provide_path(&self) -> String {
+        self.path.clone()
+    }
+}
+
+#[derive(Clone)]
+pub struct Handler {
+    path: String,
+}
+
+#[injectable]
+impl Handler {
+    #[inject]
+    pub fn new(path: String) -> Self {
+        Self { path }
+    }
+}
+
+#[builder_modules]
+pub struct RequestModules {
+    request: crate::RequestModule,
+}
+
+#[subcomponent(builder_modules: crate::RequestModules)]
+pub trait RequestComponent<'a> {
+    fn handler(&self) -> crate::Handler;
+}
+
+struct AppModule {}
+
+#[module(subcomponents: [RequestComponent])]
+impl AppModule {}
+
+#[component(modules: [AppModule])]
+pub trait AppComponent {
+    fn request_component(&'_ self) -> Cl<dyn RequestComponentBuilder<'_>>;
+}
+
+#[tokio::test]
+async fn provides_dependencies_from_request_component() {
+    let app: &'static dyn AppComponent = Box::leak(<dyn AppComponent>::new());
+    let layer = ComponentLayer::new(move |request: &http::Request<()>| {
+        app.request_component()
+            .build(RequestModules {
+                request: RequestModule {
+                    path: request.uri().path().to_owned(),
+                },
+            })
+            .handler()
+    });
+    let service = layer.layer(service_fn(|request: http::Request<()>| async move {
+        let handler = request.extensions().get::<Handler>().unwrap().clone();
+        Ok::<String, ()>(handler.path)
+    }));
+
+    let response = service
+        .oneshot(http::Request::get("/user").body(()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response, "/user");
+}
+
+lockjaw::epilogue!();
@@ -0,0 +1,73 @@
+/*
+Copyright 2020 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use lockjaw_common::environment::normalize_windows_path;
+
+#[test]
+pub fn mixed_separators() {
+    assert_eq!(
+        normalize_windows_path(r"C:\workspace/target\debug/build"),
+        r"C:\workspace\target\debug\build"
+    );
+}
+
+#[test]
+pub fn verbatim_drive() {
+    assert_eq!(
+        normalize_windows_path(r"\\?\D:\workspace\target\lockjaw"),
+        r"D:\workspace\target\lockjaw"
+    );
+}
+
+#[test]
+pub fn verbatim_unc() {
+    assert_eq!(
+        normalize_windows_path(r"\\?\UNC\server\share\target\lockjaw"),
+        r"\\server\share\target\lockjaw"
+    );
+}
+
+#[test]
+pub fn unc() {
+    assert_eq!(
+        normalize_windows_path(r"\\server\share/target/lockjaw"),
+        r"\\server\share\target\lockjaw"
+    );
+}
+
+#[test]
+pub fn drive_relative() {
+    assert_eq!(
+        normalize_windows_path(r"D:target/lockjaw"),
+        r"D:target\lockjaw"
+    );
+}
+
+#[cfg(windows)]
+#[test]
+pub fn normalize_path_is_absolute() {
+    let path = lockjaw_common::environment::normalize_path(std::path::Path::new("target/lockjaw"));
+    assert!(path.is_absolute());
+    assert!(!path.to_string_lossy().contains('/'));
+    assert!(!path.to_string_lossy().starts_with(r"\\?\"));
+}
+
+#[cfg(not(windows))]
+#[test]
+pub fn normalize_path_is_absolute() {
+    let path = lockjaw_common::environment::normalize_path(std::path::Path::new("target/lockjaw"));
+    assert!(path.is_absolute());
+}
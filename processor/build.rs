@@ -14,6 +14,10 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
 fn main() {
     if rustc_version::version_meta()
         .expect("cannot find rustc version")
@@ -24,4 +28,41 @@ fn main() {
     } else {
         println!("cargo:rustc-cfg=stable");
     }
+    // identifies the processor sources, so `epilogue!(codegen_cache)` is invalidated when they
+    // change even if the version is unchanged (e.g. path or git dependencies).
+    let manifest_dir = std::path::PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let mut hasher = DefaultHasher::new();
+    std::env::var("CARGO_PKG_VERSION")
+        .unwrap()
+        .hash(&mut hasher);
+    hash_dir(&manifest_dir, &manifest_dir.join("src"), &mut hasher);
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=build.rs");
+    println!(
+        "cargo:rustc-env=LOCKJAW_PROCESSOR_BUILD_ID={:016x}",
+        hasher.finish()
+    );
+}
+
+/// Hashes the path and content of every file under `dir`, in a stable order.
+fn hash_dir(base: &Path, dir: &Path, hasher: &mut DefaultHasher) {
+    let mut entries: Vec<_> = std::fs::read_dir(dir)
+        .expect("cannot read processor sources")
+        .map(|entry| entry.expect("cannot read processor sources").path())
+        .collect();
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            hash_dir(base, &path, hasher);
+        } else {
+            path.strip_prefix(base)
+                .unwrap()
+                .to_string_lossy()
+                .replace('\\', "/")
+                .hash(hasher);
+            std::fs::read(&path)
+                .expect("cannot read processor sources")
+                .hash(hasher);
+        }
+    }
 }
@@ -0,0 +1,246 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! `assert_binding_exists!()`, which checks the graph of a component at compile time and expands to
+//! a panic if the binding is not found, so architecture tests fail like regular tests.
+//!
+//! The macro cannot resolve `use` declarations, so the paths are matched against the suffix of the
+//! full paths in the manifest instead.
+
+use crate::error::spanned_compile_error;
+use crate::graph;
+use lockjaw_common::manifest::{Component, Manifest, TypeRoot};
+use lockjaw_common::type_data::TypeData;
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::{GenericArgument, PathArguments, Token};
+
+struct AssertBindingExists {
+    component: syn::Path,
+    type_: syn::Type,
+    scope: Option<syn::Path>,
+    qualifier: Option<syn::Path>,
+}
+
+impl Parse for AssertBindingExists {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let component = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let type_ = input.parse()?;
+        let mut scope = None;
+        let mut qualifier = None;
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let key: syn::Ident = input.parse()?;
+            input.parse::<Token![:]>()?;
+            match key.to_string().as_str() {
+                "scope" => scope = Some(input.parse()?),
+                "qualified" => qualifier = Some(input.parse()?),
+                _ => return Err(syn::Error::new(key.span(), format!("unknown key: {}", key))),
+            }
+        }
+        Ok(AssertBindingExists {
+            component,
+            type_,
+            scope,
+            qualifier,
+        })
+    }
+}
+
+pub fn handle_assert_binding_exists(input: TokenStream) -> Result<TokenStream, TokenStream> {
+    let span = input.span();
+    let assertion: AssertBindingExists =
+        syn::parse2(input).map_err(|err| err.to_compile_error())?;
+    let manifest = crate::crate_manifest(true)?;
+    let component = find_component(&manifest, &assertion.component)?;
+    let (graph, _) = graph::build_graph(&manifest, &component, &Vec::new())?;
+
+    let candidates: Vec<&TypeData> = graph
+        .map
+        .values()
+        .map(|node| node.get_type())
+        .filter(|type_data| type_data.identifier_suffix.is_empty())
+        .filter(|type_data| type_matches(type_data, &assertion.type_))
+        .filter(
+            |type_data| match (&type_data.qualifier, &assertion.qualifier) {
+                (None, None) => true,
+                (Some(qualifier), Some(path)) => path_matches(qualifier, path),
+                _ => false,
+            },
+        )
+        .collect();
+    let type_ = &assertion.type_;
+    let type_string = quote! {#type_}.to_string();
+    let Some(binding) = candidates.first() else {
+        return Ok(assertion_failure(
+            span,
+            &format!(
+                "{} does not provide a binding for {}",
+                component.type_data.readable(),
+                type_string
+            ),
+        ));
+    };
+    if candidates.len() > 1 {
+        return spanned_compile_error(
+            assertion.type_.span(),
+            &format!(
+                "{} matches multiple bindings in {}: {}, use a longer path",
+                type_string,
+                component.type_data.readable(),
+                candidates
+                    .iter()
+                    .map(|candidate| candidate.readable())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        );
+    }
+    if let Some(ref scope) = assertion.scope {
+        if !binding
+            .scopes
+            .iter()
+            .any(|binding_scope| path_matches(binding_scope, scope))
+        {
+            return Ok(assertion_failure(
+                span,
+                &format!(
+                    "{} in {} is not scoped to {}",
+                    binding.readable(),
+                    component.type_data.readable(),
+                    quote! {#scope}
+                ),
+            ));
+        }
+    }
+    Ok(quote! {})
+}
+
+fn assertion_failure(span: proc_macro2::Span, message: &str) -> TokenStream {
+    quote_spanned! {span=>
+        ::lockjaw::__private::std::panic!(#message)
+    }
+}
+
+fn find_component(manifest: &Manifest, path: &syn::Path) -> Result<Component, TokenStream> {
+    let components: Vec<&Component> = manifest
+        .components
+        .iter()
+        .filter(|component| path_matches(&component.type_data, path))
+        .collect();
+    match components.as_slice() {
+        [component] => Ok((*component).clone()),
+        [] => spanned_compile_error(
+            path.span(),
+            &format!(
+                "component {} not found in the lockjaw manifest",
+                quote! {#path}
+            ),
+        ),
+        _ => spanned_compile_error(
+            path.span(),
+            &format!(
+                "{} matches multiple components, use a longer path",
+                quote! {#path}
+            ),
+        ),
+    }
+}
+
+/// The segments of the type, starting with the crate name.
+fn full_segments(type_data: &TypeData) -> Vec<String> {
+    let mut segments = Vec::new();
+    if type_data.root == TypeRoot::CRATE {
+        segments.push(type_data.field_crate.replace('-', "_"));
+    }
+    segments.extend(type_data.path.split("::").map(str::to_owned));
+    segments
+}
+
+/// Whether `path` is a suffix of the type's full path, and the generic arguments match.
+fn path_matches(type_data: &TypeData, path: &syn::Path) -> bool {
+    let full_segments = full_segments(type_data);
+    let segments: Vec<&syn::PathSegment> = path
+        .segments
+        .iter()
+        .skip_while(|segment| segment.ident == "crate")
+        .collect();
+    if segments.is_empty() || segments.len() > full_segments.len() {
+        return false;
+    }
+    let offset = full_segments.len() - segments.len();
+    for (i, segment) in segments.iter().enumerate() {
+        if segment.ident != full_segments[offset + i] {
+            return false;
+        }
+    }
+    match &segments.last().unwrap().arguments {
+        PathArguments::None => type_data.args.is_empty(),
+        PathArguments::AngleBracketed(arguments) => {
+            let types: Vec<&syn::Type> = arguments
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    GenericArgument::Type(ty) => Some(ty),
+                    _ => None,
+                })
+                .collect();
+            types.len() == type_data.args.len()
+                && types
+                    .iter()
+                    .zip(type_data.args.iter())
+                    .all(|(ty, arg)| type_matches(arg, ty))
+        }
+        PathArguments::Parenthesized(_) => false,
+    }
+}
+
+fn type_matches(type_data: &TypeData, ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Reference(reference) => {
+            if !type_data.field_ref {
+                return false;
+            }
+            let mut inner = type_data.clone();
+            inner.field_ref = false;
+            type_matches(&inner, &reference.elem)
+        }
+        syn::Type::Path(type_path) => {
+            !type_data.field_ref
+                && !type_data.trait_object
+                && path_matches(type_data, &type_path.path)
+        }
+        syn::Type::TraitObject(trait_object) => {
+            if !type_data.trait_object {
+                return false;
+            }
+            trait_object.bounds.iter().any(|bound| match bound {
+                syn::TypeParamBound::Trait(trait_bound) => {
+                    path_matches(type_data, &trait_bound.path)
+                }
+                _ => false,
+            })
+        }
+        syn::Type::Paren(paren) => type_matches(type_data, &paren.elem),
+        _ => false,
+    }
+}
@@ -0,0 +1,175 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Caches the code generated for each component with `epilogue!(codegen_cache)`, so components
+//! whose part of the manifest is unchanged are not regenerated on incremental builds.
+//!
+//! Each component is keyed by the hash of the manifest without the other root components, the
+//! epilogue options affecting the generated code, and the lockjaw version and the sources of
+//! `lockjaw_processor` and `lockjaw_common`.
+
+use crate::environment;
+use lockjaw_common::manifest::{
+    Component, ComponentType, Manifest, LOCKJAW_COMMON_BUILD_ID, LOCKJAW_VERSION,
+};
+use proc_macro2::TokenStream;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+
+/// Fields serialized from a `HashSet`, whose order changes every time the manifest is loaded.
+const SET_FIELDS: &[&str] = &[
+    "scopes",
+    "subcomponents",
+    "install_in",
+    "lifetimed_types",
+    "instantiated_components",
+    "private_types",
+];
+
+pub struct CodegenCache {
+    dir: PathBuf,
+    /// Identifies the target being compiled. The lib and test targets of a package share the output
+    /// dir but are generated from different manifests.
+    target: String,
+    /// Hash of everything except the components.
+    base_hash: u64,
+}
+
+impl CodegenCache {
+    /// `options` are the epilogue options affecting the generated code.
+    pub fn new(
+        manifest: &Manifest,
+        options: &str,
+        for_test: bool,
+    ) -> Result<CodegenCache, TokenStream> {
+        let mut shared = manifest.clone();
+        shared.components.clear();
+        let mut hasher = DefaultHasher::new();
+        LOCKJAW_VERSION.hash(&mut hasher);
+        LOCKJAW_COMMON_BUILD_ID.hash(&mut hasher);
+        env!("LOCKJAW_PROCESSOR_BUILD_ID").hash(&mut hasher);
+        cfg!(feature = "profiling").hash(&mut hasher);
+        options.hash(&mut hasher);
+        canonical_json(&shared).hash(&mut hasher);
+        Ok(CodegenCache {
+            dir: environment::lockjaw_output_dir()?.join("codegen_cache"),
+            target: format!(
+                "{}_{}_{}",
+                std::env::var("CARGO_CRATE_NAME").unwrap_or_default(),
+                std::env::var("CARGO_BIN_NAME").unwrap_or_default(),
+                if for_test { "test" } else { "prod" }
+            ),
+            base_hash: hasher.finish(),
+        })
+    }
+
+    /// Hash of the manifest subset `component` is generated from. Other root components never
+    /// affect the graph of `component`, so they are excluded.
+    pub fn key(&self, manifest: &Manifest, component: &Component) -> u64 {
+        let components: Vec<&Component> = manifest
+            .components
+            .iter()
+            .filter(|other| {
+                other.component_type != ComponentType::Component
+                    || other.type_data == component.type_data
+            })
+            .collect();
+        let mut hasher = DefaultHasher::new();
+        self.base_hash.hash(&mut hasher);
+        canonical_json(&components).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Returns the code generated for `component` if it was cached with the same `key`.
+    ///
+    /// The cached code is re-parsed from its text, so every token is spanned to the `epilogue!()`
+    /// invocation. The generated code is built from the manifest and carries no other spans, so
+    /// diagnostics in it point at the same place as when it is generated.
+    pub fn get(&self, component: &Component, key: u64) -> Option<TokenStream> {
+        let content = std::fs::read_to_string(self.path(component)).ok()?;
+        let (header, tokens) = content.split_once('\n')?;
+        if header != format!("// {:016x}", key) {
+            return None;
+        }
+        tokens.parse().ok()
+    }
+
+    /// Caches the code generated for `component`. Failures are ignored since the cache is only an
+    /// optimization.
+    pub fn put(&self, component: &Component, key: u64, tokens: &TokenStream) {
+        if std::fs::create_dir_all(&self.dir).is_err() {
+            return;
+        }
+        let _ = std::fs::write(
+            self.path(component),
+            format!("// {:016x}\n{}", key, tokens.to_string()),
+        );
+    }
+
+    fn path(&self, component: &Component) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        self.target.hash(&mut hasher);
+        component.type_data.identifier_string().hash(&mut hasher);
+        self.dir.join(format!("{:016x}.rs", hasher.finish()))
+    }
+}
+
+/// Serializes `value` with sorted object keys and sets, so equal manifests are always serialized
+/// the same.
+fn canonical_json<T: serde::Serialize>(value: &T) -> String {
+    let mut result = String::new();
+    write_canonical(
+        &serde_json::to_value(value).expect("cannot serialize manifest"),
+        false,
+        &mut result,
+    );
+    result
+}
+
+fn write_canonical(value: &serde_json::Value, sort: bool, out: &mut String) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for key in keys {
+                out.push_str(&serde_json::Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(&map[key], SET_FIELDS.contains(&key.as_str()), out);
+                out.push(',');
+            }
+            out.push('}');
+        }
+        serde_json::Value::Array(items) => {
+            let mut items: Vec<String> = items
+                .iter()
+                .map(|item| {
+                    let mut item_out = String::new();
+                    write_canonical(item, false, &mut item_out);
+                    item_out
+                })
+                .collect();
+            if sort {
+                items.sort();
+            }
+            out.push('[');
+            out.push_str(&items.join(","));
+            out.push(']');
+        }
+        _ => out.push_str(&value.to_string()),
+    }
+}
@@ -21,7 +21,7 @@ use lockjaw_common::type_data::TypeData;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use syn::spanned::Spanned;
-use syn::{ItemStruct, ItemTrait, Token, Visibility};
+use syn::{ItemEnum, ItemStruct, ItemTrait, Token, Visibility};
 
 pub fn handle_component_visible_attribute(
     _attr: TokenStream,
@@ -31,6 +31,10 @@ pub fn handle_component_visible_attribute(
         return handle_item_struct(item_struct);
     };
 
+    if let Ok(item_enum) = syn::parse2::<syn::ItemEnum>(input.clone()) {
+        return handle_item_enum(item_enum);
+    };
+
     if let Ok(item_trait) = syn::parse2::<syn::ItemTrait>(input.clone()) {
         return handle_item_trait(item_trait);
     };
@@ -54,6 +58,23 @@ fn handle_item_struct(mut item_struct: ItemStruct) -> Result<TokenStream, TokenS
     })
 }
 
+fn handle_item_enum(mut item_enum: ItemEnum) -> Result<TokenStream, TokenStream> {
+    let original_ident = item_enum.ident.clone();
+    let original_vis = item_enum.vis.clone();
+    let exported_ident = format_ident!("lockjaw_export_type_{}", original_ident);
+
+    item_enum.ident = exported_ident.clone();
+    item_enum.vis = Visibility::Public(Token![pub](item_enum.span()));
+
+    Ok(quote! {
+        #original_vis use #exported_ident as #original_ident;
+
+        #[doc(hidden)]
+        #[allow(non_camel_case_types)]
+        #item_enum
+    })
+}
+
 fn handle_item_trait(mut item_trait: ItemTrait) -> Result<TokenStream, TokenStream> {
     let original_ident = item_trait.ident.clone();
     let original_vis = item_trait.vis.clone();
@@ -97,9 +118,11 @@ pub fn visible_type(manifest: &Manifest, type_: &TypeData) -> TypeData {
     } else {
         type_.clone()
     };
-    for i in 0..type_.args.len() {
-        result.args[i] = visible_type(manifest, &type_.args[i]);
-    }
+    result.args = type_
+        .args
+        .iter()
+        .map(|arg| visible_type(manifest, arg))
+        .collect();
     result
 }
 
@@ -14,8 +14,11 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::ops::Deref;
 
+use crate::codegen_cache::CodegenCache;
+use crate::component_visibles;
 use crate::error::{spanned_compile_error, CompileError};
 use crate::graph;
 use crate::parsing;
@@ -24,19 +27,36 @@ use crate::type_data::ProcessorTypeData;
 use crate::type_validator::TypeValidator;
 use base64::engine::Engine;
 use lazy_static::lazy_static;
-use lockjaw_common::manifest::{ComponentType, Manifest};
+use lockjaw_common::environment::{current_package, current_version, symbol_suffix};
+use lockjaw_common::manifest::{
+    BindingType, BuilderModules, Component, ComponentType, Dependency, Manifest, Module,
+    MultibindingType, TypeRoot,
+};
 use lockjaw_common::type_data::TypeData;
 use proc_macro2::{Ident, TokenStream};
 use quote::quote_spanned;
 use quote::{format_ident, quote, ToTokens};
 use syn::spanned::Spanned;
-use syn::{Attribute, ItemTrait};
+use syn::{Attribute, ItemTrait, ReturnType, TraitItem};
 
 lazy_static! {
     static ref COMPONENT_METADATA_KEYS: HashSet<String> = {
         let mut set = HashSet::<String>::new();
         set.insert("modules".to_owned());
         set.insert("builder_modules".to_owned());
+        set.insert("auto_default_zst".to_owned());
+        set.insert("scope_group".to_owned());
+        set.insert("thread_safe".to_owned());
+        set.insert("fragments".to_owned());
+        set
+    };
+}
+
+lazy_static! {
+    static ref COMPONENT_FRAGMENT_METADATA_KEYS: HashSet<String> = {
+        let mut set = HashSet::<String>::new();
+        set.insert("modules".to_owned());
+        set.insert("fragments".to_owned());
         set
     };
 }
@@ -59,8 +79,39 @@ pub fn handle_component_attribute(
         syn::parse2(input).map_spanned_compile_error(span, "trait expected")?;
 
     let mut type_validator = TypeValidator::new();
+    validate_generics(&item_trait, &component_type)?;
+    validate_provision_names(&item_trait, &component_type)?;
+
+    let provisioned = if component_type == ComponentType::Subcomponent {
+        quote! {}
+    } else {
+        provisioned_impls(&item_trait)?
+    };
+    parse_provisions(&mut item_trait, &mut type_validator, false)?;
+    resolve_iterator_provisions(&mut item_trait);
+    resolve_impl_trait_provisions(&mut item_trait)?;
+    if cfg!(feature = "profiling") {
+        item_trait.items.push(syn::parse_quote! {
+            /// Returns how many times each `#[injectable]` and `#[provides]` binding has been
+            /// constructed by the component. Only generated with the `profiling` feature.
+            fn lockjaw_stats(&self) -> ::lockjaw::__private::std::vec::Vec<(&'static str, u64)> {
+                ::lockjaw::__private::std::vec::Vec::new()
+            }
+        });
+    }
 
-    parse_provisions(&mut item_trait, &mut type_validator)?;
+    let (attr, instance_bindings) = parsing::take_type_array(attr, "instance_bindings")?;
+    if let Some(type_) = instance_bindings.first() {
+        if component_type == ComponentType::Subcomponent {
+            return spanned_compile_error(
+                type_.span(),
+                "instance_bindings is only allowed in #[component]",
+            );
+        }
+    }
+    for type_ in &instance_bindings {
+        type_validator.add_type(type_, type_.span());
+    }
 
     let attributes = parsing::get_attribute_field_values(attr.clone())?;
     for key in attributes.keys() {
@@ -105,6 +156,30 @@ pub fn handle_component_attribute(
         }
     }
 
+    if let Some(value) = attributes.get("auto_default_zst") {
+        if !matches!(value, FieldValue::BoolLiteral(..)) {
+            return spanned_compile_error(value.span(), "bool expected for auto_default_zst");
+        }
+    }
+
+    if let Some(value) = attributes.get("thread_safe") {
+        if !matches!(value, FieldValue::BoolLiteral(..)) {
+            return spanned_compile_error(value.span(), "bool expected for thread_safe");
+        }
+    }
+
+    if let Some(value) = attributes.get("scope_group") {
+        for (path, span) in value.get_paths()? {
+            type_validator.add_dyn_path(&path, span);
+        }
+    }
+
+    if let Some(value) = attributes.get("fragments") {
+        for (path, span) in value.get_paths()? {
+            type_validator.add_dyn_path(&path, span);
+        }
+    }
+
     let component_vis = item_trait.vis.clone();
 
     let component_builder = if component_type == ComponentType::Subcomponent {
@@ -115,66 +190,131 @@ pub fn handle_component_attribute(
         } else {
             quote! {}
         };
+        let lifetime = component_lifetime();
         quote! {
-            #component_vis trait #builder_name<'a> {
-                fn build(&self, #args) -> ::lockjaw::Cl<'a, dyn #subcomponent_name<'a>>;
+            #component_vis trait #builder_name<#lifetime> {
+                fn build(&self, #args) -> ::lockjaw::Cl<#lifetime, dyn #subcomponent_name<#lifetime>>;
             }
         }
     } else {
         let component_name = item_trait.ident.clone();
         let address_ident = format_ident!("LOCKJAW_COMPONENT_BUILDER_ADDR_{}", item_trait.ident);
-        let components_initializer_name = format_ident!("lockjaw_init_root_components");
+        let components_initializer_name =
+            root_components_initializer(&symbol_suffix(&current_package(), &current_version()));
+        let component_name_str = component_name.to_string();
+        let generic = !item_trait.generics.params.is_empty();
+        let (impl_generics, ty_generics, where_clause) = item_trait.generics.split_for_impl();
+        // the builders of generic components are looked up by the TypeId of the instantiation.
+        let static_bounds = item_trait.generics.type_params().map(|param| {
+            let ident = &param.ident;
+            quote! {#ident: 'static,}
+        });
+        let where_clause = if generic {
+            let predicates = where_clause
+                .into_iter()
+                .flat_map(|where_clause| where_clause.predicates.iter());
+            quote! { where #(#predicates,)* #(#static_bounds)* }
+        } else {
+            quote! {}
+        };
+        let (address, builder_address) = if generic {
+            (
+                quote! {
+                    pub static #address_ident : ::lockjaw::GenericComponentBuilders =
+                        ::lockjaw::GenericComponentBuilders::new();
+                },
+                quote! {
+                    match #address_ident.get(::lockjaw::__private::std::any::TypeId::of::<dyn #component_name #ty_generics>()) {
+                        ::lockjaw::__private::std::option::Option::Some(address) => address,
+                        ::lockjaw::__private::std::option::Option::None => ::lockjaw::__private::std::ptr::null(),
+                    }
+                },
+            )
+        } else {
+            (
+                quote! {
+                    pub static mut #address_ident : *const () = ::lockjaw::__private::std::ptr::null();
+                },
+                quote! {#address_ident},
+            )
+        };
 
+        let mut params = Vec::new();
+        let mut args = Vec::new();
         if let Some(module_manifest_name) = builder_modules {
+            params.push(quote! {param : #module_manifest_name});
+            args.push(quote! {param});
+        }
+        // components with instance bindings can only be built with the instances.
+        let (try_build, build) = if instance_bindings.is_empty() {
+            (format_ident!("try_build"), format_ident!("build"))
+        } else {
+            params.push(quote! {instances : ::lockjaw::Instances});
+            args.push(quote! {instances});
+            (format_ident!("try_build_with"), format_ident!("build_with"))
+        };
+        let (params, args) = (quote! {#(#params),*}, quote! {#(#args),*});
+        let has_get = item_trait.items.iter().any(|item| match item {
+            TraitItem::Fn(method) => method.sig.ident == "get",
+            _ => false,
+        });
+        let get = if has_get {
+            quote! {}
+        } else {
             quote! {
-                #[doc(hidden)]
-                #[allow(non_upper_case_globals)]
-                pub static mut #address_ident : *const () = ::std::ptr::null();
-
-                impl dyn #component_name {
-
-                    #[allow(unused)]
-                    pub fn build (param : #module_manifest_name) -> Box<dyn #component_name>{
-                        extern "Rust" {
-                                fn  #components_initializer_name();
-                        }
-                        unsafe {
-                            #components_initializer_name();
-                            let builder: extern "Rust" fn(param : #module_manifest_name) -> Box<dyn #component_name> = std::mem::transmute(#address_ident);
-                            builder(param)
-                        }
-                    }
+                #[allow(unused)]
+                pub fn get<LockjawProvision>(&self) -> LockjawProvision
+                where
+                    Self: ::lockjaw::Provisioned<LockjawProvision>,
+                {
+                    <Self as ::lockjaw::Provisioned<LockjawProvision>>::provision(self)
                 }
             }
-        } else {
+        };
+        let new = if params.is_empty() {
             quote! {
-                #[doc(hidden)]
-                #[allow(non_upper_case_globals)]
-                pub static mut #address_ident : *const () = ::std::ptr::null();
+                pub fn new () -> ::lockjaw::__private::std::boxed::Box<dyn #component_name #ty_generics>{
+                    <dyn #component_name #ty_generics>::build()
+                }
+            }
+        } else {
+            quote! {}
+        };
+        quote! {
+            #[doc(hidden)]
+            #[allow(non_upper_case_globals)]
+            #address
 
-                impl dyn #component_name {
-                    pub fn build () -> Box<dyn #component_name>{
-                        extern "Rust" {
-                                fn  #components_initializer_name();
-                        }
-                        unsafe{
-                            #components_initializer_name();
-                            let builder: extern "Rust" fn() -> Box<dyn #component_name> = std::mem::transmute(#address_ident);
-                            builder()
-                        }
-                    }
-                    pub fn new () -> Box<dyn #component_name>{
-                        extern "Rust" {
+            impl #impl_generics dyn #component_name #ty_generics #where_clause {
+                #[allow(unused)]
+                pub fn #try_build (#params) -> ::lockjaw::__private::std::result::Result<::lockjaw::__private::std::boxed::Box<dyn #component_name #ty_generics>, ::lockjaw::Error>{
+                    extern "Rust" {
                             fn  #components_initializer_name();
+                    }
+                    unsafe {
+                        #components_initializer_name();
+                        let address: *const () = #builder_address;
+                        if address.is_null() {
+                            return Err(::lockjaw::Error::NotGenerated {
+                                component: #component_name_str,
+                            });
                         }
-                        unsafe{
-                            #components_initializer_name();
-                            let builder: extern "Rust" fn() -> Box<dyn #component_name> = std::mem::transmute(#address_ident);
-                            builder()
-                        }
+                        let builder: extern "Rust" fn(#params) -> ::lockjaw::__private::std::result::Result<::lockjaw::__private::std::boxed::Box<dyn #component_name #ty_generics>, ::lockjaw::Error> = ::lockjaw::__private::std::mem::transmute(address);
+                        builder(#args)
                     }
                 }
+
+                #[allow(unused)]
+                pub fn #build (#params) -> ::lockjaw::__private::std::boxed::Box<dyn #component_name #ty_generics>{
+                    <dyn #component_name #ty_generics>::#try_build(#args).unwrap_or_else(|e| ::lockjaw::__private::std::panic!("{}", e))
+                }
+
+                #new
+
+                #get
             }
+
+            #provisioned
         }
     };
 
@@ -207,6 +347,270 @@ pub fn handle_component_attribute(
     Ok(result)
 }
 
+pub fn handle_component_fragment_attribute(
+    attr: TokenStream,
+    input: TokenStream,
+) -> Result<TokenStream, TokenStream> {
+    let span = input.span();
+    let item_trait: syn::ItemTrait =
+        syn::parse2(input).map_spanned_compile_error(span, "trait expected")?;
+    if let Some(item) = item_trait.items.first() {
+        return spanned_compile_error(
+            item.span(),
+            "#[component_fragment] cannot have items, provisions belong to the components \
+            embedding it",
+        );
+    }
+    if !item_trait.generics.params.is_empty() {
+        return spanned_compile_error(
+            item_trait.generics.span(),
+            "#[component_fragment] cannot be generic",
+        );
+    }
+
+    let mut type_validator = TypeValidator::new();
+    let attributes = parsing::get_attribute_field_values(attr.clone())?;
+    for (key, value) in &attributes {
+        if !COMPONENT_FRAGMENT_METADATA_KEYS.contains(key) {
+            return spanned_compile_error(attr.span(), &format!("unknown key: {}", key));
+        }
+        for (path, span) in value.get_paths()? {
+            if key == "modules" {
+                type_validator.add_path(&path, span);
+            } else {
+                type_validator.add_dyn_path(&path, span);
+            }
+        }
+    }
+
+    let validate_type = type_validator.validate(item_trait.ident.to_string());
+    Ok(quote! {
+        #item_trait
+        #validate_type
+    })
+}
+
+/// Generic components are generated by the epilogue once for each instantiation found in the
+/// source, such as `<dyn Repo<User>>::new()`.
+fn validate_generics(
+    item_trait: &ItemTrait,
+    component_type: &ComponentType,
+) -> Result<(), TokenStream> {
+    if item_trait.generics.params.is_empty() {
+        return Ok(());
+    }
+    if component_type == &ComponentType::Subcomponent {
+        // subcomponents always carry the lifetime of their parent.
+        if let Some(param) = item_trait
+            .generics
+            .params
+            .iter()
+            .find(|param| !matches!(param, syn::GenericParam::Lifetime(_)))
+        {
+            return spanned_compile_error(param.span(), "subcomponents cannot be generic");
+        }
+        return Ok(());
+    }
+    for param in &item_trait.generics.params {
+        if !matches!(param, syn::GenericParam::Type(_)) {
+            return spanned_compile_error(
+                param.span(),
+                "generic components only support type parameters",
+            );
+        }
+    }
+    if let Some(impl_trait) = item_trait.items.iter().find_map(impl_trait_return) {
+        return spanned_compile_error(
+            impl_trait.span(),
+            "generic components cannot have impl Trait provisions",
+        );
+    }
+    Ok(())
+}
+
+/// Associated functions lockjaw generates on `dyn Component` for root components.
+const RESERVED_ROOT_COMPONENT_NAMES: &[&str] =
+    &["new", "build", "try_build", "build_with", "try_build_with"];
+
+/// Rejects provisions named like the methods lockjaw generates for the component, which would
+/// otherwise fail in the generated code with errors pointing nowhere near the provision.
+fn validate_provision_names(
+    item_trait: &ItemTrait,
+    component_type: &ComponentType,
+) -> Result<(), TokenStream> {
+    for item in &item_trait.items {
+        let TraitItem::Fn(method) = item else {
+            continue;
+        };
+        let name = method.sig.ident.to_string();
+        if name.starts_with("lockjaw_") {
+            return spanned_compile_error(
+                method.sig.ident.span(),
+                &format!(
+                    "`{}` conflicts with methods generated by lockjaw, names starting with \
+                    `lockjaw_` are reserved. Rename the provision.",
+                    name
+                ),
+            );
+        }
+        if *component_type == ComponentType::Component
+            && RESERVED_ROOT_COMPONENT_NAMES.contains(&name.as_str())
+        {
+            return spanned_compile_error(
+                method.sig.ident.span(),
+                &format!(
+                    "`{}` conflicts with `<dyn {}>::{}()` generated by lockjaw. Rename the \
+                    provision, e.g. to `{}_{}`.",
+                    name,
+                    item_trait.ident,
+                    name,
+                    provision_name_suggestion(&method.sig.output),
+                    name
+                ),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Snake cased last segment of the provision's return type, to suggest a non-conflicting name.
+fn provision_name_suggestion(output: &ReturnType) -> String {
+    let ReturnType::Type(_, ty) = output else {
+        return "provide".to_owned();
+    };
+    let mut ty = ty.deref();
+    while let syn::Type::Reference(reference) = ty {
+        ty = reference.elem.deref();
+    }
+    let syn::Type::Path(type_path) = ty else {
+        return "provide".to_owned();
+    };
+    let Some(segment) = type_path.path.segments.last() else {
+        return "provide".to_owned();
+    };
+    let mut result = String::new();
+    for (i, c) in segment.ident.to_string().chars().enumerate() {
+        if c.is_uppercase() && i != 0 {
+            result.push('_');
+        }
+        result.extend(c.to_lowercase());
+    }
+    result
+}
+
+/// Implements `lockjaw::Provisioned<T>` on `dyn Component` for each provision that can be looked up
+/// by its type: no parameters, not qualified, and returning an owned type without lifetimes.
+fn provisioned_impls(item_trait: &ItemTrait) -> Result<TokenStream, TokenStream> {
+    let Some((manifest, component)) = try_find_manifest_component(item_trait)? else {
+        // not seen by the build script, the component will not be generated either.
+        return Ok(quote! {});
+    };
+    let component_name = &item_trait.ident;
+    let (impl_generics, ty_generics, where_clause) = item_trait.generics.split_for_impl();
+    let type_params = parsing::generic_type_params(&item_trait.generics);
+    let mut types = HashSet::<String>::new();
+    let mut result = quote! {};
+    for item in &item_trait.items {
+        let TraitItem::Fn(ref method) = item else {
+            continue;
+        };
+        let ReturnType::Type(_, ref ty) = method.sig.output else {
+            continue;
+        };
+        if method.sig.inputs.len() != 1
+            || !method.sig.generics.params.is_empty()
+            || matches!(ty.deref(), syn::Type::ImplTrait(_))
+            || has_reference(ty.to_token_stream())
+            // the lifetime may be elided, which is not allowed in the impl header, and aliases
+            // would conflict with the impl of the aliased type.
+            || component.provisions.iter().any(|provision| {
+                provision.name == method.sig.ident.to_string()
+                    && (has_lifetime(&manifest, &provision.type_data)
+                        || manifest.type_aliases.contains(&provision.type_data))
+            })
+            // would overlap with the other impls for some instantiations.
+            || parsing::mentions_idents(ty.to_token_stream(), &type_params)
+            || method
+                .attrs
+                .iter()
+                .any(|attr| parsing::get_attribute(attr) == "qualified")
+        {
+            continue;
+        }
+        if !types.insert(ty.to_token_stream().to_string()) {
+            continue;
+        }
+        let method_name = &method.sig.ident;
+        result = quote! {
+            #result
+            impl #impl_generics ::lockjaw::Provisioned<#ty> for dyn #component_name #ty_generics #where_clause {
+                fn provision(&self) -> #ty {
+                    self.#method_name()
+                }
+            }
+        };
+    }
+    Ok(result)
+}
+
+/// Lockjaw types carrying the lifetime of the component.
+const LIFETIMED_LOCKJAW_TYPES: &[&str] = &[
+    "lockjaw::Cl",
+    "lockjaw::Lazy",
+    "lockjaw::Provider",
+    "lockjaw::Contributions",
+    "lockjaw::Elements",
+    "lockjaw::EventBus",
+];
+
+/// Whether the type or any of its type arguments has lifetime parameters.
+fn has_lifetime(manifest: &Manifest, type_: &TypeData) -> bool {
+    (type_.root == TypeRoot::GLOBAL && LIFETIMED_LOCKJAW_TYPES.contains(&type_.path.as_str()))
+        || manifest.lifetimed_types.contains(type_)
+        || type_.args.iter().any(|arg| has_lifetime(manifest, arg))
+}
+
+/// Whether the tokens contain a reference or a lifetime.
+fn has_reference(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Punct(punct) => punct.as_char() == '&' || punct.as_char() == '\'',
+        proc_macro2::TokenTree::Group(group) => has_reference(group.stream()),
+        _ => false,
+    })
+}
+
+/// Name of the lifetime generated code uses for the lifetime of a subcomponent, chosen so it does not
+/// collide with lifetimes in user signatures.
+pub fn component_lifetime() -> syn::Lifetime {
+    syn::Lifetime::new("'lockjaw_component", proc_macro2::Span::call_site())
+}
+
+/// Name of the unmangled function initializing the components of the root crate, which crates
+/// declaring a component link against with their [`symbol_suffix()`].
+pub fn root_components_initializer(suffix: &str) -> Ident {
+    format_ident!("lockjaw_init_root_components_{}", suffix)
+}
+
+/// Names the root crate exports its components initializer as, one for each crate declaring the
+/// components.
+pub fn root_components_initializers(manifest: &Manifest) -> Vec<Ident> {
+    let mut suffixes = BTreeSet::new();
+    suffixes.insert(symbol_suffix(&current_package(), &current_version()));
+    for component in &manifest.components {
+        if component.component_type == ComponentType::Subcomponent {
+            continue;
+        }
+        let crate_name = &component.type_data.field_crate;
+        if let Some(version) = manifest.crate_versions.get(crate_name) {
+            suffixes.insert(symbol_suffix(crate_name, version));
+        }
+    }
+    suffixes
+        .iter()
+        .map(|suffix| root_components_initializer(suffix))
+        .collect()
+}
+
 pub fn builder_name(component: &TypeData) -> Ident {
     format_ident!(
         "lockjaw_component_builder_{}",
@@ -220,32 +624,265 @@ pub fn builder_name(component: &TypeData) -> Ident {
 pub fn parse_provisions(
     item_trait: &mut ItemTrait,
     type_validator: &mut TypeValidator,
+    allow_optional: bool,
 ) -> Result<(), TokenStream> {
     for item in &mut item_trait.items {
         if let syn::TraitItem::Fn(ref mut method) = item {
             let mut new_attrs: Vec<Attribute> = Vec::new();
+            let mut optional = false;
+            let mut qualified = false;
             for attr in &method.attrs {
                 match parsing::get_attribute(attr).as_str() {
                     "qualified" => {
+                        qualified = true;
                         let tokens = attr.meta.require_list().unwrap().tokens.to_token_stream();
                         let path = parsing::get_path(&tokens)?;
-                        type_validator.add_path(&path, path.span());
+                        type_validator.add_qualifier_path(&path, path.span());
+                    }
+                    "optional" => {
+                        if !allow_optional {
+                            return spanned_compile_error(
+                                attr.span(),
+                                "#[optional] is only allowed in #[entry_point]",
+                            );
+                        }
+                        optional = true;
                     }
                     _ => new_attrs.push(attr.clone()),
                 }
             }
             method.attrs = new_attrs;
-            let syn::ReturnType::Type(_, _) = method.sig.output else {
+            let syn::ReturnType::Type(_, ref ty) = method.sig.output else {
                 return spanned_compile_error(
                     method.sig.span(),
                     "return type expected for component provisions",
                 );
             };
+            // whether an #[optional] provision returns `Option<T>` is checked by the build script,
+            // which can resolve the return type.
+            validate_provision_parameters(method, ty, allow_optional, optional || qualified)?;
         }
     }
     Ok(())
 }
 
+/// Provisions with parameters are fulfilled by the `#[factory]` of the returned `#[injectable]`,
+/// which takes the parameters as its `#[runtime]` arguments.
+fn validate_provision_parameters(
+    method: &syn::TraitItemFn,
+    ty: &syn::Type,
+    entry_point: bool,
+    annotated: bool,
+) -> Result<(), TokenStream> {
+    let mut has_parameters = false;
+    for input in &method.sig.inputs {
+        let syn::FnArg::Typed(ref pat_type) = input else {
+            continue;
+        };
+        if !matches!(pat_type.pat.deref(), syn::Pat::Ident(_)) {
+            return spanned_compile_error(pat_type.pat.span(), "identifier expected");
+        }
+        has_parameters = true;
+    }
+    if !has_parameters {
+        return Ok(());
+    }
+    if entry_point {
+        return spanned_compile_error(
+            method.sig.inputs.span(),
+            "#[entry_point] provisions cannot have parameters",
+        );
+    }
+    if annotated {
+        return spanned_compile_error(
+            method.sig.inputs.span(),
+            "provisions with parameters cannot be #[qualified] or #[optional]",
+        );
+    }
+    if !matches!(ty, syn::Type::Path(_)) {
+        return spanned_compile_error(
+            ty.span(),
+            "provisions with parameters must return an #[injectable] with a #[factory]",
+        );
+    }
+    Ok(())
+}
+
+/// Returns the `impl Trait` return type of the provision, if it has one.
+pub fn impl_trait_return(item: &TraitItem) -> Option<&syn::TypeImplTrait> {
+    if let TraitItem::Fn(ref method) = item {
+        if let ReturnType::Type(_, ref ty) = method.sig.output {
+            if let syn::Type::ImplTrait(ref impl_trait) = ty.deref() {
+                return Some(impl_trait);
+            }
+        }
+    }
+    None
+}
+
+/// The `Item` of an `impl Iterator<Item = T>` return type.
+fn iterator_item(impl_trait: &syn::TypeImplTrait) -> Option<&syn::Type> {
+    impl_trait.bounds.iter().find_map(|bound| {
+        let syn::TypeParamBound::Trait(trait_) = bound else {
+            return None;
+        };
+        let segment = trait_.path.segments.last()?;
+        if segment.ident != "Iterator" {
+            return None;
+        }
+        let syn::PathArguments::AngleBracketed(ref args) = segment.arguments else {
+            return None;
+        };
+        args.args.iter().find_map(|arg| match arg {
+            syn::GenericArgument::AssocType(assoc) if assoc.ident == "Item" => Some(&assoc.ty),
+            _ => None,
+        })
+    })
+}
+
+/// Rewrites `-> impl Iterator<Item = T> + '_` provisions to `lockjaw::Elements<'_, T>`, which
+/// lazily iterates the `Vec<T>` multibinding.
+fn resolve_iterator_provisions(item_trait: &mut ItemTrait) {
+    for item in &mut item_trait.items {
+        let Some(item_type) = impl_trait_return(item).and_then(iterator_item).cloned() else {
+            continue;
+        };
+        let TraitItem::Fn(ref mut method) = item else {
+            continue;
+        };
+        method.sig.output = syn::parse_quote! { -> ::lockjaw::Elements<'_, #item_type> };
+    }
+}
+
+/// The `impl Trait` bound by `#[binds(static_dispatch)]` for the `Cl<dyn Trait>` type of an
+/// `impl Trait` provision.
+pub fn impl_trait_type(cl_type: &TypeData) -> TypeData {
+    let mut result = cl_type.args[0].clone();
+    result.qualifier = cl_type.qualifier.clone();
+    result
+}
+
+/// Rewrites `-> impl Trait` provisions to return the implementation bound by
+/// `#[binds(static_dispatch)]`, or to `Cl<dyn Trait>` by `#[binds]`, since return position
+/// `impl Trait` would make the component trait not object safe.
+///
+/// The binding is looked up in the manifest gathered by the build script, among the modules
+/// installed in the component. The epilogue generates the provision with the type the graph
+/// resolves, so rustc reports any mismatch.
+fn resolve_impl_trait_provisions(item_trait: &mut ItemTrait) -> Result<(), TokenStream> {
+    if !item_trait
+        .items
+        .iter()
+        .any(|item| impl_trait_return(item).is_some())
+    {
+        return Ok(());
+    }
+    let (manifest, component) = find_manifest_component(item_trait)?;
+    for item in &mut item_trait.items {
+        let Some(impl_trait) = impl_trait_return(item) else {
+            continue;
+        };
+        let span = impl_trait.span();
+        let TraitItem::Fn(ref mut method) = item else {
+            continue;
+        };
+        let name = method.sig.ident.to_string();
+        let provision = component
+            .provisions
+            .iter()
+            .find(|provision| provision.name == name && provision.impl_trait)
+            .map_spanned_compile_error(span, "provision not found in the lockjaw manifest")?;
+        // #[binds] records the trait as the binding type for both Cl<dyn Trait> and impl Trait.
+        let trait_type = impl_trait_type(&provision.type_data);
+        let mut implementations = HashMap::<String, TypeData>::new();
+        for module in &manifest.modules {
+            if !is_installed(module, &component) {
+                continue;
+            }
+            for binding in &module.bindings {
+                if binding.binding_type == BindingType::Binds
+                    && binding.multibinding_type == MultibindingType::None
+                    && binding.type_data == trait_type
+                {
+                    let implementation = &binding.dependencies[0].type_data;
+                    implementations
+                        .insert(implementation.identifier_string(), implementation.clone());
+                }
+            }
+        }
+        if implementations.len() != 1 {
+            let mut found: Vec<String> = implementations
+                .values()
+                .map(|implementation| implementation.readable())
+                .collect();
+            found.sort();
+            return spanned_compile_error(
+                span,
+                &format!(
+                    "impl Trait provisions require exactly one #[binds] of {} in the modules installed in the component, found [{}]",
+                    provision.type_data.readable(),
+                    found.join(", ")
+                ),
+            );
+        }
+        let implementation = implementations.into_values().next().unwrap();
+        let return_type = component_visibles::visible_type(&manifest, &implementation).syn_type();
+        method.sig.output = syn::parse_quote! { -> #return_type };
+    }
+    Ok(())
+}
+
+/// Finds the manifest entry of the component being declared, in the prod manifest or the test
+/// manifest if the component is only declared for tests.
+fn find_manifest_component(item_trait: &ItemTrait) -> Result<(Manifest, Component), TokenStream> {
+    if let Some(result) = try_find_manifest_component(item_trait)? {
+        return Ok(result);
+    }
+    spanned_compile_error(
+        item_trait.ident.span(),
+        &format!(
+            "component {} not found in the lockjaw manifest",
+            item_trait.ident
+        ),
+    )
+}
+
+fn try_find_manifest_component(
+    item_trait: &ItemTrait,
+) -> Result<Option<(Manifest, Component)>, TokenStream> {
+    let name = item_trait.ident.to_string();
+    let provision_names: HashSet<String> = item_trait
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            TraitItem::Fn(method) => Some(method.sig.ident.to_string()),
+            _ => None,
+        })
+        .collect();
+    for for_test in [false, true] {
+        let manifest = crate::crate_manifest(for_test)?;
+        let component = manifest.components.iter().find(|component| {
+            component.type_data.field_crate.replace('-', "_") == current_package()
+                && component.type_data.path.rsplit("::").next() == Some(name.as_str())
+                && component
+                    .provisions
+                    .iter()
+                    .all(|provision| provision_names.contains(&provision.name))
+        });
+        if let Some(component) = component {
+            let component = component.clone();
+            return Ok(Some((manifest, component)));
+        }
+    }
+    Ok(None)
+}
+
+fn is_installed(module: &Module, component: &Component) -> bool {
+    component.modules.contains(&module.type_data)
+        || module.install_in.contains(&component.type_data)
+        || (component.definition_only && module.install_in.contains(&graph::singleton_type()))
+}
+
 pub fn handle_builder_modules_attribute(
     _attr: TokenStream,
     input: TokenStream,
@@ -261,15 +898,120 @@ pub fn handle_builder_modules_attribute(
             .as_ref()
             .map_spanned_compile_error(span, "#[builder_modules] cannot be tuples")?;
     }
+    validate_builder_modules_visibility(&item_struct)?;
 
     Ok(quote_spanned! {span=>
         #item_struct
     })
 }
 
+/// The epilogue names the module types from the crate root, so they cannot be private to a nested
+/// mod. Reports it here instead of letting the generated code fail with E0603.
+fn validate_builder_modules_visibility(item_struct: &syn::ItemStruct) -> Result<(), TokenStream> {
+    let name = item_struct.ident.to_string();
+    let source_file = item_struct.ident.span().local_file();
+    for for_test in [false, true] {
+        let manifest = crate::crate_manifest(for_test)?;
+        // The macro does not know the mod it is expanded in, so the resolved type is matched by
+        // crate, declaring file and name.
+        let candidates: Vec<&BuilderModules> = manifest
+            .builder_modules
+            .iter()
+            .filter(|builder_modules| {
+                let Some(ref type_data) = builder_modules.type_data else {
+                    return false;
+                };
+                type_data.field_crate.replace('-', "_") == current_package()
+                    && type_data.path.rsplit("::").next() == Some(name.as_str())
+                    && is_same_file(&builder_modules.source_file, source_file.as_deref())
+            })
+            .collect();
+        let [builder_modules] = candidates.as_slice() else {
+            // not found, or declared in several inline mods of the same file.
+            continue;
+        };
+        for field in &item_struct.fields {
+            let field_name = field.ident.as_ref().unwrap().to_string();
+            let Some(module) = builder_modules
+                .builder_modules
+                .iter()
+                .find(|module| module.name == field_name)
+            else {
+                continue;
+            };
+            if manifest.private_types.contains(&module.type_data) {
+                return spanned_compile_error(
+                    field.ty.span(),
+                    &format!(
+                        "{} is not visible to the crate root, add #[component_visible] to it",
+                        module.type_data.readable()
+                    ),
+                );
+            }
+        }
+        return Ok(());
+    }
+    Ok(())
+}
+
+fn is_same_file(manifest_file: &str, local_file: Option<&std::path::Path>) -> bool {
+    let Some(local_file) = local_file else {
+        return true;
+    };
+    match (
+        std::fs::canonicalize(manifest_file),
+        std::fs::canonicalize(local_file),
+    ) {
+        (Ok(manifest_file), Ok(local_file)) => manifest_file == local_file,
+        _ => true,
+    }
+}
+
+/// Replaces each generic component with a copy for every instantiation found in the source, with
+/// the type parameters substituted in the provisions.
+///
+/// The copies keep `type_params`, so the epilogue registers their builders by `TypeId`.
+pub fn instantiate_generic_components(manifest: &mut Manifest) {
+    let (generic_components, mut components): (Vec<Component>, Vec<Component>) = manifest
+        .components
+        .drain(..)
+        .partition(|component| !component.type_params.is_empty());
+    for component in generic_components {
+        let mut instantiations: Vec<&TypeData> = manifest
+            .instantiated_components
+            .iter()
+            .filter(|instantiation| {
+                let mut generic_type = (*instantiation).clone();
+                generic_type.args.clear();
+                instantiation.args.len() == component.type_params.len()
+                    && generic_type == component.type_data
+            })
+            .collect();
+        instantiations.sort_by_key(|instantiation| instantiation.identifier_string());
+        for instantiation in instantiations {
+            let mut instance = component.clone();
+            instance.type_data.args = instantiation.args.clone();
+            for provision in &mut instance.provisions {
+                substitute_dependency(provision, &component.type_params, &instantiation.args);
+            }
+            components.push(instance);
+        }
+    }
+    manifest.components = components;
+}
+
+fn substitute_dependency(dependency: &mut Dependency, params: &[TypeData], args: &[TypeData]) {
+    dependency.type_data.substitute(params, args);
+    for parameter in &mut dependency.parameters {
+        substitute_dependency(parameter, params, args);
+    }
+}
+
 pub fn generate_components(
     manifest: &Manifest,
     root: bool,
+    prune: bool,
+    cache: Option<&CodegenCache>,
 ) -> Result<(TokenStream, TokenStream, Vec<String>), TokenStream> {
     let mut result = quote! {};
     let mut initializer = quote! {};
@@ -283,7 +1025,39 @@ pub fn generate_components(
         if component.component_type != ComponentType::Component {
             continue;
         }
-        let (tokens, message) = graph::generate_component(&component, manifest)?;
+        if prune
+            && !manifest
+                .instantiated_components
+                .contains(&component.type_data)
+        {
+            messages.push(format!(
+                "pruned {}: never instantiated",
+                component.type_data.readable()
+            ));
+            continue;
+        }
+        let cache_key = cache.map(|cache| (cache, cache.key(manifest, component)));
+        let cached = cache_key.and_then(|(cache, key)| cache.get(component, key));
+        // keep generating the other components so IDEs can still resolve them.
+        let (tokens, message) = if let Some(tokens) = cached {
+            (
+                tokens,
+                format!("{}: cached", component.type_data.readable()),
+            )
+        } else {
+            match graph::generate_component(&component, manifest) {
+                Ok((tokens, message)) => {
+                    if let Some((cache, key)) = cache_key {
+                        cache.put(component, key, &tokens);
+                    }
+                    (tokens, message)
+                }
+                Err(errors) => (
+                    graph::generate_stub_component(&component, manifest, errors),
+                    format!("{}: graph has errors", component.type_data.readable()),
+                ),
+            }
+        };
         result = quote! {
             #result
             #tokens
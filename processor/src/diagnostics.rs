@@ -0,0 +1,94 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Machine readable diagnostics written to the file at `LOCKJAW_DIAG_JSON`, in addition to the
+//! `compile_error!`s, so CI bots and editor plugins can present them.
+//!
+//! Each diagnostic is a JSON object on its own line, since the file is appended by every macro
+//! invocation.
+
+use lockjaw_common::environment::current_crate;
+use serde::Serialize;
+use std::io::Write;
+
+/// Env var with the path of the file to append diagnostics to.
+pub const LOCKJAW_DIAG_JSON_ENV: &str = "LOCKJAW_DIAG_JSON";
+
+#[derive(Serialize, Debug, Default)]
+pub struct Diagnostic {
+    /// `"error"`, or `"missing_binding"` for a type with no binding in the component.
+    pub kind: String,
+    pub message: String,
+    #[serde(rename = "crate")]
+    pub crate_: String,
+    /// The component being generated, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component: Option<String>,
+    /// The type without a binding.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_: Option<String>,
+    /// Chain of bindings requesting the type, starting from the one directly depending on it.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub requested_by: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn error(message: &str) -> Self {
+        Diagnostic {
+            kind: "error".to_owned(),
+            message: message.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    pub fn missing_binding(
+        message: &str,
+        component: String,
+        type_: String,
+        requested_by: Vec<String>,
+    ) -> Self {
+        Diagnostic {
+            kind: "missing_binding".to_owned(),
+            message: message.to_owned(),
+            component: Some(component),
+            type_: Some(type_),
+            requested_by,
+            ..Default::default()
+        }
+    }
+}
+
+/// Appends the diagnostic to the `LOCKJAW_DIAG_JSON` file, if set. Failures are ignored since the
+/// `compile_error!` is still reported.
+pub fn report(mut diagnostic: Diagnostic) {
+    let Ok(path) = std::env::var(LOCKJAW_DIAG_JSON_ENV) else {
+        return;
+    };
+    if path.is_empty() {
+        return;
+    }
+    diagnostic.crate_ = current_crate();
+    let Ok(line) = serde_json::to_string(&diagnostic) else {
+        return;
+    };
+    if let Ok(mut file) = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+    {
+        let _ = writeln!(file, "{}", line);
+    }
+}
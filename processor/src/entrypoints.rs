@@ -47,7 +47,17 @@ pub fn handle_entry_point_attribute(
 
     let mut type_validator = TypeValidator::new();
 
-    components::parse_provisions(&mut item_trait, &mut type_validator)?;
+    components::parse_provisions(&mut item_trait, &mut type_validator, true)?;
+    if let Some(impl_trait) = item_trait
+        .items
+        .iter()
+        .find_map(components::impl_trait_return)
+    {
+        return spanned_compile_error(
+            impl_trait.span(),
+            "impl Trait provisions are only supported in #[component] and #[subcomponent]",
+        );
+    }
 
     let attributes = parsing::get_attribute_field_values(attr.clone())?;
 
@@ -76,6 +86,7 @@ pub fn handle_entry_point_attribute(
     let item_ident = item_trait.ident.clone();
     let validate_type = type_validator.validate(item_trait.ident.to_string());
     let address_ident = format_ident!("LOCKJAW_ENTRY_POINT_GETTER_ADDR_{}", original_ident);
+    let component_name = quote! {#component_path}.to_string().replace(' ', "");
     let result = quote! {
         #[doc(hidden)]
         #[allow(non_camel_case_types)]
@@ -87,12 +98,40 @@ pub fn handle_entry_point_attribute(
 
         #[doc(hidden)]
         #[allow(non_upper_case_globals)]
-        pub static mut #address_ident : *const () = ::std::ptr::null();
+        pub static mut #address_ident : *const () = ::lockjaw::__private::std::ptr::null();
 
         impl dyn #item_ident {
             fn get<'a>(component: &'a dyn #component_path) -> &'a dyn #item_ident {
+                if unsafe { #address_ident }.is_null() {
+                    ::lockjaw::__private::std::panic!(
+                        "{}",
+                        ::lockjaw::Error::NotGenerated {
+                            component: #component_name,
+                        }
+                    );
+                }
+                unsafe { Self::get_unchecked(component) }
+            }
+
+            /// Same as `get()`, but the check that the component is generated is only done in
+            /// debug builds.
+            ///
+            /// # Safety
+            ///
+            /// A component the entry point is installed in must have been built.
+            #[allow(unused)]
+            #[inline]
+            unsafe fn get_unchecked<'a>(component: &'a dyn #component_path) -> &'a dyn #item_ident {
                 unsafe {
-                    let getter: extern "Rust" fn(&'a dyn #component_path) -> &'static dyn #item_ident = std::mem::transmute(#address_ident);
+                    let address = #address_ident;
+                    debug_assert!(
+                        !address.is_null(),
+                        "{}",
+                        ::lockjaw::Error::NotGenerated {
+                            component: #component_name,
+                        }
+                    );
+                    let getter: extern "Rust" fn(&'a dyn #component_path) -> &'static dyn #item_ident = ::lockjaw::__private::std::mem::transmute(address);
                     getter(component)
                 }
             }
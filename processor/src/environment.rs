@@ -15,10 +15,29 @@ limitations under the License.
 */
 use crate::error::CompileError;
 use proc_macro2::TokenStream;
+use std::path::PathBuf;
 
 /// Returns the output directory for the current crate.
-pub fn lockjaw_output_dir() -> Result<String, TokenStream> {
-    let out_dir = std::env::var("OUT_DIR")
-        .map_compile_error("output dir not found. Call lockjaw::build_script in build.rs")?;
-    return Ok(format!("{}/lockjaw/", out_dir));
+pub fn lockjaw_output_dir() -> Result<PathBuf, TokenStream> {
+    lockjaw_common::environment::lockjaw_output_dir()
+        .map_compile_error("output dir not found. Call lockjaw::build_script in build.rs")
+}
+
+/// Environment variable overriding the rustfmt binary used to format `debug_output`.
+pub const RUSTFMT_ENV: &str = "LOCKJAW_RUSTFMT";
+
+/// Returns the rustfmt binary used to format `debug_output`.
+pub fn rustfmt() -> String {
+    std::env::var(RUSTFMT_ENV).unwrap_or_else(|_| "rustfmt".to_owned())
+}
+
+/// Environment variable rustdoc sets when compiling a documentation test.
+const RUSTDOC_TEST_PATH_ENV: &str = "UNSTABLE_RUSTDOC_TEST_PATH";
+
+/// Returns whether the current crate is a doctest compiled by `rustdoc --test`.
+///
+/// Cargo passes the environment of the documented crate to rustdoc, so `CARGO_CRATE_NAME` and
+/// `CARGO_PKG_NAME` cannot tell a doctest apart from the library itself.
+pub fn is_doctest() -> bool {
+    std::env::var_os(RUSTDOC_TEST_PATH_ENV).is_some()
 }
@@ -14,6 +14,8 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+use crate::diagnostics;
+use crate::diagnostics::Diagnostic;
 use backtrace::Backtrace;
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
@@ -64,6 +66,7 @@ where
 
 #[must_use]
 pub fn compile_error<T>(message: &str) -> Result<T, TokenStream> {
+    diagnostics::report(Diagnostic::error(message));
     Err(quote! {
         compile_error!(#message);
     })
@@ -71,6 +74,7 @@ pub fn compile_error<T>(message: &str) -> Result<T, TokenStream> {
 
 #[must_use]
 pub fn spanned_compile_error<T>(span: Span, message: &str) -> Result<T, TokenStream> {
+    diagnostics::report(Diagnostic::error(message));
     Err(quote_spanned! {span=>
         compile_error!(#message);
     })
@@ -84,13 +88,19 @@ pub trait CompileError<T> {
 
 impl<T> CompileError<T> for Option<T> {
     fn map_compile_error(self, message: &str) -> Result<T, TokenStream> {
-        self.ok_or(quote! {
-            compile_error!(#message);
+        self.ok_or_else(|| {
+            diagnostics::report(Diagnostic::error(message));
+            quote! {
+                compile_error!(#message);
+            }
         })
     }
     fn map_spanned_compile_error(self, span: Span, message: &str) -> Result<T, TokenStream> {
-        self.ok_or(quote_spanned! {span=>
-            compile_error!(#message);
+        self.ok_or_else(|| {
+            diagnostics::report(Diagnostic::error(message));
+            quote_spanned! {span=>
+                compile_error!(#message);
+            }
         })
     }
 }
@@ -98,6 +108,7 @@ impl<T> CompileError<T> for Option<T> {
 impl<T, E> CompileError<T> for Result<T, E> {
     fn map_compile_error(self, message: &str) -> Result<T, TokenStream> {
         self.map_err(|_| {
+            diagnostics::report(Diagnostic::error(message));
             quote! {
                 compile_error!(#message);
             }
@@ -106,6 +117,7 @@ impl<T, E> CompileError<T> for Result<T, E> {
 
     fn map_spanned_compile_error(self, span: Span, message: &str) -> Result<T, TokenStream> {
         self.map_err(|_| {
+            diagnostics::report(Diagnostic::error(message));
             quote_spanned! {span=>
                 compile_error!(#message);
             }
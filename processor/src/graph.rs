@@ -14,7 +14,8 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 use std::borrow::Borrow;
-use std::collections::{HashMap, HashSet};
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::{Debug, Formatter};
 use std::ops::Deref;
 
@@ -22,27 +23,38 @@ use proc_macro2::{Ident, TokenStream};
 use quote::format_ident;
 use quote::quote;
 
-use crate::error::compile_error;
+use crate::diagnostics;
+use crate::diagnostics::Diagnostic;
+use crate::error::{compile_error, CompileError};
 use crate::manifest::ProcessorComponent;
+use crate::nodes::arc_binds::ArcBindsNode;
 use crate::nodes::binds::BindsNode;
 use crate::nodes::binds_option_of::BindsOptionOfNode;
+use crate::nodes::default_zst::DefaultZstNode;
 use crate::nodes::entry_point::EntryPointNode;
+use crate::nodes::fallible_scoped::FallibleScopedNode;
 use crate::nodes::injectable::InjectableNode;
+use crate::nodes::instance::InstanceNode;
 use crate::nodes::map::MapNode;
 use crate::nodes::node::Node;
 use crate::nodes::parent::ParentNode;
+use crate::nodes::post_build::PostBuildNode;
 use crate::nodes::provides::ProvidesNode;
 use crate::nodes::provision::ProvisionNode;
+use crate::nodes::scoped;
 use crate::nodes::scoped::ScopedNode;
-use crate::nodes::subcomponent::SubcomponentNode;
+use crate::nodes::subcomponent::{SubcomponentNode, SubcomponentSelfBuilderNode};
 use crate::nodes::vec::VecNode;
 use crate::type_data::ProcessorTypeData;
 use crate::{component_visibles, components};
 use lockjaw_common::manifest::{
-    BindingType, BuilderModules, Component, ComponentType, Manifest, MultibindingType, TypeRoot,
+    Binding, BindingType, BuilderModules, Component, ComponentFragment, ComponentType, Dependency,
+    Injectable, Manifest, Module, MultibindingType, TypeRoot,
 };
+use lockjaw_common::timings;
 use lockjaw_common::type_data::TypeData;
 use std::iter::FromIterator;
+use std::time::Instant;
 
 /// Dependency graph and other related data
 #[derive(Debug)]
@@ -62,6 +74,21 @@ pub struct ComponentSections {
     pub methods: TokenStream,
     pub trait_methods: TokenStream,
     pub items: TokenStream,
+    /// Methods of the trait a subcomponent accesses its parent through.
+    pub parent_methods: TokenStream,
+    /// Implementation of `parent_methods` for the parent component.
+    pub parent_method_impls: TokenStream,
+    /// Statements inserting the initialization status of scoped bindings into `scoped`, for the
+    /// generated `Debug` impl.
+    pub debug_fields: TokenStream,
+    /// `(name, count)` entries returned by `lockjaw_stats()` with the `profiling` feature.
+    pub stats: TokenStream,
+    /// Statements run by the builder after the component is constructed, with `component` bound to
+    /// the boxed implementation.
+    pub post_build_statements: TokenStream,
+    /// Deprecated items surfacing warnings. Subcomponents pass them up to the root component, which
+    /// emits them at the crate root so the warnings do not name generated items.
+    pub warnings: TokenStream,
 }
 
 impl Debug for ComponentSections {
@@ -75,6 +102,17 @@ impl Debug for ComponentSections {
                 self.trait_methods.to_string()
             ))
             .field(&format!("items: {}", self.items.to_string()))
+            .field(&format!(
+                "parent_methods: {}",
+                self.parent_methods.to_string()
+            ))
+            .field(&format!("debug_fields: {}", self.debug_fields.to_string()))
+            .field(&format!("stats: {}", self.stats.to_string()))
+            .field(&format!(
+                "post_build_statements: {}",
+                self.post_build_statements.to_string()
+            ))
+            .field(&format!("warnings: {}", self.warnings.to_string()))
             .finish()
     }
 }
@@ -88,6 +126,12 @@ impl ComponentSections {
             methods: quote! {},
             trait_methods: quote! {},
             items: quote! {},
+            parent_methods: quote! {},
+            parent_method_impls: quote! {},
+            debug_fields: quote! {},
+            stats: quote! {},
+            post_build_statements: quote! {},
+            warnings: quote! {},
         }
     }
 
@@ -98,6 +142,12 @@ impl ComponentSections {
         let methods = &self.methods;
         let trait_methods = &self.trait_methods;
         let items = &self.items;
+        let parent_methods = &self.parent_methods;
+        let parent_method_impls = &self.parent_method_impls;
+        let debug_fields = &self.debug_fields;
+        let stats = &self.stats;
+        let post_build_statements = &self.post_build_statements;
+        let warnings = &self.warnings;
 
         let other_fields = &other.fields;
         let other_ctor_params = &other.ctor_params;
@@ -105,6 +155,12 @@ impl ComponentSections {
         let other_methods = &other.methods;
         let other_trait_methods = &other.trait_methods;
         let other_items = &other.items;
+        let other_parent_methods = &other.parent_methods;
+        let other_parent_method_impls = &other.parent_method_impls;
+        let other_debug_fields = &other.debug_fields;
+        let other_stats = &other.stats;
+        let other_post_build_statements = &other.post_build_statements;
+        let other_warnings = &other.warnings;
 
         self.fields = quote! {#fields #other_fields};
         self.ctor_params = quote! {#ctor_params #other_ctor_params};
@@ -112,6 +168,12 @@ impl ComponentSections {
         self.methods = quote! {#methods #other_methods};
         self.trait_methods = quote! {#trait_methods #other_trait_methods};
         self.items = quote! {#items #other_items};
+        self.parent_methods = quote! {#parent_methods #other_parent_methods};
+        self.parent_method_impls = quote! {#parent_method_impls #other_parent_method_impls};
+        self.debug_fields = quote! {#debug_fields #other_debug_fields};
+        self.stats = quote! {#stats #other_stats};
+        self.post_build_statements = quote! {#post_build_statements #other_post_build_statements};
+        self.warnings = quote! {#warnings #other_warnings};
     }
 
     pub fn add_fields(&mut self, new_fields: TokenStream) {
@@ -143,21 +205,118 @@ impl ComponentSections {
         let items = &self.items;
         self.items = quote! {#items #new_items}
     }
+
+    pub fn add_parent_methods(&mut self, new_parent_methods: TokenStream) {
+        let parent_methods = &self.parent_methods;
+        self.parent_methods = quote! {#parent_methods #new_parent_methods}
+    }
+
+    pub fn add_parent_method_impls(&mut self, new_parent_method_impls: TokenStream) {
+        let parent_method_impls = &self.parent_method_impls;
+        self.parent_method_impls = quote! {#parent_method_impls #new_parent_method_impls}
+    }
+
+    pub fn add_debug_fields(&mut self, new_debug_fields: TokenStream) {
+        let debug_fields = &self.debug_fields;
+        self.debug_fields = quote! {#debug_fields #new_debug_fields}
+    }
+
+    pub fn add_stats(&mut self, new_stats: TokenStream) {
+        let stats = &self.stats;
+        self.stats = quote! {#stats #new_stats}
+    }
+
+    pub fn add_post_build_statements(&mut self, new_post_build_statements: TokenStream) {
+        let post_build_statements = &self.post_build_statements;
+        self.post_build_statements = quote! {#post_build_statements #new_post_build_statements}
+    }
+
+    pub fn add_warnings(&mut self, new_warnings: TokenStream) {
+        let warnings = &self.warnings;
+        self.warnings = quote! {#warnings #new_warnings}
+    }
+}
+
+/// Adds a counter of how many times `node` constructs its binding, reported by `lockjaw_stats()`.
+///
+/// Returns the statement incrementing the counter, which is empty unless the `profiling` feature is
+/// enabled.
+pub fn count_construction(node: &dyn Node, sections: &mut ComponentSections) -> TokenStream {
+    if !cfg!(feature = "profiling") {
+        return quote! {};
+    }
+    let counter = format_ident!("lockjaw_construction_count_{}", node.get_identifier());
+    let name = node.get_name();
+    sections.add_fields(quote! {
+        #counter: ::lockjaw::__private::std::sync::atomic::AtomicU64,
+    });
+    sections.add_ctor_params(quote! {
+        #counter: ::lockjaw::__private::std::sync::atomic::AtomicU64::new(0),
+    });
+    sections.add_stats(quote! {
+        (#name, self.#counter.load(::lockjaw::__private::std::sync::atomic::Ordering::Relaxed)),
+    });
+    quote! {
+        self.#counter.fetch_add(1, ::lockjaw::__private::std::sync::atomic::Ordering::Relaxed);
+    }
+}
+
+/// Nodes are keyed by the mangled [TypeData::identifier_string], which two different types may
+/// collide into (e.g. `crate::a::B` and `crate::aⵆB`). Merging them would silently bind one type
+/// to the other.
+fn check_identifier_collision(existing: &dyn Node, new: &dyn Node) -> Result<(), TokenStream> {
+    let existing_type = existing.get_type();
+    let new_type = new.get_type();
+    if existing_type.readable() == new_type.readable()
+        && existing_type.identifier_suffix == new_type.identifier_suffix
+    {
+        return Ok(());
+    }
+    compile_error(&format!(
+        "lockjaw is unable to distinguish {} and {}, which are both mangled into the identifier {}. \
+        Rename one of the types.\n\t{}\n\t{}",
+        existing_type.readable(),
+        new_type.readable(),
+        new_type.identifier_string(),
+        existing.get_name(),
+        new.get_name()
+    ))
 }
 
 pub fn generate_component(
     component: &Component,
     manifest: &Manifest,
 ) -> Result<(TokenStream, String), TokenStream> {
-    let (graph, missing_deps) = build_graph(manifest, component, &Vec::new())?;
+    let (graph, duration) = timings::time(|| build_graph(manifest, component, &Vec::new()));
+    let (graph, missing_deps) = graph?;
+    if is_timings() {
+        log!(
+            "{}",
+            timings::format(
+                &format!("build graph {}", component.type_data.readable()),
+                duration
+            )
+        );
+    }
+    let codegen_start = Instant::now();
     if !missing_deps.is_empty() {
         let mut error = quote! {};
         for dep in missing_deps {
             let msg = format!(
-                "missing bindings for {}\n{}",
+                "missing bindings for {}\n{}{}{}{}{}",
                 dep.type_data.readable(),
-                dep.to_message()
+                dep.to_message(),
+                strict_hint(manifest, &dep.type_data),
+                fallible_hint(manifest, &dep.type_data),
+                from_parent_hint(&dep.type_data),
+                qualifier_hint(&graph, &dep.type_data)
             );
+            diagnostics::report(Diagnostic::missing_binding(
+                &msg,
+                component.type_data.readable(),
+                dep.type_data.readable(),
+                dep.ancestors.iter().rev().cloned().collect(),
+            ));
             error = quote! {
                 #error
                 compile_error!(#msg);
@@ -170,15 +329,28 @@ pub fn generate_component(
 
     let mut component_sections = ComponentSections::new();
 
-    component_sections.merge(graph.generate_modules(&manifest));
     component_sections.merge(graph.generate_provisions(component)?);
+    // modules are dropped after the bindings they provide.
+    component_sections.merge(graph.generate_modules(&manifest));
 
     let fields = &component_sections.fields;
     let ctor_params = &component_sections.ctor_params;
     let ctor_statements = &component_sections.ctor_statements;
+    let post_build_statements = &component_sections.post_build_statements;
     let methods = &component_sections.methods;
     let trait_methods = &component_sections.trait_methods;
     let items = &component_sections.items;
+    let warnings = &component_sections.warnings;
+    let debug_impl = graph.generate_debug_impl(
+        &quote! {#component_impl_name},
+        &quote! {},
+        &component_sections.debug_fields,
+    );
+    let report = if is_report() {
+        component_report(component, &component_impl_name, fields)?
+    } else {
+        quote! {}
+    };
 
     let component_impl = quote! {
         #[doc(hidden)]
@@ -196,7 +368,9 @@ pub fn generate_component(
         impl #component_name for #component_impl_name {
             #trait_methods
         }
+        #debug_impl
         #items
+        #warnings
     };
 
     let builder_name = components::builder_name(&component.type_data);
@@ -204,64 +378,381 @@ pub fn generate_component(
         component_visibles::visible_type(graph.manifest, &component.address).syn_type();
     let component_initialzer =
         format_ident!("lockjaw_init_{}", component.type_data.identifier_string());
-
+    let register_builder = register_builder(
+        component,
+        &component_name,
+        &component_address_syn_type,
+        &builder_name,
+    );
+
+    let instances_param = instances_param(component);
     let builder = if graph.builder_modules.type_data.is_some() {
         let module_manifest_name = graph.builder_modules.type_data.unwrap().syn_type();
         quote! {
             #[doc(hidden)]
             #[allow(non_snake_case)]
-            fn #builder_name (param : #module_manifest_name) -> Box<dyn #component_name>{
+            fn #builder_name (param : #module_manifest_name, #instances_param) -> ::lockjaw::__private::std::result::Result<::lockjaw::__private::std::boxed::Box<dyn #component_name>, ::lockjaw::Error>{
                 #ctor_statements
-                Box::new(#component_impl_name{#ctor_params})
+                let component = ::lockjaw::__private::std::boxed::Box::new(#component_impl_name{#ctor_params});
+                #post_build_statements
+                ::lockjaw::__private::std::result::Result::Ok(component)
             }
 
             #[doc(hidden)]
             #[allow(non_snake_case)]
             fn #component_initialzer(){
-                unsafe{
-                    #component_address_syn_type = #builder_name as *const();
-                }
+                #register_builder
             }
         }
     } else {
         quote! {
             #[doc(hidden)]
             #[allow(non_snake_case)]
-            fn #builder_name () -> Box<dyn #component_name>{
+            fn #builder_name (#instances_param) -> ::lockjaw::__private::std::result::Result<::lockjaw::__private::std::boxed::Box<dyn #component_name>, ::lockjaw::Error>{
                 #ctor_statements
-                Box::new(#component_impl_name{#ctor_params})
+                let component = ::lockjaw::__private::std::boxed::Box::new(#component_impl_name{#ctor_params});
+                #post_build_statements
+                ::lockjaw::__private::std::result::Result::Ok(component)
             }
 
             #[allow(non_snake_case)]
             fn #component_initialzer(){
-                unsafe{
-                    #component_address_syn_type = #builder_name as *const();
-                }
+                #register_builder
             }
         }
     };
 
+    if is_timings() {
+        log!(
+            "{}",
+            timings::format(
+                &format!("codegen {}", component.type_data.readable()),
+                codegen_start.elapsed()
+            )
+        );
+    }
+
     Ok((
         quote! {
             #component_impl
             #builder
+            #report
         },
         format!("graph: {:#?}", graph.map),
     ))
 }
 
+/// Generates a builder for a component whose graph failed to resolve, along with the `errors`.
+///
+/// The builder panics if called, but keeps the rest of the epilogue intact so rust-analyzer can still
+/// resolve the component and other items while the errors are being fixed.
+pub fn generate_stub_component(
+    component: &Component,
+    manifest: &Manifest,
+    errors: TokenStream,
+) -> TokenStream {
+    let component_name =
+        component_visibles::visible_type(manifest, &component.type_data).syn_type();
+    let builder_name = components::builder_name(&component.type_data);
+    let component_address_syn_type =
+        component_visibles::visible_type(manifest, &component.address).syn_type();
+    let component_initialzer =
+        format_ident!("lockjaw_init_{}", component.type_data.identifier_string());
+    let register_builder = register_builder(
+        component,
+        &component_name,
+        &component_address_syn_type,
+        &builder_name,
+    );
+    let mut builder_params = Vec::new();
+    if let Some(ref builder_modules) = component.builder_modules {
+        let param_type = component_visibles::visible_type(manifest, builder_modules).syn_type();
+        builder_params.push(quote! {_param : #param_type});
+    }
+    if !component.instance_bindings.is_empty() {
+        builder_params.push(quote! {_instances : ::lockjaw::Instances});
+    }
+    quote! {
+        #errors
+
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        fn #builder_name (#(#builder_params),*) -> ::lockjaw::__private::std::result::Result<::lockjaw::__private::std::boxed::Box<dyn #component_name>, ::lockjaw::Error>{
+            ::lockjaw::__private::std::unimplemented!("the dependency graph of the component has errors")
+        }
+
+        #[doc(hidden)]
+        #[allow(non_snake_case)]
+        fn #component_initialzer(){
+            #register_builder
+        }
+    }
+}
+
+/// The `Instances` parameter of the builder, if the component has `instance_bindings`.
+fn instances_param(component: &Component) -> TokenStream {
+    if component.instance_bindings.is_empty() {
+        quote! {}
+    } else {
+        quote! {mut instances : ::lockjaw::Instances}
+    }
+}
+
+/// Stores the address of the builder where `try_build()` looks it up.
+///
+/// Instances of a generic component share one registry keyed by the `TypeId` of the component.
+fn register_builder(
+    component: &Component,
+    component_name: &syn::Type,
+    component_address: &syn::Type,
+    builder_name: &syn::Ident,
+) -> TokenStream {
+    if component.type_params.is_empty() {
+        quote! {
+            unsafe{
+                #component_address = #builder_name as *const();
+            }
+        }
+    } else {
+        quote! {
+            #component_address.register(
+                ::lockjaw::__private::std::any::TypeId::of::<dyn #component_name>(),
+                #builder_name as *const(),
+            );
+        }
+    }
+}
+
+/// Explains that an `#[inject(fallible)]` injectable is only bound as a `Result`.
+fn fallible_hint(manifest: &Manifest, type_data: &TypeData) -> String {
+    let mut type_ = type_data.clone();
+    type_.field_ref = false;
+    manifest
+        .injectables
+        .iter()
+        .find(|injectable| {
+            injectable.fallible.is_some() && injectable.type_data.identifier() == type_.identifier()
+        })
+        .map(|injectable| {
+            let ok = if injectable.type_data.scopes.is_empty() {
+                injectable.type_data.canonical_string_path()
+            } else {
+                format!("&{}", injectable.type_data.canonical_string_path())
+            };
+            format!(
+                "\n#[injectable] {} has an #[inject(fallible)] constructor, request Result<{}, {}> \
+                instead.",
+                injectable.type_data.readable(),
+                ok,
+                injectable.fallible.as_ref().unwrap().canonical_string_path()
+            )
+        })
+        .unwrap_or_default()
+}
+
+/// Explains that a component without a parent cannot request `lockjaw::FromParent` bindings.
+fn from_parent_hint(type_data: &TypeData) -> &'static str {
+    if is_from_parent(type_data) {
+        "\n#[qualified(lockjaw::FromParent)] can only be requested in a subcomponent"
+    } else {
+        ""
+    }
+}
+
+/// Lists bindings of the same type with a different qualifier, in case the request forgot or
+/// mistyped the qualifier.
+fn qualifier_hint(graph: &Graph, type_data: &TypeData) -> String {
+    if is_from_parent(type_data) {
+        return String::new();
+    }
+    let mut near_misses: Vec<String> = graph
+        .map
+        .values()
+        .map(|node| node.get_type())
+        .filter(|bound| {
+            bound.qualifier != type_data.qualifier
+                && bound.field_ref == type_data.field_ref
+                && bound.canonical_string_path() == type_data.canonical_string_path()
+        })
+        .map(TypeData::readable)
+        .collect();
+    if near_misses.is_empty() {
+        return String::new();
+    }
+    near_misses.sort();
+    near_misses.dedup();
+    format!(
+        "\nbindings with a different qualifier exist:\n\t{}",
+        near_misses.join("\n\t")
+    )
+}
+
+/// Explains why an `#[injectable]` from another crate is not bound in `epilogue!(strict)`.
+fn strict_hint(manifest: &Manifest, type_data: &TypeData) -> String {
+    if !is_strict() {
+        return String::new();
+    }
+    manifest
+        .injectables
+        .iter()
+        .find(|injectable| injectable.type_data.identifier() == type_data.identifier())
+        .map(|injectable| {
+            format!(
+                "\n#[injectable] {} is declared in crate {}, which is not available in \
+                epilogue!(strict). Install a #[module] from crate {} to use it.",
+                injectable.type_data.readable(),
+                injectable.type_data.field_crate,
+                injectable.type_data.field_crate
+            )
+        })
+        .unwrap_or_default()
+}
+
+/// Adds `attributes` to every fn in `items`, which are the contents of an impl block.
+fn annotate_fns(items: TokenStream, attributes: &TokenStream) -> TokenStream {
+    let parser = |input: syn::parse::ParseStream| {
+        let mut items = Vec::<syn::ImplItem>::new();
+        while !input.is_empty() {
+            items.push(input.parse()?);
+        }
+        Ok(items)
+    };
+    let Ok(items) = syn::parse::Parser::parse2(parser, items.clone()) else {
+        return items;
+    };
+    let mut result = quote! {};
+    for item in items {
+        if let syn::ImplItem::Fn(_) = item {
+            result = quote! { #result #attributes #item };
+        } else {
+            result = quote! { #result #item };
+        }
+    }
+    result
+}
+
 impl<'a> Graph<'a> {
+    /// Describes which binding generated the node, and where it is declared.
+    fn describe_origin(&self, node: &dyn Node) -> String {
+        let mut result = format!(" lockjaw: {}", node.get_name());
+        if let Some(origin) = node.get_origin() {
+            let identifier = origin.identifier();
+            let source_file = self
+                .manifest
+                .injectables
+                .iter()
+                .find(|injectable| injectable.type_data.identifier() == identifier)
+                .map(|injectable| injectable.source_file.clone())
+                .or_else(|| {
+                    self.manifest
+                        .modules
+                        .iter()
+                        .find(|module| module.type_data.identifier() == identifier)
+                        .map(|module| module.source_file.clone())
+                })
+                .unwrap_or_default();
+            result.push_str(&format!(
+                "\n declared by {} (crate: {}, file: {})",
+                origin.readable(),
+                origin.field_crate,
+                source_file
+            ));
+        }
+        result
+    }
+
+    /// Optimization attributes for the method generated by `node`. Providers only invoked to
+    /// initialize a scoped binding run once per component and are kept out of line, while
+    /// providers with at most `inline_threshold` dependencies are cheap enough to inline.
+    fn codegen_hint(&self, node: &dyn Node) -> TokenStream {
+        if !is_codegen_hints() {
+            return quote! {};
+        }
+        if node.as_any().downcast_ref::<ScopedNode>().is_none() && self.is_scoped_init(node) {
+            return quote! {
+                #[cold]
+                #[inline(never)]
+            };
+        }
+        if node.get_dependencies().len() <= inline_threshold() {
+            return quote! {#[inline]};
+        }
+        quote! {}
+    }
+
+    /// Asserts the type bound by `node` is `Send + Sync` in a `thread_safe` component, naming the
+    /// binding and the provision requesting it instead of failing deep inside generated code.
+    fn thread_safe_assertion(
+        &self,
+        node: &dyn Node,
+        component: &Component,
+        ancestors: &Vec<String>,
+    ) -> TokenStream {
+        if node.as_any().downcast_ref::<InjectableNode>().is_none()
+            && node.as_any().downcast_ref::<ProvidesNode>().is_none()
+        {
+            return quote! {};
+        }
+        let type_ = node.get_type();
+        let type_path = scoped::static_lifetime_type(self, type_);
+        let mut chain = vec![node.get_name()];
+        chain.extend(ancestors.clone());
+        let message = format!(
+            "{} is not Send + Sync, but is bound in thread_safe component {}\n{}",
+            type_.readable(),
+            component.type_data.readable(),
+            chain.join("\nrequested by: ")
+        );
+        quote! {
+            const _ : () = {
+                #[diagnostic::on_unimplemented(message = #message, label = "not Send + Sync")]
+                trait ThreadSafeBinding {}
+                impl<T: ?Sized + ::lockjaw::__private::std::marker::Send + ::lockjaw::__private::std::marker::Sync> ThreadSafeBinding for T {}
+                fn assert_thread_safe<T: ?Sized + ThreadSafeBinding>() {}
+                #[allow(dead_code)]
+                fn assert() {
+                    assert_thread_safe::<#type_path>();
+                }
+            };
+        }
+    }
+
+    /// Whether `node` is only depended on by the [ScopedNode] caching it.
+    fn is_scoped_init(&self, node: &dyn Node) -> bool {
+        let identifier = node.get_identifier();
+        let mut scoped = false;
+        for other in self.map.values() {
+            if !other
+                .get_dependencies()
+                .iter()
+                .any(|dependency| dependency.type_.identifier() == identifier)
+            {
+                continue;
+            }
+            if other.as_any().downcast_ref::<ScopedNode>().is_none() {
+                return false;
+            }
+            scoped = true;
+        }
+        scoped
+            && !self
+                .root_nodes
+                .iter()
+                .any(|root| root.get_identifier() == identifier)
+    }
+
     pub fn has_node(&self, type_data: &TypeData) -> bool {
         self.map.contains_key(&type_data.identifier())
     }
 
     fn add_node(&mut self, node: Box<dyn Node>) -> Result<(), TokenStream> {
         if self.map.contains_key(&node.get_type().identifier()) {
-            let merged_node = self
+            let existing_node = self
                 .map
                 .get(&node.get_type().identifier())
-                .expect("cannot find node")
-                .merge(node.borrow())?;
+                .expect("cannot find node");
+            check_identifier_collision(existing_node.as_ref(), node.as_ref())?;
+            let merged_node = existing_node.merge(node.borrow())?;
             self.map
                 .insert(merged_node.get_type().identifier(), merged_node);
         } else {
@@ -305,29 +796,219 @@ impl<'a> Graph<'a> {
         result
     }
 
+    /// Sorted names of the modules and builder modules installed in the component.
+    pub fn installed_module_names(&self) -> Vec<String> {
+        let mut modules: Vec<String> = self
+            .modules
+            .iter()
+            .chain(
+                self.builder_modules
+                    .builder_modules
+                    .iter()
+                    .map(|module| &module.type_data),
+            )
+            .map(|module| module.readable())
+            .collect();
+        modules.sort();
+        modules
+    }
+
+    /// Implements `Debug` for the component impl, listing the installed modules and whether each
+    /// scoped binding is initialized.
+    pub fn generate_debug_impl(
+        &self,
+        component_impl_type: &TokenStream,
+        generics: &TokenStream,
+        debug_fields: &TokenStream,
+    ) -> TokenStream {
+        let component_name = self.component.type_data.readable();
+        let modules = self.installed_module_names();
+        quote! {
+            impl #generics ::lockjaw::__private::std::fmt::Debug for #component_impl_type {
+                fn fmt(&self, f: &mut ::lockjaw::__private::std::fmt::Formatter<'_>) -> ::lockjaw::__private::std::fmt::Result {
+                    #[allow(unused_mut)]
+                    let mut scoped = ::lockjaw::__private::std::collections::BTreeMap::<&str, &str>::new();
+                    #debug_fields
+                    f.debug_struct(#component_name)
+                        .field("modules", &(&[#(#modules),*] as &[&str]))
+                        .field("scoped", &scoped)
+                        .finish()
+                }
+            }
+        }
+    }
+
     pub fn generate_provisions(
         &self,
         component: &Component,
     ) -> Result<ComponentSections, TokenStream> {
         let mut result = ComponentSections::new();
         let mut generated_nodes = HashSet::<Ident>::new();
+        let mut fields = Vec::<TokenStream>::new();
         for provision in &self.root_nodes {
             result.merge(self.generate_provision(
                 provision.deref(),
                 component,
                 &Vec::new(),
                 &mut generated_nodes,
+                &mut fields,
             )?);
         }
+        // fields are collected after the fields of their dependencies. Rust drops fields in
+        // declaration order, so declaring them in reverse drops every scoped binding before the
+        // bindings it depends on.
+        for field in fields.iter().rev() {
+            result.add_fields(field.clone());
+        }
+        result.add_warnings(self.unused_modules(component, &generated_nodes)?);
+        if is_lint_stateless_scoped() {
+            result.add_warnings(self.stateless_scoped(component, &generated_nodes));
+        }
+        if cfg!(feature = "profiling") {
+            let stats = &result.stats;
+            result.add_trait_methods(quote! {
+                fn lockjaw_stats(&self) -> ::lockjaw::__private::std::vec::Vec<(&'static str, u64)> {
+                    ::lockjaw::__private::std::vec![#stats]
+                }
+            });
+        }
+        Ok(result)
+    }
+
+    /// Warns about modules listed in the component's `modules` whose bindings are never used, or
+    /// fails in `epilogue!(deny_unused_modules)`.
+    fn unused_modules(
+        &self,
+        component: &Component,
+        generated_nodes: &HashSet<Ident>,
+    ) -> Result<TokenStream, TokenStream> {
+        // impl Trait provisions use the implementation of their #[binds] without generating it.
+        let impl_trait_binds = component
+            .provisions
+            .iter()
+            .filter(|provision| provision.impl_trait)
+            .filter_map(|provision| impl_trait_binds(self, provision))
+            .map(|binds| binds as &dyn Node);
+        let origins: Vec<&TypeData> = generated_nodes
+            .iter()
+            .filter_map(|identifier| self.map.get(identifier))
+            .map(|node| node.as_ref())
+            .chain(impl_trait_binds)
+            .filter_map(|node| node.get_origin())
+            .collect();
+        let mut result = quote! {};
+        for module_type in &component.modules {
+            if origins
+                .iter()
+                .any(|origin| origin.identifier() == module_type.identifier())
+            {
+                continue;
+            }
+            // in strict mode a module also exposes the #[injectable]s of its crate.
+            if module_type.field_crate != component.type_data.field_crate
+                && origins
+                    .iter()
+                    .any(|origin| origin.field_crate == module_type.field_crate)
+            {
+                continue;
+            }
+            let Some(module) = self
+                .manifest
+                .modules
+                .iter()
+                .find(|module| module.type_data.identifier() == module_type.identifier())
+            else {
+                continue;
+            };
+            // empty modules are placeholders with nothing to be unused, while multibinding
+            // declarations, conditional contributions and subcomponents are not traceable to the
+            // module.
+            if module.bindings.is_empty()
+                || !module.subcomponents.is_empty()
+                || module.bindings.iter().any(|binding| {
+                    binding.binding_type == BindingType::Multibinds
+                        || binding.binding_type == BindingType::BindsOptionOf
+                        || binding.if_present.is_some()
+                })
+            {
+                continue;
+            }
+            let message = format!(
+                "module {} is listed in the modules of {}, but none of its bindings are used",
+                module_type.readable(),
+                component.type_data.readable()
+            );
+            if is_deny_unused_modules() {
+                return compile_error(&message);
+            }
+            // proc_macros cannot emit warnings on stable, use a deprecated item to surface it.
+            result = quote! {
+                #result
+                const _ : () = {
+                    #[deprecated(note = #message)]
+                    #[allow(non_camel_case_types)]
+                    struct lockjaw_unused_module;
+                    let _ = lockjaw_unused_module;
+                };
+            };
+        }
         Ok(result)
     }
 
+    /// Warns about scoped `#[injectable]`s without interior mutability whose dependencies are all
+    /// references to other scoped bindings, since constructing them on each request is cheap and
+    /// caching them only costs a field in the component.
+    fn stateless_scoped(
+        &self,
+        component: &Component,
+        generated_nodes: &HashSet<Ident>,
+    ) -> TokenStream {
+        let mut injectables: Vec<&Injectable> = generated_nodes
+            .iter()
+            .filter_map(|identifier| self.map.get(identifier))
+            .filter_map(|node| node.as_any().downcast_ref::<ScopedNode>())
+            .filter_map(|node| self.map.get(&node.target.identifier()))
+            .filter_map(|node| node.as_any().downcast_ref::<InjectableNode>())
+            .map(|node| &node.injectable)
+            .filter(|injectable| {
+                !injectable.type_data.scopes.is_empty()
+                    && injectable.container.is_none()
+                    && injectable.interior_mutability == Some(false)
+                    && injectable
+                        .dependencies
+                        .iter()
+                        .all(|dependency| dependency.type_data.field_ref)
+            })
+            .collect();
+        injectables.sort_by_key(|injectable| injectable.type_data.readable());
+        let mut result = quote! {};
+        for injectable in injectables {
+            let message = format!(
+                "{} is scoped in {}, but has no interior mutability and only depends on other \
+                scoped bindings. Consider removing the scope so it is not stored in the component.",
+                injectable.type_data.readable(),
+                component.type_data.readable()
+            );
+            result = quote! {
+                #result
+                const _ : () = {
+                    #[deprecated(note = #message)]
+                    #[allow(non_camel_case_types)]
+                    struct lockjaw_stateless_scoped;
+                    let _ = lockjaw_stateless_scoped;
+                };
+            };
+        }
+        result
+    }
+
     fn generate_provision(
         &self,
         node: &dyn Node,
         component: &Component,
         ancestors: &Vec<String>,
         generated_nodes: &mut HashSet<Ident>,
+        fields: &mut Vec<TokenStream>,
     ) -> Result<ComponentSections, TokenStream> {
         let mut result = ComponentSections::new();
 
@@ -336,7 +1017,26 @@ impl<'a> Graph<'a> {
         }
 
         generated_nodes.insert(node.get_identifier());
-        result.merge(node.generate_implementation(self)?);
+        let mut implementation = node.generate_implementation(self)?;
+        let node_fields = std::mem::take(&mut implementation.fields);
+        if !implementation.methods.is_empty() {
+            let hint = self.codegen_hint(node);
+            let methods = implementation.methods;
+            implementation.methods = quote! {
+                #hint
+                #methods
+            };
+        }
+        if is_debug_output() {
+            let origin = self.describe_origin(node);
+            let doc = quote! {#[doc = #origin]};
+            implementation.methods = annotate_fns(implementation.methods, &doc);
+            implementation.trait_methods = annotate_fns(implementation.trait_methods, &doc);
+        }
+        result.merge(implementation);
+        if component.thread_safe {
+            result.add_items(self.thread_safe_assertion(node, component, ancestors));
+        }
 
         let mut new_ancestors = Vec::<String>::new();
         new_ancestors.push(node.get_name());
@@ -355,6 +1055,7 @@ impl<'a> Graph<'a> {
                 component,
                 &new_ancestors,
                 generated_nodes,
+                fields,
             )?);
         }
         for dependency in node.get_optional_dependencies() {
@@ -370,13 +1071,39 @@ impl<'a> Graph<'a> {
                 component,
                 &new_ancestors,
                 generated_nodes,
+                fields,
             )?);
         }
+        if !node_fields.is_empty() {
+            fields.push(node_fields);
+        }
         Ok(result)
     }
 
+    /// `#[doc]` attributes carrying the docs of the binding for `type_`, so they show up on the
+    /// provisions returning it. Scoped bindings take the docs of the binding they wrap.
+    pub fn doc_attributes(&self, type_: &TypeData) -> TokenStream {
+        let Some(mut node) = self.map.get(&type_.identifier()) else {
+            return quote! {};
+        };
+        while let Some(scoped) = node.as_any().downcast_ref::<ScopedNode>() {
+            let Some(target) = self.map.get(&scoped.target.identifier()) else {
+                return quote! {};
+            };
+            node = target;
+        }
+        let mut result = quote! {};
+        for line in node.get_doc().lines() {
+            result = quote! {
+                #result
+                #[doc = #line]
+            };
+        }
+        result
+    }
+
     pub fn has_lifetime(&self, type_: &TypeData) -> bool {
-        if type_.path == "lockjaw::Cl" {
+        if type_.path == "lockjaw::Cl" || type_.path == "lockjaw::EventBus" {
             return true;
         }
         return self.manifest.lifetimed_types.contains(type_);
@@ -448,6 +1175,174 @@ impl MissingDependency {
     }
 }
 
+thread_local! {
+    static DEBUG_OUTPUT : Cell<bool> = Cell::new(false);
+    static STRICT : Cell<bool> = Cell::new(false);
+    static DENY_UNUSED_MODULES : Cell<bool> = Cell::new(false);
+    static CODEGEN_HINTS : Cell<bool> = Cell::new(true);
+    static TIMINGS : Cell<bool> = Cell::new(false);
+    static REPORT : Cell<bool> = Cell::new(false);
+    static LINT_STATELESS_SCOPED : Cell<bool> = Cell::new(false);
+    static INLINE_THRESHOLD : Cell<usize> = Cell::new(DEFAULT_INLINE_THRESHOLD);
+}
+
+/// Default maximum number of dependencies for a provider to be marked `#[inline]`.
+pub const DEFAULT_INLINE_THRESHOLD: usize = 1;
+
+/// Annotates generated methods with where their bindings are declared, for
+/// `epilogue!(debug_output)`.
+pub fn set_debug_output(debug_output: bool) {
+    DEBUG_OUTPUT.with(|d| d.set(debug_output));
+}
+
+fn is_debug_output() -> bool {
+    DEBUG_OUTPUT.with(|d| d.get())
+}
+
+/// Only allows `#[injectable]`s from crates that the component installs a module from, for
+/// `epilogue!(strict)`.
+pub fn set_strict(strict: bool) {
+    STRICT.with(|s| s.set(strict));
+}
+
+fn is_strict() -> bool {
+    STRICT.with(|s| s.get())
+}
+
+/// Fails instead of warning about unused modules, for `epilogue!(deny_unused_modules)`.
+pub fn set_deny_unused_modules(deny_unused_modules: bool) {
+    DENY_UNUSED_MODULES.with(|d| d.set(deny_unused_modules));
+}
+
+fn is_deny_unused_modules() -> bool {
+    DENY_UNUSED_MODULES.with(|d| d.get())
+}
+
+/// Controls the `#[inline]`/`#[cold]` attributes on generated providers, for
+/// `epilogue!(no_codegen_hints)` and `epilogue!(inline_threshold(n))`.
+pub fn set_codegen_hints(enabled: bool, inline_threshold: usize) {
+    CODEGEN_HINTS.with(|c| c.set(enabled));
+    INLINE_THRESHOLD.with(|i| i.set(inline_threshold));
+}
+
+fn is_codegen_hints() -> bool {
+    CODEGEN_HINTS.with(|c| c.get())
+}
+
+fn inline_threshold() -> usize {
+    INLINE_THRESHOLD.with(|i| i.get())
+}
+
+/// Reports how long each component takes to generate, for `epilogue!(timings)`.
+pub fn set_timings(timings: bool) {
+    TIMINGS.with(|t| t.set(timings));
+}
+
+fn is_timings() -> bool {
+    TIMINGS.with(|t| t.get())
+}
+
+/// Prints the fields of each component, and generates a test reporting their sizes, for
+/// `epilogue!(report)`.
+pub fn set_report(report: bool) {
+    REPORT.with(|r| r.set(report));
+}
+
+fn is_report() -> bool {
+    REPORT.with(|r| r.get())
+}
+
+/// Warns about scoped `#[injectable]`s that do not need to be scoped, for
+/// `epilogue!(lint_stateless_scoped)`.
+pub fn set_lint_stateless_scoped(lint: bool) {
+    LINT_STATELESS_SCOPED.with(|l| l.set(lint));
+}
+
+fn is_lint_stateless_scoped() -> bool {
+    LINT_STATELESS_SCOPED.with(|l| l.get())
+}
+
+/// Logs the fields of the component impl, and generates a `#[test]` printing the size of each
+/// field, since the sizes are only known after type checking.
+fn component_report(
+    component: &Component,
+    component_impl_name: &Ident,
+    fields: &TokenStream,
+) -> Result<TokenStream, TokenStream> {
+    let fields: syn::FieldsNamed =
+        syn::parse2(quote! {{#fields}}).map_compile_error("unable to parse component fields")?;
+    let component_name = component.type_data.readable();
+    let mut message = format!("{} fields:", component_name);
+    let mut field_sizes = quote! {};
+    for field in &fields.named {
+        let name = field.ident.as_ref().unwrap().to_string();
+        let ty = &field.ty;
+        let type_string = quote! {#ty}.to_string();
+        message.push_str(&format!("\n  {}: {}", name, type_string));
+        field_sizes = quote! {
+            #field_sizes
+            report.push_str(&format!(
+                "\n  {}: {} ({} bytes)",
+                #name,
+                #type_string,
+                ::lockjaw::__private::std::mem::size_of::<#ty>()
+            ));
+        };
+    }
+    log!("{}", message);
+    let test_name = format_ident!(
+        "lockjaw_size_report_{}",
+        component.type_data.identifier_string()
+    );
+    Ok(quote! {
+        #[test]
+        #[allow(non_snake_case)]
+        fn #test_name() {
+            let mut report = format!(
+                "{} ({} bytes):",
+                #component_name,
+                ::lockjaw::__private::std::mem::size_of::<#component_impl_name>()
+            );
+            #field_sizes
+            println!("{}", report);
+        }
+    })
+}
+
+/// `#[factory(implementing: T, into_vec)]`, bound like a `#[binds] #[into_vec]` method taking the
+/// factory and returning `Cl<dyn T>`.
+fn factory_into_vec(
+    graph: &Graph,
+    injectable: &Injectable,
+    into_vec: &TypeData,
+) -> Result<Vec<Box<dyn Node>>, TokenStream> {
+    let mut dependency = Dependency::new();
+    dependency.type_data = injectable.type_data.clone();
+    let mut binding = Binding::new(BindingType::Binds);
+    binding.name = "lockjaw_into_vec".to_owned();
+    // like `#[binds]`, the binding holds the type inside `Cl`.
+    binding.type_data = into_vec.args[0].clone();
+    binding.dependencies.push(dependency);
+    binding.multibinding_type = MultibindingType::IntoVec;
+    BindsNode::new(&graph.builder_modules, &injectable.type_data, &binding)
+}
+
+/// Crates whose `#[injectable]`s are available to `component` in `epilogue!(strict)`.
+fn strict_crates(
+    manifest: &Manifest,
+    component: &Component,
+    installed_modules: &HashSet<Ident>,
+) -> HashSet<String> {
+    let mut result = HashSet::new();
+    result.insert(component.type_data.field_crate.clone());
+    for module in &manifest.modules {
+        if installed_modules.contains(&module.type_data.identifier()) {
+            result.insert(module.type_data.field_crate.clone());
+        }
+    }
+    result
+}
+
 pub fn build_graph<'a>(
     manifest: &'a Manifest,
     component: &Component,
@@ -461,31 +1356,34 @@ pub fn build_graph<'a>(
         root_nodes: vec![],
         manifest,
     };
+    let fragments = collect_fragments(manifest, component)?;
+    // bindings scoped to an embedded fragment are scoped to the component, like a `scope_group`.
+    let mut component = component.clone();
+    component
+        .scope_groups
+        .extend(fragments.iter().map(|fragment| fragment.type_data.clone()));
+    let component = &component;
     result.component = component.clone();
     let singleton = singleton_type();
     for node in parent_multibinding_nodes {
         result.add_node(node.clone_box())?;
     }
 
-    for injectable in &manifest.injectables {
-        if injectable.type_data.scopes.is_empty()
-            || injectable.type_data.scopes.contains(&component.type_data)
-            || injectable.type_data.scopes.contains(&singleton)
-        {
-            result.add_node(InjectableNode::new(injectable))?;
-            if !injectable.type_data.scopes.is_empty() {
-                let mut ref_type = injectable.type_data.clone();
-                ref_type.field_ref = true;
-                ref_type.scopes = HashSet::new();
-                result.add_node(ScopedNode::for_type(&ref_type))?;
-            }
-        }
-    }
     let mut installed_modules = HashSet::<Ident>::new();
     result.builder_modules = get_module_manifest(manifest, component)?;
     result.modules = HashSet::from_iter(component.modules.clone());
+    for fragment in &fragments {
+        result.modules.extend(fragment.modules.iter().cloned());
+    }
 
     for module in &manifest.modules {
+        if fragments
+            .iter()
+            .any(|fragment| module.install_in.contains(&fragment.type_data))
+        {
+            result.modules.insert(module.type_data.clone());
+            continue;
+        }
         if module.install_in.contains(&component.type_data)
             || (component.component_type == ComponentType::Component
                 && module.install_in.contains(&singleton_type()))
@@ -497,15 +1395,17 @@ pub fn build_graph<'a>(
                 if module.bindings.is_empty() && module.subcomponents.len() == 1 {
                     return compile_error(
                         &format!("#[subcomponent] {} has `parent` {},\
-                     but the component is not annotated with #[define_component] or #[define_subcomponent]",
+                     but the component is not annotated with #[define_component] or #[define_subcomponent], {}",
                                  module.subcomponents.iter().next().unwrap().readable(),
-                                 component.type_data.readable()));
+                                 component.type_data.readable(),
+                                 define_attribute_hint(component)));
                 }
                 return compile_error(
                     &format!("#[module] {} is `install_in` {},\
-                     but the component is not annotated with #[define_component] or #[define_subcomponent]",
+                     but the component is not annotated with #[define_component] or #[define_subcomponent], {}",
                              module.type_data.readable(),
-                             component.type_data.readable()));
+                             component.type_data.readable(),
+                             define_attribute_hint(component)));
             }
             result.modules.insert(module.type_data.clone());
         }
@@ -543,19 +1443,63 @@ pub fn build_graph<'a>(
     for module in &result.builder_modules.builder_modules {
         installed_modules.insert(module.type_data.identifier());
     }
+
+    for instance in &component.instance_bindings {
+        let node = InstanceNode::new(instance);
+        result.add_node(node.clone_box())?;
+        // always generated so every instance is moved into the component.
+        result.root_nodes.push(node);
+    }
+
+    let available_crates = strict_crates(manifest, component, &installed_modules);
+    let decorators = collect_decorators(manifest, component, &installed_modules, &singleton);
+    for injectable in &manifest.injectables {
+        if is_strict() && !available_crates.contains(&injectable.type_data.field_crate) {
+            continue;
+        }
+        if in_scope(&injectable.type_data, component, &singleton) {
+            let mut injectable = injectable.clone();
+            injectable.type_data = undecorated(&injectable.type_data, &decorators)?;
+            let injectable = &injectable;
+            result.add_node(InjectableNode::new(injectable))?;
+            if !injectable.type_data.scopes.is_empty() {
+                if let Some(ref error) = injectable.fallible {
+                    result.add_node(FallibleScopedNode::new(injectable, error))?;
+                } else {
+                    let mut ref_type = injectable.type_data.clone();
+                    ref_type.field_ref = true;
+                    ref_type.scopes = HashSet::new();
+                    result.add_node(ScopedNode::for_type(&ref_type))?;
+                }
+            }
+            if let Some(ref into_vec) = injectable.factory_into_vec {
+                result.add_nodes(factory_into_vec(&result, injectable, into_vec)?)?;
+            }
+        }
+    }
     for module in &manifest.modules {
         if !installed_modules.contains(&module.type_data.identifier()) {
             continue;
         }
         for binding in &module.bindings {
-            if binding.type_data.scopes.is_empty()
-                || binding.type_data.scopes.contains(&component.type_data)
-                || binding.type_data.scopes.contains(&singleton)
+            let binding = infer_binds_scope(manifest, module, binding, &decorators, &singleton)?;
+            let mut binding = install_scoped(&binding, component);
+            validate_install_scope(module, &binding, component)?;
+            if binding.binding_type != BindingType::Decorator
+                && binding.multibinding_type == MultibindingType::None
+                && in_scope(&binding.type_data, component, &singleton)
             {
+                binding.type_data = undecorated(&binding.type_data, &decorators)?;
+            }
+            let binding = &binding;
+            if in_scope(&binding.type_data, component, &singleton) {
                 result.add_nodes(match &binding.binding_type {
                     BindingType::Provides => {
                         ProvidesNode::new(&result.builder_modules, &module.type_data, binding)?
                     }
+                    BindingType::Binds if ArcBindsNode::is_arc_binds(binding) => {
+                        ArcBindsNode::new(&result.builder_modules, &module.type_data, binding)?
+                    }
                     BindingType::Binds => {
                         BindsNode::new(&result.builder_modules, &module.type_data, binding)?
                     }
@@ -575,10 +1519,39 @@ pub fn build_graph<'a>(
                             panic!("unexpected type for multibinds");
                         }
                     },
+                    // chained after all bindings are added.
+                    BindingType::Decorator => Vec::new(),
                 })?;
             }
         }
     }
+    for decorators in decorators.values() {
+        let nodes = decorator_nodes(&result, decorators)?;
+        result.add_nodes(nodes)?;
+    }
+    // `if_present` is checked once all bindings of the component are added.
+    let vec_types: Vec<Ident> = result
+        .map
+        .iter()
+        .filter(|(_, v)| v.as_any().downcast_ref::<VecNode>().is_some())
+        .map(|(k, _)| k.clone())
+        .collect();
+    for ident in vec_types {
+        let mut vec_node = result
+            .map
+            .get(&ident)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<VecNode>()
+            .unwrap()
+            .clone();
+        vec_node.retain_present(&result);
+        result.map.insert(ident, Box::new(vec_node));
+    }
+    // every multibinding contribution of this component, including the ones inherited from its own
+    // parent, is accessed by subcomponents through a `ParentNode`. Subcomponents request the whole
+    // collection from their parent, so the parent always generates the contributions, and
+    // inheritance is transitive however deep the subcomponents are nested.
     let mut multibinding_nodes: Vec<Box<dyn Node>> = Vec::new();
 
     for (_, v) in result.map.iter() {
@@ -591,7 +1564,8 @@ pub fn build_graph<'a>(
                     message: String::new(),
                     multibinding_type: binding.multibinding_type.clone(),
                 })?;
-                sub_vec_node.add_binding(&binding.type_data, &binding.multibinding_type);
+                // already filtered by `if_present` in the parent.
+                sub_vec_node.add_binding(parent_node.get_type(), &binding.multibinding_type);
                 multibinding_nodes.push(parent_node);
             }
             multibinding_nodes.push(sub_vec_node);
@@ -620,11 +1594,19 @@ pub fn build_graph<'a>(
             subcomponents.insert(subcomponent.clone());
         }
     }
+    if component.component_type == ComponentType::Subcomponent {
+        // a subcomponent can always build its siblings. This also stops a subcomponent that
+        // installs itself from being expanded infinitely.
+        subcomponents.retain(|subcomponent| {
+            subcomponent.canonical_string_path() != component.type_data.canonical_string_path()
+        });
+        result.add_node(SubcomponentSelfBuilderNode::new(&component.type_data))?;
+    }
     for subcomponent in &subcomponents {
         result.add_nodes(SubcomponentNode::new(
             manifest,
             subcomponent,
-            &component.type_data,
+            &result,
             &multibinding_nodes,
         )?)?;
     }
@@ -632,7 +1614,12 @@ pub fn build_graph<'a>(
     let mut resolved_nodes = HashSet::<Ident>::new();
     let mut missing_deps = Vec::new();
     for provision in &component.provisions {
-        let provision = Box::new(ProvisionNode::new(provision.clone(), component.clone()));
+        let provision = if provision.impl_trait {
+            resolve_impl_trait_provision(&result, component, provision)?
+        } else {
+            provision.clone()
+        };
+        let provision = Box::new(ProvisionNode::new(provision, component.clone()));
         missing_deps.extend(resolve_dependencies(
             provision.as_ref(),
             &mut result.map,
@@ -650,9 +1637,10 @@ pub fn build_graph<'a>(
             if !component.definition_only {
                 return compile_error(
                     &format!("#[entry_point] {} is `install_in` {},\
-                     but the component is not annotated with #[define_component] or #[define_subcomponent]",
+                     but the component is not annotated with #[define_component] or #[define_subcomponent], {}",
                              entry_point.type_data.readable(),
-                             component.type_data.readable()));
+                             component.type_data.readable(),
+                             define_attribute_hint(component)));
             }
             let node = Box::new(EntryPointNode::new(entry_point));
             missing_deps.extend(resolve_dependencies(
@@ -666,6 +1654,38 @@ pub fn build_graph<'a>(
         }
     }
 
+    // subcomponents inherit the listeners of their parent, which already ran when the parent was
+    // built.
+    if component.component_type != ComponentType::Subcomponent
+        && result.has_node(&PostBuildNode::listeners_type())
+    {
+        let node = PostBuildNode::new();
+        missing_deps.extend(resolve_dependencies(
+            node.as_ref(),
+            &mut result.map,
+            vec![],
+            vec![],
+            &mut resolved_nodes,
+        )?);
+        result.root_nodes.push(node);
+    }
+
+    if component.auto_default_zst {
+        let mut unresolved_deps = Vec::new();
+        for missing_dep in missing_deps {
+            if missing_dep.multibinding_type == MultibindingType::None {
+                if let Some(node) = DefaultZstNode::for_type(&missing_dep.type_data) {
+                    if !result.has_node(&missing_dep.type_data) {
+                        result.add_node(node)?;
+                    }
+                    continue;
+                }
+            }
+            unresolved_deps.push(missing_dep);
+        }
+        missing_deps = unresolved_deps;
+    }
+
     if component.component_type == ComponentType::Subcomponent {
         for (_, v) in &mut result.map {
             if let Some(vec_node) = v.as_mut_any().downcast_mut::<VecNode>() {
@@ -685,15 +1705,280 @@ pub fn build_graph<'a>(
             }
         }
 
-        for missing_dep in &missing_deps {
-            result.add_node(ParentNode::new(&missing_dep)?)?;
+        for missing_dep in &mut missing_deps {
+            let parent_node = ParentNode::new(missing_dep)?;
+            // requested from the parent without the `FromParent` qualifier.
+            missing_dep.type_data = parent_node.parent_type.clone();
+            result.add_node(parent_node)?;
         }
     }
     validate_graph(manifest, &result)?;
     Ok((result, missing_deps))
 }
 
-fn singleton_type() -> TypeData {
+/// The `#[component_fragment]`s embedded in the component, including the ones embedded by other
+/// fragments.
+pub fn collect_fragments<'a>(
+    manifest: &'a Manifest,
+    component: &Component,
+) -> Result<Vec<&'a ComponentFragment>, TokenStream> {
+    let mut result = Vec::<&ComponentFragment>::new();
+    let mut pending: Vec<&TypeData> = component.fragments.iter().rev().collect();
+    while let Some(fragment_type) = pending.pop() {
+        if result
+            .iter()
+            .any(|fragment| &fragment.type_data == fragment_type)
+        {
+            continue;
+        }
+        let Some(fragment) = manifest
+            .component_fragments
+            .iter()
+            .find(|fragment| &fragment.type_data == fragment_type)
+        else {
+            return compile_error(&format!(
+                "{} is not a #[component_fragment], required by {}",
+                fragment_type.readable(),
+                component.type_data.readable()
+            ));
+        };
+        pending.extend(fragment.fragments.iter().rev());
+        result.push(fragment);
+    }
+    Ok(result)
+}
+
+/// `#[binds_decorator]` methods of the installed modules, keyed by the decorated type and sorted in
+/// the order they wrap the binding.
+fn collect_decorators<'a>(
+    manifest: &'a Manifest,
+    component: &Component,
+    installed_modules: &HashSet<Ident>,
+    singleton: &TypeData,
+) -> BTreeMap<String, Vec<(&'a Module, &'a Binding)>> {
+    let mut result = BTreeMap::<String, Vec<(&Module, &Binding)>>::new();
+    for module in &manifest.modules {
+        if !installed_modules.contains(&module.type_data.identifier()) {
+            continue;
+        }
+        for binding in &module.bindings {
+            if binding.binding_type == BindingType::Decorator
+                && in_scope(&binding.type_data, component, singleton)
+            {
+                result
+                    .entry(binding.type_data.identifier_string())
+                    .or_default()
+                    .push((module, binding));
+            }
+        }
+    }
+    for decorators in result.values_mut() {
+        decorators.sort_by_key(|(module, binding)| {
+            (
+                binding.priority,
+                module.type_data.canonical_string_path(),
+                binding.name.clone(),
+            )
+        });
+    }
+    result
+}
+
+/// Moves the binding of a decorated type aside, so the outermost decorator is bound to the type
+/// instead.
+fn undecorated(
+    type_data: &TypeData,
+    decorators: &BTreeMap<String, Vec<(&Module, &Binding)>>,
+) -> Result<TypeData, TokenStream> {
+    if !decorators.contains_key(&type_data.identifier_string()) {
+        return Ok(type_data.clone());
+    }
+    if !type_data.scopes.is_empty() {
+        return compile_error(&format!(
+            "scoped binding {} cannot be decorated",
+            type_data.readable()
+        ));
+    }
+    let mut result = type_data.clone();
+    result.identifier_suffix = "_undecorated".to_owned();
+    Ok(result)
+}
+
+/// Chains the decorators of a type, each requesting the binding wrapped by the previous one as its
+/// inner binding, so a decorator never depends on itself.
+fn decorator_nodes(
+    graph: &Graph,
+    decorators: &[(&Module, &Binding)],
+) -> Result<Vec<Box<dyn Node>>, TokenStream> {
+    let decorated_type = &decorators[0].1.type_data;
+    let mut inner = decorated_type.clone();
+    inner.identifier_suffix = "_undecorated".to_owned();
+    if !graph.map.contains_key(&inner.identifier()) {
+        return compile_error(&format!(
+            "no binding of {} to decorate, decorated by:\n\t{}",
+            decorated_type.readable(),
+            decorators
+                .iter()
+                .map(|(module, binding)| format!(
+                    "{}.{}",
+                    module.type_data.readable(),
+                    binding.name
+                ))
+                .collect::<Vec<String>>()
+                .join("\n\t")
+        ));
+    }
+    // the inner parameter is not qualified even if the decorator is.
+    let mut inner_param_type = decorated_type.clone();
+    inner_param_type.qualifier = None;
+    let mut result = Vec::new();
+    for (i, (module, decorator)) in decorators.iter().enumerate() {
+        let mut binding = (*decorator).clone();
+        if i + 1 < decorators.len() {
+            binding.type_data.identifier_suffix = format!("_decorator_{}", i);
+        }
+        for dependency in &mut binding.dependencies {
+            if dependency.type_data == inner_param_type {
+                dependency.type_data = inner.clone();
+            }
+        }
+        inner = binding.type_data.clone();
+        result.extend(ProvidesNode::new(
+            &graph.builder_modules,
+            &module.type_data,
+            &binding,
+        )?);
+    }
+    Ok(result)
+}
+
+/// The attribute change needed to install modules/entry points into `component`.
+fn define_attribute_hint(component: &Component) -> &'static str {
+    match component.component_type {
+        ComponentType::Component => "change #[component] to #[define_component]",
+        ComponentType::Subcomponent => "change #[subcomponent] to #[define_subcomponent]",
+    }
+}
+
+/// Whether a binding of `type_data` can be installed in `component`.
+/// Scopes a `scope: install` binding to `component`, which its module is installed in.
+fn install_scoped(binding: &Binding, component: &Component) -> Binding {
+    let mut result = binding.clone();
+    if binding.install_scoped {
+        result.type_data.scopes.insert(component.type_data.clone());
+    }
+    result
+}
+
+/// `#[binds]` of a reference to a scoped `#[injectable]` takes the scope of the implementation, unless
+/// it is scoped explicitly to a compatible scope.
+///
+/// Decorated bindings are left unscoped, since scoped bindings cannot be decorated.
+fn infer_binds_scope(
+    manifest: &Manifest,
+    module: &Module,
+    binding: &Binding,
+    decorators: &BTreeMap<String, Vec<(&Module, &Binding)>>,
+    singleton: &TypeData,
+) -> Result<Binding, TokenStream> {
+    let mut result = binding.clone();
+    if binding.binding_type != BindingType::Binds
+        || binding.install_scoped
+        || decorators.contains_key(&binding.type_data.identifier_string())
+    {
+        return Ok(result);
+    }
+    let Some(dependency) = binding.dependencies.first() else {
+        return Ok(result);
+    };
+    if !dependency.type_data.field_ref || dependency.type_data.qualifier.is_some() {
+        return Ok(result);
+    }
+    let Some(implementation) = manifest.injectables.iter().find(|injectable| {
+        injectable.type_data.canonical_string_path() == dependency.type_data.canonical_string_path()
+    }) else {
+        return Ok(result);
+    };
+    let implementation_scopes = &implementation.type_data.scopes;
+    if implementation_scopes.is_empty() {
+        return Ok(result);
+    }
+    if binding.type_data.scopes.is_empty() {
+        result.type_data.scopes = implementation_scopes.clone();
+        return Ok(result);
+    }
+    if !implementation_scopes.contains(singleton)
+        && binding.type_data.scopes.is_disjoint(implementation_scopes)
+    {
+        let mut binding_scopes: Vec<String> = binding
+            .type_data
+            .scopes
+            .iter()
+            .map(TypeData::readable)
+            .collect();
+        binding_scopes.sort();
+        let mut implementation_scopes: Vec<String> = implementation_scopes
+            .iter()
+            .map(TypeData::readable)
+            .collect();
+        implementation_scopes.sort();
+        return compile_error(&format!(
+            "{}.{} is scoped to {}, but its implementation {} is scoped to {}. Remove the scope \
+            from #[binds] to use the scope of the implementation",
+            module.type_data.readable(),
+            binding.name,
+            binding_scopes.join(", "),
+            implementation.type_data.readable(),
+            implementation_scopes.join(", ")
+        ));
+    }
+    Ok(result)
+}
+
+/// A binding in a module installed in multiple components cannot be scoped to only some of them,
+/// since it would silently be missing from the others.
+fn validate_install_scope(
+    module: &Module,
+    binding: &Binding,
+    component: &Component,
+) -> Result<(), TokenStream> {
+    if module.install_in.len() < 2
+        || !module.install_in.contains(&component.type_data)
+        || binding.type_data.scopes.contains(&component.type_data)
+    {
+        return Ok(());
+    }
+    let scoped_to: Vec<String> = binding
+        .type_data
+        .scopes
+        .iter()
+        .filter(|scope| module.install_in.contains(scope))
+        .map(|scope| scope.readable())
+        .collect();
+    if scoped_to.is_empty() {
+        return Ok(());
+    }
+    compile_error(&format!(
+        "{}.{} is scoped to {}, but the module is also installed in {}. Use `scope: install` to \
+        scope it to each component the module is installed in",
+        module.type_data.readable(),
+        binding.name,
+        scoped_to.join(", "),
+        component.type_data.readable()
+    ))
+}
+
+fn in_scope(type_data: &TypeData, component: &Component, singleton: &TypeData) -> bool {
+    type_data.scopes.is_empty()
+        || type_data.scopes.contains(&component.type_data)
+        || type_data.scopes.contains(singleton)
+        || component
+            .scope_groups
+            .iter()
+            .any(|group| type_data.scopes.contains(group))
+}
+
+pub fn singleton_type() -> TypeData {
     let mut result = TypeData::new();
     result.root = TypeRoot::GLOBAL;
     result.path = "lockjaw::Singleton".to_string();
@@ -701,6 +1986,23 @@ fn singleton_type() -> TypeData {
     result
 }
 
+/// `lockjaw::FromParent`, the qualifier a subcomponent uses to request a binding from its parent
+/// even if it binds the same type itself.
+pub fn from_parent_type() -> TypeData {
+    let mut result = TypeData::new();
+    result.root = TypeRoot::GLOBAL;
+    result.path = "lockjaw::FromParent".to_string();
+    result.field_crate = "lockjaw".to_string();
+    result
+}
+
+pub fn is_from_parent(type_data: &TypeData) -> bool {
+    type_data
+        .qualifier
+        .as_ref()
+        .is_some_and(|qualifier| qualifier.as_ref() == &from_parent_type())
+}
+
 fn resolve_dependencies(
     node: &dyn Node,
     map: &mut HashMap<Ident, Box<dyn Node>>,
@@ -803,7 +2105,8 @@ fn validate_graph(manifest: &Manifest, graph: &Graph) -> Result<(), TokenStream>
     let qualifiers: HashSet<TypeData> = HashSet::from_iter(manifest.qualifiers.clone());
     for node in graph.map.values() {
         if let Some(ref qualifier) = node.get_type().qualifier {
-            if !qualifiers.contains(qualifier) {
+            // `lockjaw::FromParent` is built into lockjaw rather than a #[qualifier] struct.
+            if !qualifiers.contains(qualifier) && !is_from_parent(node.get_type()) {
                 return compile_error(&format!(
                     "{} binds {} with a qualifier, but the qualifier struct is not annotated with \
                     the #[lockjaw::qualifier] attribute",
@@ -813,5 +2116,183 @@ fn validate_graph(manifest: &Manifest, graph: &Graph) -> Result<(), TokenStream>
             }
         }
     }
+    validate_scope_width(graph)?;
+    validate_mutable_provisions(graph)?;
+    Ok(())
+}
+
+/// `-> impl Trait` provisions return the implementation bound by `#[binds(static_dispatch)]` or to
+/// `Cl<dyn Trait>` by `#[binds]` directly, matching the return type `#[component]` rewrote the
+/// provision to.
+fn resolve_impl_trait_provision(
+    graph: &Graph,
+    component: &Component,
+    provision: &Dependency,
+) -> Result<Dependency, TokenStream> {
+    let Some(binds) = impl_trait_binds(graph, provision) else {
+        return compile_error(&format!(
+            "{}.{} returns impl Trait, which requires {} to be bound with #[binds]",
+            component.type_data.readable(),
+            provision.name,
+            provision.type_data.readable()
+        ));
+    };
+    let mut result = provision.clone();
+    result.type_data = binds.dependency.clone();
+    Ok(result)
+}
+
+/// The `#[binds]` an `impl Trait` provision returns the implementation of.
+fn impl_trait_binds<'a>(graph: &'a Graph, provision: &Dependency) -> Option<&'a BindsNode> {
+    graph
+        .map
+        .get(&components::impl_trait_type(&provision.type_data).identifier())
+        .or_else(|| graph.map.get(&provision.type_data.identifier()))
+        .and_then(|node| node.as_any().downcast_ref::<BindsNode>())
+}
+
+/// Only one `&mut T` provision can exist for each `T`, so exclusive access cannot be obtained
+/// through different paths.
+fn validate_mutable_provisions(graph: &Graph) -> Result<(), TokenStream> {
+    let mut mutable_provisions = HashMap::<Ident, &Dependency>::new();
+    for provision in &graph.component.provisions {
+        if !provision.mutable {
+            continue;
+        }
+        if let Some(existing) =
+            mutable_provisions.insert(provision.type_data.identifier(), provision)
+        {
+            return compile_error(&format!(
+                "found multiple &mut provisions for {} in {}:\n\t{}\n\t{}",
+                provision.type_data.readable(),
+                graph.component.type_data.readable(),
+                existing.name,
+                provision.name
+            ));
+        }
+        validate_mutable_provision_dependents(graph, provision)?;
+    }
+    Ok(())
+}
+
+/// The `&mut self` receiver only guarantees exclusive access against references handed out by the
+/// component methods. Other scoped bindings are stored inside the component, and may hold `&T`
+/// while `&mut T` is handed out.
+fn validate_mutable_provision_dependents(
+    graph: &Graph,
+    provision: &Dependency,
+) -> Result<(), TokenStream> {
+    let mut target = provision.type_data.clone();
+    target.field_ref = false;
+    let target_identifier = target.identifier();
+    let ref_identifier = provision.type_data.identifier();
+    for node in graph.map.values() {
+        if node.get_type().scopes.is_empty() || node.get_identifier() == target_identifier {
+            continue;
+        }
+        let mut visited = HashSet::<Ident>::new();
+        let mut pending = vec![node.as_ref()];
+        while let Some(current) = pending.pop() {
+            let dependencies = current
+                .get_dependencies()
+                .into_iter()
+                .map(|dependency| dependency.type_)
+                .chain(current.get_optional_dependencies());
+            for dependency in dependencies {
+                let identifier = dependency.identifier();
+                if identifier == target_identifier || identifier == ref_identifier {
+                    return compile_error(&format!(
+                        "{}.{} returns &mut {}, but the scoped binding {} depends on it and may \
+                        hold a reference to it",
+                        graph.component.type_data.readable(),
+                        provision.name,
+                        target.readable(),
+                        node.get_name()
+                    ));
+                }
+                if !visited.insert(identifier.clone()) {
+                    continue;
+                }
+                if let Some(dependency_node) = graph.map.get(&identifier) {
+                    pending.push(dependency_node.as_ref());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `Singleton` scoped bindings can be installed in any component, and must not capture bindings
+/// scoped to a specific component, which is always narrower.
+fn validate_scope_width(graph: &Graph) -> Result<(), TokenStream> {
+    let singleton = singleton_type();
+    for node in graph.map.values() {
+        if !node.get_type().scopes.contains(&singleton) {
+            continue;
+        }
+        let mut visited = HashSet::<Ident>::new();
+        validate_scope_width_impl(
+            graph,
+            node.as_ref(),
+            &singleton,
+            &mut vec![node.get_name()],
+            &mut visited,
+        )?;
+    }
+    Ok(())
+}
+
+fn validate_scope_width_impl(
+    graph: &Graph,
+    node: &dyn Node,
+    singleton: &TypeData,
+    ancestors: &mut Vec<String>,
+    visited: &mut HashSet<Ident>,
+) -> Result<(), TokenStream> {
+    let dependencies = node
+        .get_dependencies()
+        .into_iter()
+        .map(|dependency| dependency.type_)
+        .chain(node.get_optional_dependencies());
+    for dependency in dependencies {
+        let identifier = dependency.identifier();
+        if !visited.insert(identifier.clone()) {
+            continue;
+        }
+        let dependency_node = if let Some(dependency_node) = graph.map.get(&identifier) {
+            dependency_node
+        } else {
+            continue;
+        };
+        let scopes = &dependency_node.get_type().scopes;
+        if scopes.contains(singleton) {
+            // validated on its own.
+            continue;
+        }
+        if !scopes.is_empty() {
+            let mut reverse_ancestors = ancestors.clone();
+            reverse_ancestors.reverse();
+            return compile_error(&format!(
+                "Singleton scoped binding cannot depend on {}, which is scoped to the narrower {}\n\
+                requested by:{}",
+                dependency_node.get_name(),
+                scopes
+                    .iter()
+                    .map(|scope| scope.readable())
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                reverse_ancestors.join("\nrequested by:")
+            ));
+        }
+        ancestors.push(dependency_node.get_name());
+        validate_scope_width_impl(
+            graph,
+            dependency_node.as_ref(),
+            singleton,
+            ancestors,
+            visited,
+        )?;
+        ancestors.pop();
+    }
     Ok(())
 }
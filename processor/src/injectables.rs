@@ -16,12 +16,16 @@ limitations under the License.
 
 use std::collections::{HashMap, HashSet};
 
+use crate::components;
 use crate::error::{spanned_compile_error, CompileError};
 use crate::parsing;
 use crate::parsing::FieldValue;
 
+use crate::type_data::ProcessorTypeData;
 use crate::type_validator::TypeValidator;
 use lazy_static::lazy_static;
+use lockjaw_common::environment::current_package;
+use lockjaw_common::manifest::TypeRoot;
 use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote};
 use syn::spanned::Spanned;
@@ -32,6 +36,7 @@ lazy_static! {
         let mut set = HashSet::<String>::new();
         set.insert("scope".to_owned());
         set.insert("container".to_owned());
+        set.insert("default".to_owned());
         set
     };
 }
@@ -41,10 +46,22 @@ lazy_static! {
         let mut set = HashSet::<String>::new();
         set.insert("implementing".to_owned());
         set.insert("visibility".to_owned());
+        set.insert("into_vec".to_owned());
         set
     };
 }
 
+lazy_static! {
+    static ref INJECT_METADATA_KEYS: HashSet<String> = {
+        let mut set = HashSet::<String>::new();
+        set.insert("fallible".to_owned());
+        set
+    };
+}
+
+/// Name of the ctor generated by `#[injectable(default)]`.
+const DEFAULT_CTOR_NAME: &str = "lockjaw_new_default";
+
 #[derive(PartialEq)]
 enum CtorType {
     Inject,
@@ -56,16 +73,19 @@ pub fn handle_injectable_attribute(
     input: TokenStream,
 ) -> Result<TokenStream, TokenStream> {
     let span = input.span();
-    let mut item: syn::ItemImpl =
-        syn::parse2(input).map_spanned_compile_error(span, "impl block expected")?;
-    let mut type_validator = TypeValidator::new();
-
     let attributes = parsing::get_attribute_field_values(attr.clone())?;
     for key in attributes.keys() {
         if !INJECTABLE_METADATA_KEYS.contains(key) {
             return spanned_compile_error(attr.span(), &format!("unknown key: {}", key));
         }
     }
+    if is_default(&attributes)? {
+        return handle_default(attr.span(), input, &attributes);
+    }
+
+    let mut item: syn::ItemImpl =
+        syn::parse2(input).map_spanned_compile_error(span, "impl block expected")?;
+    let mut type_validator = TypeValidator::new();
     let (ctor_type, ctor, fields) = get_ctor(item.span(), &mut item.items)?;
     if ctor_type == CtorType::Factory {
         let factory = handle_factory(item.self_ty.clone(), ctor.clone(), fields.clone())?;
@@ -89,6 +109,30 @@ pub fn handle_injectable_attribute(
             #factory
         });
     }
+    for param in &ctor.sig.generics.params {
+        if let syn::GenericParam::Lifetime(_) = param {
+            continue;
+        }
+        return spanned_compile_error(
+            param.span(),
+            "generic type parameters are not supported on #[inject] constructors since lockjaw \
+            cannot choose a type for them, use a concrete type or #[factory] with the parameter \
+            marked #[runtime]",
+        );
+    }
+    for (key, value) in &fields {
+        if !INJECT_METADATA_KEYS.contains(key) {
+            return spanned_compile_error(value.span(), &format!("unknown key: {}", key));
+        }
+    }
+    let fallible = is_fallible(&fields)?;
+    if fallible && attributes.contains_key("container") {
+        return spanned_compile_error(
+            ctor.sig.span(),
+            "#[inject(fallible)] cannot be used with 'container'",
+        );
+    }
+    validate_ctor_return_type(&ctor.sig, &item.self_ty, fallible)?;
     for arg in ctor.sig.inputs.iter_mut() {
         if let FnArg::Receiver(ref receiver) = arg {
             return spanned_compile_error(receiver.span(), &format!("self not allowed"));
@@ -99,7 +143,7 @@ pub fn handle_injectable_attribute(
                 for attr in &type_.attrs {
                     match parsing::get_attribute(attr).as_str() {
                         "qualified" => {
-                            type_validator.add_path(
+                            type_validator.add_qualifier_path(
                                 &parsing::get_path(&attr.meta.require_list().unwrap().tokens)?,
                                 attr.span(),
                             );
@@ -115,9 +159,7 @@ pub fn handle_injectable_attribute(
     }
 
     if let Some(scopes) = attributes.get("scope") {
-        for (path, span) in scopes.get_paths()? {
-            type_validator.add_dyn_path(&path, span);
-        }
+        validate_scopes(scopes, &mut type_validator)?;
     } else {
         if attributes.contains_key("container") {
             return spanned_compile_error(
@@ -127,6 +169,7 @@ pub fn handle_injectable_attribute(
         }
     }
     validate_container(attr.span(), &attributes, &mut type_validator, &item.self_ty)?;
+    validate_resolved_container(&attributes, &mut type_validator, &item.self_ty)?;
 
     let type_check = type_validator.validate(parsing::type_string(&item.self_ty)?);
 
@@ -138,6 +181,64 @@ pub fn handle_injectable_attribute(
     Ok(result)
 }
 
+fn is_default(attributes: &HashMap<String, FieldValue>) -> Result<bool, TokenStream> {
+    match attributes.get("default") {
+        None => Ok(false),
+        // `#[injectable(default)]` is parsed as the shorthand `default: default`
+        Some(FieldValue::Path(_, path)) if path.is_ident("default") => Ok(true),
+        Some(FieldValue::BoolLiteral(_, value)) => Ok(*value),
+        Some(value) => spanned_compile_error(value.span(), "bool expected for 'default'"),
+    }
+}
+
+fn handle_default(
+    attr_span: Span,
+    input: TokenStream,
+    attributes: &HashMap<String, FieldValue>,
+) -> Result<TokenStream, TokenStream> {
+    let span = input.span();
+    let item: syn::Item = syn::parse2(input)
+        .map_spanned_compile_error(span, "struct or enum expected for #[injectable(default)]")?;
+    let (ident, generics) = match item {
+        syn::Item::Struct(ref item) => (&item.ident, &item.generics),
+        syn::Item::Enum(ref item) => (&item.ident, &item.generics),
+        _ => {
+            return spanned_compile_error(
+                span,
+                "struct or enum expected for #[injectable(default)]",
+            )
+        }
+    };
+    let mut type_validator = TypeValidator::new();
+
+    if let Some(scopes) = attributes.get("scope") {
+        validate_scopes(scopes, &mut type_validator)?;
+    } else if attributes.contains_key("container") {
+        return spanned_compile_error(
+            span,
+            "the 'container' metadata should only be used with an injectable that also has 'scope'",
+        );
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let self_ty: syn::Type = syn::parse_quote! {#ident #ty_generics};
+    validate_container(attr_span, attributes, &mut type_validator, &self_ty)?;
+    validate_resolved_container(attributes, &mut type_validator, &self_ty)?;
+
+    let type_check = type_validator.validate(ident.to_string());
+    let ctor_name = format_ident!("{}", DEFAULT_CTOR_NAME);
+
+    Ok(quote! {
+        #item
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #[doc(hidden)]
+            pub fn #ctor_name() -> Self {
+                <Self as ::lockjaw::__private::std::default::Default>::default()
+            }
+        }
+        #type_check
+    })
+}
+
 fn get_ctor(
     span: Span,
     items: &mut Vec<ImplItem>,
@@ -199,7 +300,17 @@ fn validate_container(
 ) -> Result<(), TokenStream> {
     if attributes.contains_key("container") {
         if let FieldValue::Path(span, path) = attributes.get("container").unwrap() {
-            type_validator.add_path_and_arg(path, span.clone(), element_type);
+            // `Mutex`/`RwLock` may be used without importing them, and are resolved to
+            // `std::sync` by the build script.
+            let path = match path
+                .get_ident()
+                .and_then(|ident| lockjaw_common::type_data::sync_container(&ident.to_string()))
+            {
+                Some(sync_container) => syn::parse_str(&format!("::{}", sync_container))
+                    .map_spanned_compile_error(span.clone(), "path expected")?,
+                None => path.clone(),
+            };
+            type_validator.add_path_and_arg(&path, span.clone(), element_type);
         } else {
             return spanned_compile_error(span, "path expected for 'container'");
         }
@@ -207,6 +318,87 @@ fn validate_container(
     Ok(())
 }
 
+fn validate_scopes(
+    scopes: &FieldValue,
+    type_validator: &mut TypeValidator,
+) -> Result<(), TokenStream> {
+    let mut seen = HashSet::<String>::new();
+    for (path, span) in scopes.get_paths()? {
+        let path_string = quote! {#path}.to_string().replace(' ', "");
+        if !seen.insert(path_string.clone()) {
+            return spanned_compile_error(span, &format!("duplicate scope `{}`", path_string));
+        }
+        type_validator.add_dyn_path(&path, span);
+    }
+    Ok(())
+}
+
+/// The epilogue names the container the way the build script resolved it, possibly from another
+/// crate, and shares it between threads if a scope is a `thread_safe` component. Reports mismatches
+/// here instead of letting the generated code fail at the crate root.
+fn validate_resolved_container(
+    attributes: &HashMap<String, FieldValue>,
+    type_validator: &mut TypeValidator,
+    self_ty: &syn::Type,
+) -> Result<(), TokenStream> {
+    let Some(FieldValue::Path(span, _)) = attributes.get("container") else {
+        return Ok(());
+    };
+    let syn::Type::Path(ref self_path) = self_ty else {
+        return Ok(());
+    };
+    let name = self_path.path.segments.last().unwrap().ident.to_string();
+    for for_test in [false, true] {
+        let manifest = crate::crate_manifest(for_test)?;
+        let Some(injectable) = manifest.injectables.iter().find(|injectable| {
+            injectable.type_data.field_crate.replace('-', "_") == current_package()
+                && injectable.type_data.path.rsplit("::").next() == Some(name.as_str())
+                && injectable.container.is_some()
+        }) else {
+            continue;
+        };
+        let container = injectable.container.as_ref().unwrap();
+        if manifest.private_types.contains(container) {
+            return spanned_compile_error(
+                span.clone(),
+                &format!(
+                    "container {} is not visible to the crate root, add #[component_visible] to it",
+                    container.readable()
+                ),
+            );
+        }
+        let container_type = container.syn_type();
+        let container_type: syn::Type = syn::parse_quote! {#container_type<#self_ty>};
+        if container.root == TypeRoot::CRATE {
+            // the path guessed by the build script if the container is not imported by name.
+            type_validator.add_type(&container_type, span.clone());
+        }
+        for scope in &injectable.type_data.scopes {
+            let Some(component) = manifest
+                .components
+                .iter()
+                .find(|component| component.type_data == *scope && component.thread_safe)
+            else {
+                continue;
+            };
+            type_validator.add_thread_safe(
+                &container_type,
+                span.clone(),
+                &format!(
+                    "container {} of {} is not Send + Sync, but it is scoped to thread_safe \
+                    component {} (crate: {}), use `container: Mutex` or `container: RwLock`",
+                    container.readable(),
+                    injectable.type_data.readable(),
+                    component.type_data.readable(),
+                    component.type_data.field_crate
+                ),
+            );
+        }
+        return Ok(());
+    }
+    Ok(())
+}
+
 fn handle_factory(
     mut self_ty: Box<syn::Type>,
     method: ImplItemFn,
@@ -221,6 +413,25 @@ fn handle_factory(
     let mut fields_arg = quote! {};
     let mut runtime_args = quote! {};
     let mut args = quote! {};
+    // reuse the lifetime of the injectable so it can be referenced by the ctor params.
+    let factory_lifetime = method
+        .sig
+        .generics
+        .lifetimes()
+        .next()
+        .map(|lifetime_param| lifetime_param.lifetime.clone())
+        .or_else(|| user_lifetime(&self_ty))
+        .unwrap_or_else(components::component_lifetime);
+    // Generic parameters can only be inferred from #[runtime] arguments, so they are moved to the
+    // factory method instead of the factory.
+    let generic_params = parsing::generic_type_params(&method.sig.generics);
+    let mut method_generics = method.sig.generics.clone();
+    method_generics.params = method_generics
+        .params
+        .into_iter()
+        .filter(|param| !matches!(param, syn::GenericParam::Lifetime(_)))
+        .collect();
+    let where_clause = method_generics.where_clause.take();
     for arg in method.sig.inputs.iter() {
         if let FnArg::Receiver(ref receiver) = arg {
             return spanned_compile_error(receiver.span(), &format!("self not allowed"));
@@ -240,9 +451,16 @@ fn handle_factory(
                     }
                 } else {
                     let ty = &type_.ty;
+                    if parsing::mentions_idents(quote! {#ty}, &generic_params) {
+                        return spanned_compile_error(
+                            ty.span(),
+                            "generic type parameters can only be used by #[runtime] parameters of \
+                            a #[factory] since lockjaw cannot choose a type for them",
+                        );
+                    }
                     fields = quote! {
                         #fields
-                        #ident : ::lockjaw::Provider<'a, #ty>,
+                        #ident : ::lockjaw::Provider<#factory_lifetime, #ty>,
                     };
                     fields_arg = quote! {
                         #fields_arg
@@ -263,7 +481,7 @@ fn handle_factory(
     if let syn::Type::Path(ref mut path) = self_ty.as_mut() {
         let last_segment = path.path.segments.last_mut().unwrap();
         if last_segment.arguments != PathArguments::None {
-            lifetime = quote! {<'a>};
+            lifetime = quote! {<#factory_lifetime>};
             last_segment.arguments = PathArguments::None;
         }
 
@@ -276,6 +494,27 @@ fn handle_factory(
     } else {
         return spanned_compile_error(self_ty.span(), &format!("path expected"));
     }
+    if let Some(into_vec) = metadata.get("into_vec") {
+        match into_vec {
+            // `#[factory(into_vec)]` is parsed as the shorthand `into_vec: into_vec`
+            FieldValue::Path(_, path) if path.is_ident("into_vec") => {}
+            FieldValue::BoolLiteral(_, _) => {}
+            _ => return spanned_compile_error(into_vec.span(), "bool expected for 'into_vec'"),
+        }
+        if !metadata.contains_key("implementing") {
+            return spanned_compile_error(
+                into_vec.span(),
+                "'into_vec' requires 'implementing', the factory is contributed to \
+                Vec<Cl<dyn Trait>>",
+            );
+        }
+    }
+    // a factory implementing a trait shared by several types may return e.g. `Box<dyn Trait>`
+    // instead of the struct itself.
+    let return_type = match method.sig.output {
+        syn::ReturnType::Type(_, ref ty) if !is_self(ty) => quote! {#ty},
+        _ => quote! {#self_ty #lifetime},
+    };
     let method_name = method.sig.ident;
     let method_viz;
     let impl_for = if let Some(implementing) = metadata.get("implementing") {
@@ -315,24 +554,33 @@ fn handle_factory(
 
     let result = quote! {
         #component_visible
-        #factory_viz struct #factory_ty<'a> {
+        #factory_viz struct #factory_ty<#factory_lifetime> {
             #fields
-            lockjaw_phamtom_data: ::std::marker::PhantomData<&'a ::std::string::String>
+            lockjaw_phamtom_data: ::lockjaw::__private::std::marker::PhantomData<&#factory_lifetime ::lockjaw::__private::std::string::String>
         }
         #[::lockjaw::injectable]
-        impl <'a> #factory_ty<'a> {
+        impl <#factory_lifetime> #factory_ty<#factory_lifetime> {
             #[doc(hidden)]
             #[inject]
             pub fn lockjaw_new_factory(#fields) -> Self{
                 Self{
                     #fields_arg
-                    lockjaw_phamtom_data: ::std::marker::PhantomData
+                    lockjaw_phamtom_data: ::lockjaw::__private::std::marker::PhantomData
                 }
             }
         }
 
-        impl <'a> #impl_for #factory_ty<'a> {
-            #method_viz fn #method_name(&self,#runtime_args) -> #self_ty #lifetime {
+        impl <#factory_lifetime> #impl_for #factory_ty<#factory_lifetime> {
+            #method_viz fn #method_name #method_generics(&self,#runtime_args) -> #return_type #where_clause {
+                #self_ty::#method_name(#args)
+            }
+        }
+
+        impl <#factory_lifetime> #factory_ty<#factory_lifetime> {
+            /// Entry for component provisions with parameters, which cannot know the name of the
+            /// factory method.
+            #[doc(hidden)]
+            pub fn lockjaw_create #method_generics(&self,#runtime_args) -> #return_type #where_clause {
                 #self_ty::#method_name(#args)
             }
         }
@@ -341,3 +589,136 @@ fn handle_factory(
     //log!("{}", result.to_string());
     Ok(result)
 }
+
+fn is_fallible(fields: &HashMap<String, FieldValue>) -> Result<bool, TokenStream> {
+    match fields.get("fallible") {
+        None => Ok(false),
+        // `#[inject(fallible)]` is parsed as the shorthand `fallible: fallible`
+        Some(FieldValue::Path(_, path)) if path.is_ident("fallible") => Ok(true),
+        Some(FieldValue::BoolLiteral(_, value)) => Ok(*value),
+        Some(value) => spanned_compile_error(value.span(), "bool expected for 'fallible'"),
+    }
+}
+
+/// The `#[inject]` constructor must return `Self` or the type of the impl block, since the generated
+/// code stores its result as the injectable. `#[inject(fallible)]` constructors return it in a
+/// `Result`.
+fn validate_ctor_return_type(
+    sig: &syn::Signature,
+    self_ty: &syn::Type,
+    fallible: bool,
+) -> Result<(), TokenStream> {
+    if fallible {
+        return validate_fallible_return_type(sig, self_ty);
+    }
+    let ty = match sig.output {
+        syn::ReturnType::Type(_, ref ty) => ty,
+        syn::ReturnType::Default => {
+            return spanned_compile_error(
+                sig.span(),
+                &format!(
+                    "#[inject] constructor must return Self ({})",
+                    quote! {#self_ty}
+                ),
+            )
+        }
+    };
+    if is_self(ty) || is_same_type(ty, self_ty) {
+        return Ok(());
+    }
+    let hint = if validate_fallible_return_type(sig, self_ty).is_ok() {
+        ", use #[inject(fallible)] for constructors that can fail"
+    } else {
+        ""
+    };
+    spanned_compile_error(
+        ty.span(),
+        &format!(
+            "#[inject] constructor must return Self ({}), found {}{}",
+            quote! {#self_ty},
+            quote! {#ty},
+            hint
+        ),
+    )
+}
+
+fn validate_fallible_return_type(
+    sig: &syn::Signature,
+    self_ty: &syn::Type,
+) -> Result<(), TokenStream> {
+    let message = |found: String| {
+        format!(
+            "#[inject(fallible)] constructor must return Result<Self, E> (Result<{}, E>), found {}",
+            quote! {#self_ty},
+            found
+        )
+    };
+    let ty = match sig.output {
+        syn::ReturnType::Type(_, ref ty) => ty,
+        syn::ReturnType::Default => {
+            return spanned_compile_error(sig.span(), &message("()".to_owned()))
+        }
+    };
+    if let syn::Type::Path(ref path) = **ty {
+        if let Some(segment) = path.path.segments.last() {
+            if let PathArguments::AngleBracketed(ref angle) = segment.arguments {
+                let args: Vec<&syn::GenericArgument> = angle.args.iter().collect();
+                if let [syn::GenericArgument::Type(ok), syn::GenericArgument::Type(_)] =
+                    args.as_slice()
+                {
+                    if segment.ident == "Result" && (is_self(ok) || is_same_type(ok, self_ty)) {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+    spanned_compile_error(ty.span(), &message(quote! {#ty}.to_string()))
+}
+
+/// Whether `ty` names `self_ty`, ignoring the path prefix and lifetimes, which the compiler checks.
+fn is_same_type(ty: &syn::Type, self_ty: &syn::Type) -> bool {
+    let (syn::Type::Path(ty), syn::Type::Path(self_ty)) = (ty, self_ty) else {
+        return false;
+    };
+    let (Some(segment), Some(self_segment)) =
+        (ty.path.segments.last(), self_ty.path.segments.last())
+    else {
+        return false;
+    };
+    segment.ident == self_segment.ident
+        && type_args(&segment.arguments) == type_args(&self_segment.arguments)
+}
+
+fn type_args(arguments: &PathArguments) -> Vec<String> {
+    let PathArguments::AngleBracketed(ref angle) = arguments else {
+        return Vec::new();
+    };
+    angle
+        .args
+        .iter()
+        .filter(|arg| !matches!(arg, syn::GenericArgument::Lifetime(_)))
+        .map(|arg| quote! {#arg}.to_string())
+        .collect()
+}
+
+fn is_self(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Path(path) if path.qself.is_none() && path.path.is_ident("Self"))
+}
+
+fn user_lifetime(self_ty: &syn::Type) -> Option<syn::Lifetime> {
+    let syn::Type::Path(ref path) = self_ty else {
+        return None;
+    };
+    let PathArguments::AngleBracketed(ref angle) = path.path.segments.last()?.arguments else {
+        return None;
+    };
+    angle.args.iter().find_map(|arg| {
+        if let syn::GenericArgument::Lifetime(lifetime) = arg {
+            if lifetime.ident != "_" {
+                return Some(lifetime.clone());
+            }
+        }
+        None
+    })
+}
@@ -18,30 +18,41 @@ limitations under the License.
 
 use proc_macro;
 use proc_macro::TokenStream;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::BufReader;
 use std::path::Path;
 use std::process::Command;
+use std::time::Instant;
 
 use quote::quote;
 
 use error::handle_error;
 
+use crate::codegen_cache::CodegenCache;
 use crate::error::CompileError;
-use lockjaw_common::environment::{current_crate, current_package};
+use lockjaw_common::environment::{
+    current_crate, current_package, current_version, LOCKJAW_TRYBUILD_PATH_ENV,
+};
 use lockjaw_common::manifest::LockjawPackage;
-use lockjaw_common::manifest::{ComponentType, DepManifests, Manifest};
+use lockjaw_common::manifest::{
+    CfgManifest, ComponentType, DepManifests, Manifest, LOCKJAW_VERSION, MANIFEST_SCHEMA_VERSION,
+};
+use lockjaw_common::timings;
 #[macro_use]
 mod log;
+mod assertions;
+mod codegen_cache;
 mod component_visibles;
 mod components;
+mod diagnostics;
 mod entrypoints;
 mod environment;
 mod error;
 mod graph;
 mod injectables;
 mod manifest;
+mod markdown_report;
 mod modules;
 mod nodes;
 mod parsing;
@@ -87,6 +98,11 @@ pub fn subcomponent(attr: TokenStream, input: TokenStream) -> TokenStream {
     })
 }
 
+#[proc_macro_attribute]
+pub fn component_fragment(attr: TokenStream, input: TokenStream) -> TokenStream {
+    handle_error(|| components::handle_component_fragment_attribute(attr.into(), input.into()))
+}
+
 #[proc_macro_attribute]
 pub fn define_component(attr: TokenStream, input: TokenStream) -> TokenStream {
     handle_error(|| {
@@ -130,11 +146,21 @@ pub fn module_binds(_attr: TokenStream, _input: TokenStream) -> TokenStream {
     doc_proc_macro("#[binds] should only annotate an item under a #[module] item. This attribute macro is for documentation purpose only and should not be called directly.")
 }
 
+#[proc_macro_attribute]
+pub fn module_binds_all(_attr: TokenStream, _input: TokenStream) -> TokenStream {
+    doc_proc_macro("#[binds_all] should only annotate an item under a #[module] item. This attribute macro is for documentation purpose only and should not be called directly.")
+}
+
 #[proc_macro_attribute]
 pub fn module_binds_option_of(_attr: TokenStream, _input: TokenStream) -> TokenStream {
     doc_proc_macro("#[binds_option_of] should only annotate an item under a #[module] item. This attribute macro is for documentation purpose only and should not be called directly.")
 }
 
+#[proc_macro_attribute]
+pub fn module_binds_decorator(_attr: TokenStream, _input: TokenStream) -> TokenStream {
+    doc_proc_macro("#[binds_decorator] should only annotate an item under a #[module] item. This attribute macro is for documentation purpose only and should not be called directly.")
+}
+
 #[proc_macro_attribute]
 pub fn module_multibinds(_attr: TokenStream, _input: TokenStream) -> TokenStream {
     doc_proc_macro("#[multibinds] should only annotate an item under a #[module] item. This attribute macro is for documentation purpose only and should not be called directly.")
@@ -172,6 +198,12 @@ pub fn component_visible(attr: TokenStream, input: TokenStream) -> TokenStream {
     })
 }
 
+#[proc_macro]
+pub fn register_manifest(_input: TokenStream) -> TokenStream {
+    // the declarations are only read by the build script.
+    TokenStream::new()
+}
+
 #[proc_macro]
 pub fn epilogue(input: TokenStream) -> TokenStream {
     let input2: proc_macro2::TokenStream = input.into();
@@ -188,19 +220,32 @@ pub fn epilogue(input: TokenStream) -> TokenStream {
 struct EpilogueConfig {
     for_test: bool,
     debug_output: bool,
+    prune_components: bool,
+    strict: bool,
+    deny_unused_modules: bool,
     root: bool,
+    doctest: bool,
+    no_codegen_hints: bool,
+    inline_threshold: Option<usize>,
+    timings: bool,
+    report: bool,
+    markdown_report: bool,
+    codegen_cache: bool,
+    lint_stateless_scoped: bool,
 }
 
 #[proc_macro]
 pub fn private_root_epilogue(input: TokenStream) -> TokenStream {
     handle_error(|| {
         let mut config = EpilogueConfig {
-            ..create_epilogue_config(input)
+            ..create_epilogue_config(input)?
         };
         if current_package().eq("lockjaw") {
             // rustdoc --test does not run with #[cfg(test)] and will reach here.
             config.for_test = true;
             config.root = true;
+            // lockjaw's own examples are gathered by its build script and can be resolved.
+            config.doctest = false;
         }
 
         internal_epilogue(config)
@@ -213,30 +258,141 @@ pub fn private_test_epilogue(input: TokenStream) -> TokenStream {
         let config = EpilogueConfig {
             for_test: true,
             root: true,
-            ..create_epilogue_config(input)
+            ..create_epilogue_config(input)?
         };
         internal_epilogue(config)
     })
 }
 
-fn create_epilogue_config(input: TokenStream) -> EpilogueConfig {
-    let set: HashSet<String> = input.into_iter().map(|t| t.to_string()).collect();
-    EpilogueConfig {
+#[proc_macro]
+pub fn assert_binding_exists(input: TokenStream) -> TokenStream {
+    handle_error(|| assertions::handle_assert_binding_exists(input.into()))
+}
+
+fn create_epilogue_config(input: TokenStream) -> Result<EpilogueConfig, proc_macro2::TokenStream> {
+    let tokens: Vec<proc_macro2::TokenTree> =
+        proc_macro2::TokenStream::from(input).into_iter().collect();
+    let set: HashSet<String> = tokens.iter().map(|t| t.to_string()).collect();
+    Ok(EpilogueConfig {
         debug_output: set.contains("debug_output"),
+        prune_components: set.contains("prune_components"),
+        strict: set.contains("strict"),
+        deny_unused_modules: set.contains("deny_unused_modules"),
         for_test: false,
         root: std::env::var("CARGO_BIN_NAME").is_ok(),
+        doctest: environment::is_doctest(),
+        no_codegen_hints: set.contains("no_codegen_hints"),
+        inline_threshold: parse_inline_threshold(&tokens)?,
+        timings: set.contains("timings") || timings::enabled(),
+        report: set.contains("report"),
+        markdown_report: set.contains("markdown_report"),
+        codegen_cache: set.contains("codegen_cache"),
+        lint_stateless_scoped: set.contains("lint_stateless_scoped"),
         ..EpilogueConfig::default()
-    }
+    })
+}
+
+/// Parses `inline_threshold(n)` from the `epilogue!()` parameters.
+fn parse_inline_threshold(
+    tokens: &Vec<proc_macro2::TokenTree>,
+) -> Result<Option<usize>, proc_macro2::TokenStream> {
+    let index = match tokens
+        .iter()
+        .position(|t| t.to_string() == "inline_threshold")
+    {
+        Some(index) => index,
+        None => return Ok(None),
+    };
+    let group = match tokens.get(index + 1) {
+        Some(proc_macro2::TokenTree::Group(group)) => group,
+        _ => {
+            return error::spanned_compile_error(
+                tokens[index].span(),
+                "inline_threshold requires a value, e.g. inline_threshold(2)",
+            )
+        }
+    };
+    let threshold = syn::parse2::<syn::LitInt>(group.stream())
+        .and_then(|lit| lit.base10_parse::<usize>())
+        .map_spanned_compile_error(
+            group.span(),
+            "inline_threshold expects a non-negative integer",
+        )?;
+    Ok(Some(threshold))
 }
 
 fn internal_epilogue(
     config: EpilogueConfig,
 ) -> Result<proc_macro2::TokenStream, proc_macro2::TokenStream> {
-    let merged_manifest = merge_manifest(&config)?;
-    let expanded_visibilities = component_visibles::expand_visibilities(&merged_manifest)?;
+    if config.doctest {
+        return Ok(doctest_epilogue());
+    }
+    let mut config = config;
+    let epilogue_start = Instant::now();
+    let (merged_manifest, duration) = timings::time(|| merge_manifest(&mut config));
+    let merged_manifest = merged_manifest?;
+    graph::set_timings(config.timings);
+    if config.timings {
+        log!("{}", timings::format("load manifest", duration));
+    }
+    graph::set_debug_output(config.debug_output);
+    graph::set_strict(config.strict);
+    graph::set_deny_unused_modules(config.deny_unused_modules);
+    graph::set_report(config.report);
+    graph::set_lint_stateless_scoped(config.lint_stateless_scoped);
+    graph::set_codegen_hints(
+        !config.no_codegen_hints,
+        config
+            .inline_threshold
+            .unwrap_or(graph::DEFAULT_INLINE_THRESHOLD),
+    );
+    let (expanded_visibilities, duration) =
+        timings::time(|| component_visibles::expand_visibilities(&merged_manifest));
+    let expanded_visibilities = expanded_visibilities?;
+    if config.timings {
+        log!("{}", timings::format("expand visibilities", duration));
+    }
 
-    let (components, initiazers, messages) =
-        components::generate_components(&merged_manifest, config.root)?;
+    // the report is logged while generating the component, which a cached component would skip.
+    let cache = if config.codegen_cache && !config.report {
+        Some(CodegenCache::new(
+            &merged_manifest,
+            &format!(
+                "{} {} {} {} {:?} {}",
+                config.debug_output,
+                config.strict,
+                config.deny_unused_modules,
+                config.no_codegen_hints,
+                config.inline_threshold,
+                config.lint_stateless_scoped
+            ),
+            config.for_test,
+        )?)
+    } else {
+        None
+    };
+    let (components, initiazers, messages) = components::generate_components(
+        &merged_manifest,
+        config.root,
+        config.prune_components,
+        cache.as_ref(),
+    )?;
+    if config.markdown_report {
+        let path = markdown_report::write(&merged_manifest, config.root, config.for_test)?;
+        log!(
+            "writing markdown report to file:///{}",
+            path.display().to_string().replace("\\", "/")
+        );
+    }
+    if config.timings {
+        log!(
+            "{}",
+            timings::format(
+                &format!("epilogue total ({})", current_crate()),
+                epilogue_start.elapsed()
+            )
+        );
+    }
 
     let path_test;
     if config.for_test {
@@ -257,13 +413,24 @@ fn internal_epilogue(
     }
 
     let root_component_initializer = if config.root {
+        let initializer_names = components::root_components_initializers(&merged_manifest);
+        let initializer_name = &initializer_names[0];
+        let aliases = &initializer_names[1..];
         quote! {
             #[doc(hidden)]
             #[no_mangle]
             #[allow(non_snake_case)]
-            pub(crate) fn lockjaw_init_root_components(){
+            pub(crate) fn #initializer_name(){
                 #initiazers
             }
+            #(
+                #[doc(hidden)]
+                #[no_mangle]
+                #[allow(non_snake_case)]
+                pub(crate) fn #aliases(){
+                    #initializer_name()
+                }
+            )*
         }
     } else {
         quote! {}
@@ -283,53 +450,107 @@ fn internal_epilogue(
             content.push_str(&format!("/*\n{}\n*/\n", message));
         }
         content.push_str(&result.to_string());
-        let path = format!(
-            "{}debug_{}.rs",
-            environment::lockjaw_output_dir()?,
-            current_crate()
-        );
+        let output_dir = environment::lockjaw_output_dir()?;
+        let path = output_dir
+            .join(format!("debug_{}.rs", current_crate()))
+            .display()
+            .to_string();
         log!(
             "writing debug output to file:///{}",
             path.replace("\\", "/")
         );
-        std::fs::create_dir_all(Path::new(&environment::lockjaw_output_dir()?))
-            .expect("cannot create output dir");
+        std::fs::create_dir_all(&output_dir).expect("cannot create output dir");
         std::fs::write(Path::new(&path), &content)
             .expect(&format!("cannot write debug output to {}", path));
 
-        Command::new("rustfmt")
-            .arg(&path)
-            .output()
-            .map_compile_error("unable to format output")?;
+        let rustfmt = environment::rustfmt();
+        let format_error = match Command::new(&rustfmt).arg(&path).output() {
+            Ok(output) if output.status.success() => None,
+            Ok(output) => Some(String::from_utf8_lossy(&output.stderr).to_string()),
+            Err(e) => Some(e.to_string()),
+        };
+        if let Some(format_error) = format_error {
+            // formatting is only a convenience, leave the output unformatted instead of failing.
+            log!(
+                "unable to format debug output with {}: {}",
+                rustfmt,
+                format_error
+            );
+            let note = format!(
+                "/* unable to format with `{}`, set {} to the rustfmt binary to enable formatting:\n{}\n*/\n",
+                rustfmt,
+                environment::RUSTFMT_ENV,
+                format_error.replace("*/", "* /")
+            );
+            std::fs::write(Path::new(&path), note + &content)
+                .expect(&format!("cannot write debug output to {}", path));
+        }
 
         Ok(quote! {
-            std::include!(#path);
+            ::lockjaw::__private::std::include!(#path);
         })
     } else {
         Ok(result)
     }
 }
 
-fn merge_manifest(config: &EpilogueConfig) -> Result<Manifest, proc_macro2::TokenStream> {
+/// Inert epilogue for doctests.
+///
+/// The build script only gathers bindings of the documented crate, and its paths are relative to
+/// that crate, so the graph cannot be generated inside the doctest. Only the root initializer is
+/// emitted so the example links, and components report `lockjaw::Error::NotGenerated` when built.
+fn doctest_epilogue() -> proc_macro2::TokenStream {
+    let initializer_names =
+        components::root_components_initializers(&crate_manifest(false).unwrap_or_default());
+    quote! {
+        #(
+            #[doc(hidden)]
+            #[no_mangle]
+            #[allow(non_snake_case)]
+            pub(crate) fn #initializer_names(){}
+        )*
+    }
+}
+
+/// Manifest of the crate being compiled, for macros that need the bindings before `epilogue!()`.
+fn crate_manifest(for_test: bool) -> Result<Manifest, proc_macro2::TokenStream> {
+    merge_manifest(&mut EpilogueConfig {
+        for_test,
+        ..EpilogueConfig::default()
+    })
+}
+
+fn merge_manifest(config: &mut EpilogueConfig) -> Result<Manifest, proc_macro2::TokenStream> {
     let mut result: Manifest = Manifest::new();
-    if let Ok(manifest) = std::env::var("LOCKJAW_TRYBUILD_PATH") {
+    if let Ok(manifest) = std::env::var(LOCKJAW_TRYBUILD_PATH_ENV) {
         let test_manifest = lockjaw_common::manifest_parser::parse_manifest(&LockjawPackage {
             id: "".to_string(),
             name: std::env::var("CARGO_PKG_NAME").unwrap().replace("-", "_"),
+            version: current_version(),
             src_path: manifest,
             direct_prod_crate_deps: vec![],
             direct_test_crate_deps: vec![],
+            features: None,
         })
         .test_manifest;
         result.merge_from(&test_manifest);
+        components::instantiate_generic_components(&mut result);
         return Ok(result);
     }
 
     if let Ok(manifest) = std::env::var("LOCKJAW_DEP_MANIFEST") {
-        let reader = BufReader::new(File::open(manifest).expect("cannot find manifest file"));
-        let dep_manifest: DepManifests =
+        let reader = BufReader::new(
+            File::open(&manifest).expect(&format!("cannot find manifest file {}", manifest)),
+        );
+        let dep_manifest_json: serde_json::Value =
             serde_json::from_reader(reader).expect("cannot read manifest");
-        if config.for_test {
+        check_manifest_schema(&dep_manifest_json)?;
+        let dep_manifest: DepManifests =
+            serde_json::from_value(dep_manifest_json).expect("cannot read manifest");
+        check_crate_lockjaw_versions(&dep_manifest)?;
+        // tests use the prod graph if the test manifests are not gathered.
+        let use_test_manifest = config.for_test && dep_manifest.include_tests;
+        if use_test_manifest {
             for dep in &dep_manifest.test_manifest {
                 result.merge_from(dep)
             }
@@ -338,45 +559,115 @@ fn merge_manifest(config: &EpilogueConfig) -> Result<Manifest, proc_macro2::Toke
                 result.merge_from(dep)
             }
         }
-        if let Ok(bin_name) = std::env::var("CARGO_BIN_NAME") {
-            let root_manifest = dep_manifest
-                .root_manifests
-                .get(&bin_name)
-                .expect("CARGO_BIN_NAME not in manifest");
-            if config.for_test {
-                result.merge_from(&root_manifest.test_manifest);
-            } else {
-                result.merge_from(&root_manifest.prod_manifest);
+        let crate_name = std::env::var("CARGO_CRATE_NAME").expect("CARGO_CRATE_NAME not set");
+        // cargo also sets CARGO_BIN_NAME for examples, which are not keyed as binaries.
+        let target = match std::env::var("CARGO_BIN_NAME") {
+            Ok(bin) => Some(DepManifests::target_key(&["bin".to_string()], &bin))
+                .filter(|key| find_target(&dep_manifest.root_manifests, key).is_some())
+                .unwrap_or(crate_name),
+            Err(_) => crate_name,
+        };
+        let root_manifest = find_root_manifest(&dep_manifest, &target)?;
+        // examples and benches/tests with `harness = false` are executables without
+        // CARGO_BIN_NAME or cfg(test), but still need to initialize the root components.
+        if let Some(kinds) = find_target(&dep_manifest.root_kinds, &target) {
+            if kinds
+                .iter()
+                .any(|kind| ["bin", "example", "bench", "test"].contains(&kind.as_str()))
+            {
+                config.root = true;
             }
+        }
+        if use_test_manifest {
+            //log!("test manifest: {:#?}", root_manifest.test_manifest);
+            result.merge_from(&root_manifest.test_manifest);
         } else {
-            if config.for_test {
-                let test_target = std::env::var("CARGO_CRATE_NAME").unwrap();
-                let test_manifest = &dep_manifest
-                    .root_manifests
-                    .get(&test_target)
-                    .unwrap()
-                    .test_manifest;
-
-                //log!("test manifest: {:#?}", test_manifest);
-                result.merge_from(&test_manifest);
-            } else {
-                result.merge_from(
-                    &dep_manifest
-                        .root_manifests
-                        .get(&std::env::var("CARGO_CRATE_NAME").unwrap())
-                        .expect("CARGO_CRATE_NAME not in manifest")
-                        .prod_manifest,
-                )
-            }
+            result.merge_from(&root_manifest.prod_manifest);
         }
     } else {
         return Err(
             quote! { compile_error!("manifest missing, is the lockjaw::build_script called in build.rs?");},
         );
     }
+    components::instantiate_generic_components(&mut result);
     Ok(result)
 }
 
+/// Finds the manifest of the target being compiled.
+/// Checks the manifest is written by a build script this processor can read before deserializing
+/// it, since mismatching formats would otherwise only report the missing field.
+fn check_manifest_schema(dep_manifest: &serde_json::Value) -> Result<(), proc_macro2::TokenStream> {
+    let schema_version = dep_manifest
+        .get("schema_version")
+        .and_then(serde_json::Value::as_u64);
+    if schema_version == Some(MANIFEST_SCHEMA_VERSION as u64) {
+        return Ok(());
+    }
+    let lockjaw_version = dep_manifest
+        .get("lockjaw_version")
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or("an older version");
+    let crate_name = current_package();
+    error::compile_error(&format!(
+        "the lockjaw manifest of {} is built with lockjaw {} (manifest schema {}), but lockjaw {} \
+        (manifest schema {}) is processing it. Please align the versions of lockjaw in \
+        [dependencies] and [build-dependencies]",
+        crate_name,
+        lockjaw_version,
+        schema_version.map_or("unknown".to_string(), |version| version.to_string()),
+        LOCKJAW_VERSION,
+        MANIFEST_SCHEMA_VERSION
+    ))
+}
+
+/// Bindings of crates depending on another lockjaw version are parsed by this build script, which
+/// may not understand them.
+fn check_crate_lockjaw_versions(
+    dep_manifest: &DepManifests,
+) -> Result<(), proc_macro2::TokenStream> {
+    let Some(root_version) = dep_manifest
+        .crate_lockjaw_versions
+        .get(&dep_manifest.crate_name)
+    else {
+        return Ok(());
+    };
+    let mut mismatches: Vec<(&String, &String)> = dep_manifest
+        .crate_lockjaw_versions
+        .iter()
+        .filter(|(_, version)| *version != root_version)
+        .collect();
+    mismatches.sort();
+    if let Some((crate_name, version)) = mismatches.first() {
+        return error::compile_error(&format!(
+            "crate {} is built with lockjaw {}, but {} uses lockjaw {}. Please align versions",
+            crate_name, version, dep_manifest.crate_name, root_version
+        ));
+    }
+    Ok(())
+}
+
+fn find_root_manifest<'a>(
+    dep_manifest: &'a DepManifests,
+    target: &str,
+) -> Result<&'a CfgManifest, proc_macro2::TokenStream> {
+    find_target(&dep_manifest.root_manifests, target).map_compile_error(&format!(
+        "target {} not found in the lockjaw manifest of {}",
+        target, dep_manifest.crate_name
+    ))
+}
+
+/// Cargo replaces `-` with `_` in `CARGO_CRATE_NAME`, so target names like `tests/my-test.rs` are
+/// compared normalized.
+fn find_target<'a, T>(map: &'a HashMap<String, T>, target: &str) -> Option<&'a T> {
+    if let Some(value) = map.get(target) {
+        return Some(value);
+    }
+    let normalized = target.replace('-', "_");
+    map.iter()
+        .find(|(name, _)| name.replace('-', "_") == normalized)
+        .map(|(_, value)| value)
+}
+
 fn doc_proc_macro(message: &str) -> TokenStream {
     (quote! { compile_error!(#message)}).into()
 }
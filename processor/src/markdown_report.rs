@@ -0,0 +1,181 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Writes the composition of each component as Markdown with `epilogue!(markdown_report)`, so it
+//! can be included in an mdbook or other documentation.
+//!
+//! The report is built from the merged manifest instead of the resolved graph, so it is still
+//! written when the components are restored from the `codegen_cache`.
+
+use crate::environment;
+use crate::graph;
+use lockjaw_common::environment::current_crate;
+use lockjaw_common::manifest::{Component, ComponentType, Manifest};
+use lockjaw_common::type_data::TypeData;
+use proc_macro2::TokenStream;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// Writes the report for the components generated by the epilogue, and returns the path written.
+pub fn write(manifest: &Manifest, root: bool, for_test: bool) -> Result<PathBuf, TokenStream> {
+    let output_dir = environment::lockjaw_output_dir()?;
+    let suffix = if for_test { "_test" } else { "" };
+    let path = output_dir.join(format!("components_{}{}.md", current_crate(), suffix));
+    std::fs::create_dir_all(&output_dir).expect("cannot create output dir");
+    std::fs::write(&path, generate(manifest, root)).expect(&format!(
+        "cannot write markdown report to {}",
+        path.display()
+    ));
+    Ok(path)
+}
+
+fn generate(manifest: &Manifest, root: bool) -> String {
+    let mut components: Vec<&Component> = manifest
+        .components
+        .iter()
+        .filter(|component| root || !component.definition_only)
+        .collect();
+    components.sort_by_key(|component| component.type_data.readable());
+
+    let mut result = format!("# Components in `{}`\n", current_crate());
+    if components.is_empty() {
+        result.push_str("\n*None*\n");
+    }
+    for component in components {
+        write_component(&mut result, manifest, component);
+    }
+    result
+}
+
+fn write_component(result: &mut String, manifest: &Manifest, component: &Component) {
+    let kind = match component.component_type {
+        ComponentType::Component => "component",
+        ComponentType::Subcomponent => "subcomponent",
+    };
+    let thread_safe = if component.thread_safe {
+        ", thread safe"
+    } else {
+        ""
+    };
+    write!(
+        result,
+        "\n## `{}`\n\n*{}{}*\n",
+        component.type_data.readable(),
+        kind,
+        thread_safe
+    )
+    .unwrap();
+
+    result.push_str("\n### Provisions\n\n");
+    if component.provisions.is_empty() {
+        result.push_str("*None*\n");
+    } else {
+        result.push_str("| Name | Type |\n| --- | --- |\n");
+        for provision in &component.provisions {
+            writeln!(
+                result,
+                "| `{}` | {} |",
+                provision.name,
+                code(&provision.type_data.readable())
+            )
+            .unwrap();
+        }
+    }
+
+    let fragments = graph::collect_fragments(manifest, component).unwrap_or_default();
+    let mut scopes: Vec<&TypeData> = vec![&component.type_data];
+    scopes.extend(&component.scope_groups);
+    scopes.extend(fragments.iter().map(|fragment| &fragment.type_data));
+
+    let mut modules = BTreeSet::<String>::new();
+    modules.extend(component.modules.iter().map(TypeData::readable));
+    for fragment in &fragments {
+        modules.extend(fragment.modules.iter().map(TypeData::readable));
+    }
+    let installed_modules: Vec<_> = manifest
+        .modules
+        .iter()
+        .filter(|module| {
+            modules.contains(&module.type_data.readable())
+                || scopes.iter().any(|scope| module.install_in.contains(scope))
+        })
+        .collect();
+    // modules generated for `#[define_subcomponent(parent: ...)]` are listed as subcomponents.
+    modules.extend(
+        installed_modules
+            .iter()
+            .filter(|module| !module.type_data.path.contains("lockjaw_parent_module_"))
+            .map(|module| module.type_data.readable()),
+    );
+    write_list(result, "Modules", modules.iter().map(|module| code(module)));
+
+    let is_scoped =
+        |type_data: &TypeData| scopes.iter().any(|scope| type_data.scopes.contains(scope));
+    let mut scoped = BTreeSet::<String>::new();
+    scoped.extend(
+        manifest
+            .injectables
+            .iter()
+            .filter(|injectable| is_scoped(&injectable.type_data))
+            .map(|injectable| code(&injectable.type_data.readable())),
+    );
+    for module in &installed_modules {
+        scoped.extend(
+            module
+                .bindings
+                .iter()
+                .filter(|binding| binding.install_scoped || is_scoped(&binding.type_data))
+                .map(|binding| {
+                    format!(
+                        "{} (`{}.{}`)",
+                        code(&binding.type_data.readable()),
+                        module.type_data.readable(),
+                        binding.name
+                    )
+                }),
+        );
+    }
+    write_list(result, "Scoped types", scoped);
+
+    let subcomponents: BTreeSet<String> = installed_modules
+        .iter()
+        .flat_map(|module| module.subcomponents.iter().map(TypeData::readable))
+        .collect();
+    write_list(
+        result,
+        "Subcomponents",
+        subcomponents.iter().map(|subcomponent| code(subcomponent)),
+    );
+}
+
+/// Writes a section listing the already formatted `items`.
+fn write_list(result: &mut String, title: &str, items: impl IntoIterator<Item = String>) {
+    write!(result, "\n### {}\n\n", title).unwrap();
+    let mut empty = true;
+    for item in items {
+        writeln!(result, "* {}", item).unwrap();
+        empty = false;
+    }
+    if empty {
+        result.push_str("*None*\n");
+    }
+}
+
+/// Formats the text as inline code, escaping `|` so it can be used in table cells.
+fn code(text: &str) -> String {
+    format!("`{}`", text.replace('|', "\\|"))
+}
@@ -19,7 +19,7 @@ use std::ops::{Deref, DerefMut};
 
 use lazy_static::lazy_static;
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{quote, ToTokens};
 use syn::parse_quote;
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
@@ -38,6 +38,7 @@ lazy_static! {
         let mut set = HashSet::<String>::new();
         set.insert("subcomponents".to_owned());
         set.insert("install_in".to_owned());
+        set.insert("subcomponent_qualifier".to_owned());
         set
     };
 }
@@ -88,6 +89,21 @@ fn handle_module_attribute_internal(
             type_validator.add_dyn_path(path, span.clone());
         }
     }
+    if let Some(qualifier) = attributes.get("subcomponent_qualifier") {
+        let FieldValue::Path(span, path) = qualifier else {
+            return spanned_compile_error(
+                qualifier.span(),
+                "path expected for subcomponent_qualifier",
+            );
+        };
+        if !attributes.contains_key("subcomponents") {
+            return spanned_compile_error(
+                span.clone(),
+                "subcomponent_qualifier requires subcomponents",
+            );
+        }
+        type_validator.add_qualifier_path(path, span.clone());
+    }
 
     let validate_type = type_validator.validate(parsing::type_string(&item_impl.self_ty)?);
 
@@ -104,22 +120,35 @@ fn parse_binding(
 ) -> Result<(), TokenStream> {
     let mut option_binding: Option<BindingType> = None;
     let mut multibinding = MultibindingType::None;
+    let mut static_dispatch = false;
     let mut new_attrs: Vec<syn::Attribute> = Vec::new();
     for attr in &method.attrs {
         let attr_str = parsing::get_attribute(attr);
         match attr_str.as_str() {
             "provides" => {
                 if option_binding.is_some() {
-                    return spanned_compile_error(attr.span(), "#[module] methods can only be annotated by one of #[provides]/#[binds]/#[binds_option_of]/#[multibinds]");
+                    return spanned_compile_error(attr.span(), "#[module] methods can only be annotated by one of #[provides]/#[binds]/#[binds_option_of]/#[binds_decorator]/#[multibinds]");
                 }
                 handle_provides(attr, &mut method.sig, type_validator)?;
                 option_binding = Some(BindingType::Provides);
             }
             "binds" => {
                 if option_binding.is_some() {
-                    return spanned_compile_error(attr.span(), "#[module] methods can only be annotated by one of #[provides]/#[binds]/#[binds_option_of]/#[multibinds]");
+                    return spanned_compile_error(attr.span(), "#[module] methods can only be annotated by one of #[provides]/#[binds]/#[binds_option_of]/#[binds_decorator]/#[multibinds]");
+                }
+                static_dispatch =
+                    handle_binds(attr, &mut method.sig, &mut method.block, type_validator)?;
+                option_binding = Some(BindingType::Binds);
+                let allow_dead_code: Attribute = parse_quote! {#[allow(dead_code)]};
+                new_attrs.push(allow_dead_code);
+                let allow_unused: Attribute = parse_quote! {#[allow(unused)]};
+                new_attrs.push(allow_unused);
+            }
+            "binds_all" => {
+                if option_binding.is_some() {
+                    return spanned_compile_error(attr.span(), "#[module] methods can only be annotated by one of #[provides]/#[binds]/#[binds_option_of]/#[binds_decorator]/#[multibinds]");
                 }
-                handle_binds(attr, &mut method.sig, &mut method.block, type_validator)?;
+                handle_binds_all(attr, &method.sig, &method.block, type_validator)?;
                 option_binding = Some(BindingType::Binds);
                 let allow_dead_code: Attribute = parse_quote! {#[allow(dead_code)]};
                 new_attrs.push(allow_dead_code);
@@ -128,16 +157,23 @@ fn parse_binding(
             }
             "binds_option_of" => {
                 if option_binding.is_some() {
-                    return spanned_compile_error(attr.span(), "#[module] methods can only be annotated by one of #[provides]/#[binds]/#[binds_option_of]/#[multibinds]");
+                    return spanned_compile_error(attr.span(), "#[module] methods can only be annotated by one of #[provides]/#[binds]/#[binds_option_of]/#[binds_decorator]/#[multibinds]");
                 }
                 handle_binds_option_of(&mut method.sig, &mut method.block)?;
                 option_binding = Some(BindingType::BindsOptionOf);
                 let allow_dead_code: Attribute = parse_quote! {#[allow(dead_code)]};
                 new_attrs.push(allow_dead_code);
             }
+            "binds_decorator" => {
+                if option_binding.is_some() {
+                    return spanned_compile_error(attr.span(), "#[module] methods can only be annotated by one of #[provides]/#[binds]/#[binds_option_of]/#[binds_decorator]/#[multibinds]");
+                }
+                handle_binds_decorator(attr, &method.sig)?;
+                option_binding = Some(BindingType::Decorator);
+            }
             "multibinds" => {
                 if option_binding.is_some() {
-                    return spanned_compile_error(attr.span(), "#[module] methods can only be annotated by one of #[provides]/#[binds]/#[binds_option_of]/#[multibinds]");
+                    return spanned_compile_error(attr.span(), "#[module] methods can only be annotated by one of #[provides]/#[binds]/#[binds_option_of]/#[binds_decorator]/#[multibinds]");
                 }
                 handle_multibinds(&mut method.sig, &mut method.block)?;
                 option_binding = Some(BindingType::Multibinds);
@@ -146,6 +182,18 @@ fn parse_binding(
             }
             "into_vec" => {
                 multibinding = MultibindingType::IntoVec;
+                let fields = get_parenthesized_field_values(&attr.meta)?;
+                for (key, field) in &fields {
+                    if key != "if_present" {
+                        return spanned_compile_error(
+                            field.span(),
+                            &format!("unknown key: {}", key),
+                        );
+                    }
+                    let FieldValue::Path(_, _) = field else {
+                        return spanned_compile_error(field.span(), "path expected for if_present");
+                    };
+                }
             }
             "elements_into_vec" => {
                 multibinding = MultibindingType::ElementsIntoVec;
@@ -175,6 +223,28 @@ fn parse_binding(
                     let FieldValue::Path(_, _) = field else {
                         return spanned_compile_error(attr.span(), "path expected for enum_key");
                     };
+                } else if let Some(field) = fields.get("const_key") {
+                    let FieldValue::Path(_, _) = field else {
+                        return spanned_compile_error(attr.span(), "path expected for const_key");
+                    };
+                    let Some(FieldValue::Path(_, _)) = fields.get("key_type") else {
+                        return spanned_compile_error(
+                            attr.span(),
+                            "key_type path expected for const_key",
+                        );
+                    };
+                } else if let Some(field) = fields.get("key") {
+                    let (FieldValue::Expr(_, _) | FieldValue::Path(_, _)) = field else {
+                        return spanned_compile_error(attr.span(), "expression expected for key");
+                    };
+                    if let Some(key_type) = fields.get("key_type") {
+                        let FieldValue::Path(_, _) = key_type else {
+                            return spanned_compile_error(
+                                key_type.span(),
+                                "path expected for key_type",
+                            );
+                        };
+                    }
                 }
             }
             _ => {
@@ -190,6 +260,12 @@ fn parse_binding(
         );
     }
     let binding = option_binding.unwrap();
+    if binding == BindingType::Decorator && multibinding != MultibindingType::None {
+        return spanned_compile_error(
+            method.span(),
+            "#[binds_decorator] cannot be used with multibindings",
+        );
+    }
     if binding == BindingType::Binds {
         if multibinding == MultibindingType::ElementsIntoVec {
             return spanned_compile_error(
@@ -197,6 +273,12 @@ fn parse_binding(
                 "#[elements_into_set] cannot be used on #[binds]",
             );
         }
+        if static_dispatch && multibinding != MultibindingType::None {
+            return spanned_compile_error(
+                method.span(),
+                "#[binds(static_dispatch)] cannot be used with multibindings",
+            );
+        }
     }
     Ok(())
 }
@@ -224,10 +306,72 @@ fn handle_provides(
         }
     }
     let provides_attr = parsing::get_parenthesized_field_values(&attr.meta)?;
+    // references borrow from the module or its parameters, which already live as long as the
+    // component.
+    if let syn::ReturnType::Type(_, ref type_) = signature.output {
+        if let syn::Type::Reference(ref reference) = type_.deref() {
+            if reference.mutability.is_some() {
+                return spanned_compile_error(
+                    type_.span(),
+                    "#[provides] cannot return &mut, return &T instead",
+                );
+            }
+            if provides_attr.contains_key("scope") {
+                return spanned_compile_error(
+                    type_.span(),
+                    "#[provides] returning a reference cannot be scoped, the referenced value is \
+                    already owned by the module",
+                );
+            }
+        }
+    }
     if let Some(scope) = provides_attr.get("scope") {
-        for (path, span) in scope.get_paths()? {
-            type_validator.add_dyn_path(&path, span);
+        validate_scope(scope, type_validator)?;
+    }
+    Ok(())
+}
+
+/// A decorator is a `#[provides]` method that takes the binding it wraps as one of its parameters.
+fn handle_binds_decorator(
+    attr: &syn::Attribute,
+    signature: &syn::Signature,
+) -> Result<(), TokenStream> {
+    let syn::ReturnType::Type(_, ref return_type) = signature.output else {
+        return spanned_compile_error(signature.span(), "return type expected");
+    };
+    let return_type = return_type.to_token_stream().to_string();
+    let mut inner_count = 0;
+    for args in &signature.inputs {
+        match args {
+            syn::FnArg::Receiver(ref receiver) => {
+                if receiver.reference.is_none() {
+                    return spanned_compile_error(args.span(), "modules should not consume self");
+                }
+            }
+            syn::FnArg::Typed(ref type_) => {
+                let syn::Pat::Ident(_) = type_.pat.deref() else {
+                    return spanned_compile_error(args.span(), "identifier expected");
+                };
+                if type_.ty.to_token_stream().to_string() == return_type {
+                    inner_count += 1;
+                }
+            }
+        }
+    }
+    if inner_count != 1 {
+        return spanned_compile_error(
+            signature.span(),
+            "#[binds_decorator] method must take exactly one parameter of the decorated type",
+        );
+    }
+    let fields = parsing::get_parenthesized_field_values(&attr.meta)?;
+    for (key, field) in &fields {
+        if key != "priority" {
+            return spanned_compile_error(field.span(), &format!("unknown key: {}", key));
         }
+        let FieldValue::IntLiteral(_, _) = field else {
+            return spanned_compile_error(field.span(), "integer literal expected for priority");
+        };
     }
     Ok(())
 }
@@ -237,19 +381,38 @@ fn handle_binds(
     signature: &mut syn::Signature,
     block: &mut syn::Block,
     type_validator: &mut TypeValidator,
-) -> Result<(), TokenStream> {
+) -> Result<bool, TokenStream> {
     if !block.stmts.is_empty() {
         return spanned_compile_error(block.span(), "#[binds] methods must have empty body");
     }
+    let provides_attr = parsing::get_parenthesized_field_values(&attr.meta)?;
+    if let Some(static_dispatch) = provides_attr.get("static_dispatch") {
+        match static_dispatch {
+            FieldValue::Path(..) | FieldValue::BoolLiteral(_, true) => {
+                handle_static_binds(signature, block, type_validator)?;
+                return Ok(true);
+            }
+            FieldValue::BoolLiteral(_, false) => {}
+            _ => {
+                return spanned_compile_error(
+                    static_dispatch.span(),
+                    "static_dispatch expects no value or a bool",
+                )
+            }
+        }
+    }
     let body: syn::Stmt = syn::parse2(quote! { unimplemented!(); }).unwrap();
     block.stmts.push(body);
 
     if let syn::ReturnType::Type(ref _token, ref mut ty) = signature.output {
         if let syn::Type::Path(ref mut type_path) = ty.deref_mut() {
+            let last_segment = type_path.path.segments.last_mut().unwrap();
+            // Arc<dyn Trait> is not bound to the component's lifetime.
+            let is_arc = last_segment.ident == "Arc";
             if let syn::PathArguments::AngleBracketed(ref mut angle_bracketed) =
-                type_path.path.segments.last_mut().unwrap().arguments
+                last_segment.arguments
             {
-                if !has_lifetime(&angle_bracketed.args) {
+                if !is_arc && !has_lifetime(&angle_bracketed.args) {
                     let lifetime: GenericArgument = syn::parse2(quote! {'static}).unwrap();
                     angle_bracketed.args.push(lifetime);
                 }
@@ -278,12 +441,128 @@ fn handle_binds(
             };
         }
     }
-    let provides_attr = parsing::get_parenthesized_field_values(&attr.meta)?;
     if let Some(scope) = provides_attr.get("scope") {
-        for (path, span) in scope.get_paths()? {
-            type_validator.add_dyn_path(&path, span);
+        validate_scope(scope, type_validator)?;
+    }
+    Ok(false)
+}
+
+/// Validates `scope: path`, `scope: [path, ...]` or `scope: install`, which scopes the binding to
+/// whichever component the module is installed in.
+fn validate_scope(
+    scope: &FieldValue,
+    type_validator: &mut TypeValidator,
+) -> Result<(), TokenStream> {
+    let paths = scope.get_paths()?;
+    if paths.iter().any(|(path, _)| path.is_ident("install")) {
+        if paths.len() > 1 {
+            return spanned_compile_error(
+                scope.span(),
+                "`scope: install` cannot be combined with other scopes",
+            );
         }
+        return Ok(());
+    }
+    for (path, span) in paths {
+        type_validator.add_dyn_path(&path, span);
+    }
+    Ok(())
+}
+
+/// `#[binds(static_dispatch)] fn bind(impl_: T) -> impl Trait {}` is kept as
+/// `fn bind(impl_: T) -> T { impl_ }`, with `T: Trait` checked by the type validator. The
+/// implementation is returned as is, so it can also be `&T` of a scoped type.
+fn handle_static_binds(
+    signature: &mut syn::Signature,
+    block: &mut syn::Block,
+    type_validator: &mut TypeValidator,
+) -> Result<(), TokenStream> {
+    let syn::ReturnType::Type(_, ref ty) = signature.output else {
+        return spanned_compile_error(signature.span(), "return type expected");
+    };
+    let syn::Type::ImplTrait(ref impl_trait) = ty.deref() else {
+        return spanned_compile_error(
+            ty.span(),
+            "#[binds(static_dispatch)] methods must return impl Trait",
+        );
+    };
+    let impl_trait = impl_trait.clone();
+    if signature.inputs.len() != 1 {
+        return spanned_compile_error(
+            signature.span(),
+            "binds method must only take the binding type as parameter",
+        );
     }
+    let syn::FnArg::Typed(ref arg) = signature.inputs.first().unwrap() else {
+        return spanned_compile_error(
+            signature.inputs.span(),
+            "binds method must only take the binding type as parameter",
+        );
+    };
+    let syn::Pat::Ident(ref pat_ident) = arg.pat.deref() else {
+        return spanned_compile_error(arg.span(), "identifier expected");
+    };
+    let arg_ident = pat_ident.ident.clone();
+    let arg_type = arg.ty.deref().clone();
+    let implementation = if let syn::Type::Reference(ref reference) = arg_type {
+        reference.elem.deref().clone()
+    } else {
+        arg_type.clone()
+    };
+    type_validator.add_impl_bounds(&implementation, &impl_trait.bounds, impl_trait.span());
+
+    let body: syn::Stmt = syn::parse2(quote! { return #arg_ident; }).unwrap();
+    block.stmts.push(body);
+    signature.output = syn::parse2(quote! { -> #arg_type }).unwrap();
+    Ok(())
+}
+
+fn handle_binds_all(
+    attr: &syn::Attribute,
+    signature: &syn::Signature,
+    block: &syn::Block,
+    type_validator: &mut TypeValidator,
+) -> Result<(), TokenStream> {
+    if !block.stmts.is_empty() {
+        return spanned_compile_error(block.span(), "#[binds_all] methods must have empty body");
+    }
+    if let syn::ReturnType::Type(_, _) = signature.output {
+        return spanned_compile_error(
+            signature.output.span(),
+            "#[binds_all] methods should not have a return type, the traits are listed in the attribute",
+        );
+    }
+    let paths = attr
+        .parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+        .map_spanned_compile_error(attr.span(), "trait paths expected for #[binds_all]")?;
+    if paths.is_empty() {
+        return spanned_compile_error(attr.span(), "#[binds_all] requires at least one trait");
+    }
+    for path in &paths {
+        type_validator.add_dyn_path(path, path.span());
+    }
+    if signature.inputs.len() != 1 {
+        return spanned_compile_error(
+            signature.span(),
+            "binds_all method must only take the binding type as parameter",
+        );
+    }
+    let args = signature.inputs.first().unwrap();
+    let syn::FnArg::Typed(ref type_) = args else {
+        return spanned_compile_error(
+            args.span(),
+            "binds_all method must only take the binding type as parameter",
+        );
+    };
+    let syn::Pat::Ident(_) = type_.pat.deref() else {
+        return spanned_compile_error(args.span(), "identifier expected");
+    };
+    let syn::Type::Reference(_) = type_.ty.deref() else {
+        return spanned_compile_error(
+            type_.ty.span(),
+            "#[binds_all] must take a reference to a scoped binding, so all traits share the same instance",
+        );
+    };
     Ok(())
 }
 
@@ -302,10 +581,13 @@ fn handle_binds_option_of(
 
     if let syn::ReturnType::Type(ref _token, ref mut ty) = signature.output {
         if let syn::Type::Path(ref mut type_path) = ty.deref_mut() {
+            let last_segment = type_path.path.segments.last_mut().unwrap();
+            // Arc<dyn Trait> is not bound to the component's lifetime.
+            let is_arc = last_segment.ident == "Arc";
             if let syn::PathArguments::AngleBracketed(ref mut angle_bracketed) =
-                type_path.path.segments.last_mut().unwrap().arguments
+                last_segment.arguments
             {
-                if !has_lifetime(&angle_bracketed.args) {
+                if !is_arc && !has_lifetime(&angle_bracketed.args) {
                     let lifetime: GenericArgument = syn::parse2(quote! {'static}).unwrap();
                     angle_bracketed.args.push(lifetime);
                 }
@@ -0,0 +1,174 @@
+/*
+Copyright 2020 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::component_visibles;
+use crate::error::compile_error;
+use crate::graph::ComponentSections;
+use crate::graph::Graph;
+use crate::nodes::map::MapNode;
+use crate::nodes::node;
+use crate::nodes::node::{DependencyData, ModuleInstance, Node};
+use crate::nodes::vec::VecNode;
+use crate::type_data::ProcessorTypeData;
+use lockjaw_common::manifest::{Binding, BuilderModules, MultibindingType};
+use lockjaw_common::type_data::TypeData;
+use std::any::Any;
+use std::collections::HashSet;
+
+/// `#[binds] fn bind(impl_: T) -> Arc<dyn Trait> {}`, shares the implementation as an
+/// `Arc<dyn Trait>` that is not bound to the component's lifetime. A scoped binding caches the
+/// `Arc` in the component and hands out clones.
+#[derive(Debug, Clone)]
+pub struct ArcBindsNode {
+    pub type_: TypeData,
+    pub dependency: TypeData,
+
+    pub module_instance: ModuleInstance,
+    pub binding: Binding,
+}
+
+impl ArcBindsNode {
+    pub fn is_arc_binds(binding: &Binding) -> bool {
+        binding.type_data.path == "std::sync::Arc"
+    }
+
+    pub fn new(
+        module_manifest: &BuilderModules,
+        module_type: &TypeData,
+        binding: &Binding,
+    ) -> Result<Vec<Box<dyn Node>>, TokenStream> {
+        let dependency = binding
+            .dependencies
+            .first()
+            .expect("binds must have one arg")
+            .type_data
+            .clone();
+        if dependency.field_ref {
+            return compile_error(&format!(
+                "{} cannot be bound from the reference {}, as it must own the implementation. \
+                Bind T or Arc<T> instead",
+                binding.type_data.readable(),
+                dependency.readable()
+            ));
+        }
+        // The cached Arc is cloned out of the component, so the binding itself can be requested by
+        // value.
+        let mut type_ = binding.type_data.clone();
+        type_.scopes = HashSet::new();
+        if binding.multibinding_type != MultibindingType::None {
+            type_.identifier_suffix = format!("{}", node::get_multibinding_id());
+        }
+
+        let mut result: Vec<Box<dyn Node>> = vec![Box::new(ArcBindsNode {
+            type_: type_.clone(),
+            dependency,
+            module_instance: <dyn Node>::get_module_instance(module_manifest, module_type),
+            binding: binding.clone(),
+        })];
+        match binding.multibinding_type {
+            MultibindingType::IntoVec => {
+                let mut vec_node = VecNode::new(&type_);
+                vec_node.add_conditional_binding(
+                    &type_,
+                    &binding.multibinding_type,
+                    &binding.if_present,
+                );
+                result.push(vec_node);
+            }
+            MultibindingType::ElementsIntoVec => {
+                panic!("unexpected #[elements_into_vec] for #[binds]")
+            }
+            MultibindingType::IntoMap => {
+                let mut value_type = type_.clone();
+                value_type.qualifier = binding.type_data.qualifier.clone();
+                let mut map_node = MapNode::new(&binding.map_key, &value_type)?;
+                map_node.add_binding(&binding.map_key, &type_);
+                result.push(map_node);
+            }
+            _ => {}
+        }
+        Ok(result)
+    }
+}
+
+impl Node for ArcBindsNode {
+    fn get_name(&self) -> String {
+        format!(
+            "{}.{} (module binds)",
+            self.module_instance.type_.canonical_string_path(),
+            self.binding.name
+        )
+    }
+
+    fn generate_implementation(&self, graph: &Graph) -> Result<ComponentSections, TokenStream> {
+        let arg_provider_name = self.dependency.identifier();
+
+        let name_ident = self.get_identifier();
+        let type_path = component_visibles::visible_type(graph.manifest, &self.type_).syn_type();
+        let arc = if self.dependency.path == "std::sync::Arc" {
+            quote! {self.#arg_provider_name()}
+        } else {
+            quote! {::lockjaw::__private::std::sync::Arc::new(self.#arg_provider_name())}
+        };
+
+        let mut result = ComponentSections::new();
+        if self.binding.type_data.scopes.is_empty() {
+            result.add_methods(quote! {
+                fn #name_ident(&'_ self) -> #type_path{
+                    #arc
+                }
+            });
+        } else {
+            let once_name = format_ident!("once_{}", self.type_.identifier());
+            result.add_fields(quote! {
+                #once_name : lockjaw::Once<#type_path>,
+            });
+            result.add_ctor_params(quote! {#once_name : lockjaw::Once::new(),});
+            result.add_methods(quote! {
+                fn #name_ident(&'_ self) -> #type_path{
+                    ::lockjaw::__private::std::sync::Arc::clone(self.#once_name.get_or_init(|| #arc))
+                }
+            });
+        }
+        Ok(result)
+    }
+
+    fn get_type(&self) -> &TypeData {
+        &self.type_
+    }
+
+    fn get_origin(&self) -> Option<&TypeData> {
+        Some(&self.module_instance.type_)
+    }
+
+    fn get_dependencies(&self) -> Vec<DependencyData> {
+        vec![DependencyData::from_type(&self.dependency)]
+    }
+
+    fn clone_box(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
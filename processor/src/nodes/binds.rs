@@ -24,6 +24,7 @@ use crate::nodes::component_lifetime::ComponentLifetimeNode;
 use crate::nodes::map::MapNode;
 use crate::nodes::node;
 use crate::nodes::node::{DependencyData, ModuleInstance, Node};
+use crate::nodes::scoped;
 use crate::nodes::vec::VecNode;
 use crate::type_data::ProcessorTypeData;
 use lockjaw_common::manifest::{Binding, BuilderModules, MultibindingType};
@@ -45,7 +46,11 @@ impl BindsNode {
         module_type: &TypeData,
         binding: &Binding,
     ) -> Result<Vec<Box<dyn Node>>, TokenStream> {
-        let mut type_ = ComponentLifetimeNode::component_lifetime_type(&binding.type_data);
+        let mut type_ = if binding.static_dispatch {
+            binding.type_data.clone()
+        } else {
+            ComponentLifetimeNode::component_lifetime_type(&binding.type_data)
+        };
         if binding.multibinding_type != MultibindingType::None {
             type_.identifier_suffix = format!("{}", node::get_multibinding_id());
         }
@@ -64,14 +69,20 @@ impl BindsNode {
         match binding.multibinding_type {
             MultibindingType::IntoVec => {
                 let mut vec_node = VecNode::new(&type_);
-                vec_node.add_binding(&type_, &binding.multibinding_type);
+                vec_node.add_conditional_binding(
+                    &type_,
+                    &binding.multibinding_type,
+                    &binding.if_present,
+                );
                 result.push(vec_node);
             }
             MultibindingType::ElementsIntoVec => {
                 panic!("unexpected #[elements_into_vec] for #[binds]")
             }
             MultibindingType::IntoMap => {
-                let mut map_node = MapNode::new(&binding.map_key, &binding.type_data)?;
+                let mut value_type = type_.clone();
+                value_type.qualifier = binding.type_data.qualifier.clone();
+                let mut map_node = MapNode::new(&binding.map_key, &value_type)?;
                 map_node.add_binding(&binding.map_key, &type_);
                 result.push(map_node);
             }
@@ -97,7 +108,22 @@ impl Node for BindsNode {
         let type_path = component_visibles::visible_type(graph.manifest, &self.type_).syn_type();
 
         let mut result = ComponentSections::new();
-        if self.dependency.field_ref {
+        if self.binding.static_dispatch {
+            let dependency_path =
+                component_visibles::visible_type(graph.manifest, &self.dependency).syn_type();
+            result.add_methods(quote! {
+                fn #name_ident(&'_ self) -> #dependency_path{
+                    self.#arg_provider_name()
+                }
+            });
+        } else if scoped::is_arc_backed(graph, &self.dependency) {
+            let arc_ident = scoped::arc_ident(&self.dependency);
+            result.add_methods(quote! {
+                fn #name_ident(&'_ self) -> #type_path{
+                    lockjaw::Cl::Shared(self.#arc_ident())
+                }
+            });
+        } else if self.dependency.field_ref {
             result.add_methods(quote! {
                 fn #name_ident(&'_ self) -> #type_path{
                     lockjaw::Cl::Ref(self.#arg_provider_name())
@@ -106,7 +132,7 @@ impl Node for BindsNode {
         } else {
             result.add_methods(quote! {
                 fn #name_ident(&'_ self) -> #type_path{
-                    lockjaw::Cl::Val(Box::new(self.#arg_provider_name()))
+                    lockjaw::Cl::Val(::lockjaw::__private::std::boxed::Box::new(self.#arg_provider_name()))
                 }
             });
         }
@@ -117,6 +143,10 @@ impl Node for BindsNode {
         &self.type_
     }
 
+    fn get_origin(&self) -> Option<&TypeData> {
+        Some(&self.module_instance.type_)
+    }
+
     fn get_dependencies(&self) -> Vec<DependencyData> {
         vec![DependencyData::from_type(&self.dependency)]
     }
@@ -42,11 +42,15 @@ impl BindsOptionOfNode {
         })]
     }
 
+    /// `Option<T>` for the optional type. The qualifier of `T` is moved to the `Option`, since
+    /// `#[qualified(Q)] Option<T>` is how the optional binding is requested.
     pub fn option_type(type_: &TypeData) -> TypeData {
         let mut option_type = TypeData::new();
         option_type.root = TypeRoot::GLOBAL;
         option_type.path = "std::option::Option".to_string();
-        option_type.args.push(type_.clone());
+        let mut arg = type_.clone();
+        option_type.qualifier = arg.qualifier.take();
+        option_type.args.push(arg);
         option_type
     }
 }
@@ -16,13 +16,16 @@ limitations under the License.
 use crate::component_visibles;
 use crate::graph::ComponentSections;
 use crate::graph::Graph;
+use crate::nodes::binds::BindsNode;
+use crate::nodes::component_lifetime::ComponentLifetimeNode;
 use crate::nodes::node::{DependencyData, Node};
 use crate::type_data::ProcessorTypeData;
 use lockjaw_common::manifest::TypeRoot;
 use lockjaw_common::type_data::TypeData;
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 use std::any::Any;
+use std::collections::HashMap;
 
 #[derive(Debug)]
 pub struct BoxedNode {
@@ -33,16 +36,38 @@ pub struct BoxedNode {
 }
 
 impl BoxedNode {
-    pub fn for_type(type_: &TypeData) -> Option<Box<dyn Node>> {
+    pub fn for_type(
+        map: &HashMap<Ident, Box<dyn Node>>,
+        type_: &TypeData,
+    ) -> Option<Box<dyn Node>> {
         let inner = type_.args.get(0).unwrap();
+        let dependency = if inner.trait_object {
+            BoxedNode::bound_implementation(map, inner).unwrap_or(inner.clone())
+        } else {
+            inner.clone()
+        };
         Some(Box::new(BoxedNode {
             type_: BoxedNode::boxed_type(&inner),
-            dependencies: vec![inner.clone()],
+            dependencies: vec![dependency.clone()],
 
-            inner: inner.clone(),
+            inner: dependency,
         }))
     }
 
+    /// The implementation bound to `Cl<dyn T>` by an unscoped `#[binds]`, which can be boxed as
+    /// `Box<dyn T>` directly since the component does not own it.
+    fn bound_implementation(
+        map: &HashMap<Ident, Box<dyn Node>>,
+        trait_: &TypeData,
+    ) -> Option<TypeData> {
+        let node = map.get(&ComponentLifetimeNode::component_lifetime_type(trait_).identifier())?;
+        let binds_node = node.as_any().downcast_ref::<BindsNode>()?;
+        if binds_node.binding.static_dispatch || binds_node.dependency.field_ref {
+            return None;
+        }
+        Some(binds_node.dependency.clone())
+    }
+
     pub fn boxed_type(type_: &TypeData) -> TypeData {
         let mut boxed_type = TypeData::new();
         boxed_type.root = TypeRoot::GLOBAL;
@@ -75,7 +100,7 @@ impl Node for BoxedNode {
         let mut result = ComponentSections::new();
         result.add_methods(quote! {
             fn #name_ident(&self) -> #type_path{
-                std::boxed::Box::new(self.#arg_provider_name())
+                ::lockjaw::__private::std::boxed::Box::new(self.#arg_provider_name())
             }
         });
 
@@ -0,0 +1,118 @@
+/*
+Copyright 2020 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use crate::component_visibles;
+use crate::error::compile_error;
+use crate::graph::{ComponentSections, Graph};
+use crate::manifest::ProcessorComponent;
+use crate::nodes::node::Node;
+use crate::type_data::ProcessorTypeData;
+use lockjaw_common::manifest::{ComponentType, TypeRoot};
+use lockjaw_common::type_data::TypeData;
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::any::Any;
+
+/// Provides the component itself as `&dyn Any`, which can be recovered with
+/// `lockjaw::downcast_component()`.
+#[derive(Debug, Clone)]
+pub struct ComponentAnyNode {
+    pub type_: TypeData,
+}
+
+impl ComponentAnyNode {
+    pub fn for_type(type_: &TypeData) -> Option<Box<dyn Node>> {
+        if !ComponentAnyNode::is_any_ref(type_) {
+            return None;
+        }
+        Some(Box::new(ComponentAnyNode {
+            type_: type_.clone(),
+        }))
+    }
+
+    pub fn is_any_ref(type_: &TypeData) -> bool {
+        type_.field_ref
+            && type_.trait_object
+            && type_.root == TypeRoot::GLOBAL
+            && type_.path == "std::any::Any"
+            && type_.qualifier.is_none()
+    }
+}
+
+impl Node for ComponentAnyNode {
+    fn get_name(&self) -> String {
+        "&dyn Any (component)".to_owned()
+    }
+
+    fn generate_implementation(&self, graph: &Graph) -> Result<ComponentSections, TokenStream> {
+        if graph.component.component_type != ComponentType::Component {
+            return compile_error(&format!(
+                "&dyn Any cannot be requested in subcomponent {}",
+                graph.component.type_data.readable()
+            ));
+        }
+        let name_ident = self.get_identifier();
+        let component_impl_name = graph.component.impl_ident();
+        let component_type =
+            component_visibles::visible_type(graph.manifest, &graph.component.type_data).syn_type();
+
+        let mut casts = quote! {
+            lockjaw::register_component_cast::<dyn #component_type>(
+                ::lockjaw::__private::std::any::TypeId::of::<#component_impl_name>(),
+                |any| any.downcast_ref::<#component_impl_name>().unwrap(),
+            );
+        };
+        for entry_point in &graph.manifest.entry_points {
+            if entry_point.component.canonical_string_path()
+                != graph.component.type_data.canonical_string_path()
+            {
+                continue;
+            }
+            let entry_point_type =
+                component_visibles::visible_type(graph.manifest, &entry_point.type_data).syn_type();
+            casts = quote! {
+                #casts
+                lockjaw::register_component_cast::<dyn #entry_point_type>(
+                    ::lockjaw::__private::std::any::TypeId::of::<#component_impl_name>(),
+                    |any| any.downcast_ref::<#component_impl_name>().unwrap(),
+                );
+            };
+        }
+
+        let mut result = ComponentSections::new();
+        result.add_methods(quote! {
+            fn #name_ident(&'_ self) -> &'_ dyn ::lockjaw::__private::std::any::Any {
+                self
+            }
+        });
+        result.add_ctor_statements(casts);
+        Ok(result)
+    }
+
+    fn get_type(&self) -> &TypeData {
+        &self.type_
+    }
+
+    fn clone_box(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
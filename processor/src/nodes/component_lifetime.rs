@@ -17,6 +17,7 @@ use crate::component_visibles;
 use crate::graph::ComponentSections;
 use crate::graph::Graph;
 use crate::nodes::node::{DependencyData, Node};
+use crate::nodes::scoped;
 use crate::type_data::ProcessorTypeData;
 use lockjaw_common::manifest::TypeRoot;
 use lockjaw_common::type_data::TypeData;
@@ -85,7 +86,14 @@ impl Node for ComponentLifetimeNode {
         let type_path = component_visibles::visible_type(graph.manifest, &self.type_).syn_type();
 
         let mut result = ComponentSections::new();
-        if self.inner.field_ref {
+        if scoped::is_arc_backed(graph, &self.inner) {
+            let arc_ident = scoped::arc_ident(&self.inner);
+            result.add_methods(quote! {
+                fn #name_ident(&'_ self) -> #type_path{
+                    lockjaw::Cl::Shared(self.#arc_ident())
+                }
+            });
+        } else if self.inner.field_ref {
             result.add_methods(quote! {
                 fn #name_ident(&'_ self) -> #type_path{
                     lockjaw::Cl::Ref(self.#arg_provider_name())
@@ -94,7 +102,7 @@ impl Node for ComponentLifetimeNode {
         } else {
             result.add_methods(quote! {
                 fn #name_ident(&'_ self) -> #type_path{
-                    lockjaw::Cl::Val(Box::new(self.#arg_provider_name()))
+                    lockjaw::Cl::Val(::lockjaw::__private::std::boxed::Box::new(self.#arg_provider_name()))
                 }
             });
         }
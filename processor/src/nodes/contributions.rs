@@ -0,0 +1,98 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use crate::component_visibles;
+use crate::graph::{ComponentSections, Graph};
+use crate::nodes::node::{DependencyData, Node};
+use crate::type_data::ProcessorTypeData;
+use lockjaw_common::manifest::TypeRoot;
+use lockjaw_common::type_data::TypeData;
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::any::Any;
+
+/// Lends out a component-cached `Vec<T>` multibinding as `lockjaw::Contributions<T>`.
+#[derive(Debug, Clone)]
+pub struct ContributionsNode {
+    pub type_: TypeData,
+    pub inner: TypeData,
+    pub target: TypeData,
+}
+
+impl ContributionsNode {
+    pub fn for_type(type_: &TypeData) -> Option<Box<dyn Node>> {
+        let inner = type_.args.get(0)?;
+        let mut vec_ref = TypeData::new();
+        vec_ref.root = TypeRoot::GLOBAL;
+        vec_ref.path = "std::vec::Vec".to_string();
+        vec_ref.args.push(inner.clone());
+        vec_ref.qualifier = type_.qualifier.clone();
+        vec_ref.field_ref = true;
+        let mut contributions_type = ContributionsNode::contributions_type(inner);
+        contributions_type.qualifier = type_.qualifier.clone();
+        Some(Box::new(ContributionsNode {
+            type_: contributions_type,
+            inner: inner.clone(),
+            target: vec_ref,
+        }))
+    }
+
+    pub fn contributions_type(type_: &TypeData) -> TypeData {
+        let mut contributions_type = TypeData::new();
+        contributions_type.root = TypeRoot::GLOBAL;
+        contributions_type.path = "lockjaw::Contributions".to_string();
+        contributions_type.args.push(type_.clone());
+        contributions_type
+    }
+}
+
+impl Node for ContributionsNode {
+    fn get_name(&self) -> String {
+        format!("Contributions<{}>", self.inner.readable())
+    }
+
+    fn generate_implementation(&self, graph: &Graph) -> Result<ComponentSections, TokenStream> {
+        let name_ident = self.get_identifier();
+        let vec_provider_name = self.target.identifier();
+        let inner_type = component_visibles::visible_type(graph.manifest, &self.inner).syn_type();
+
+        let mut result = ComponentSections::new();
+        result.add_methods(quote! {
+            fn #name_ident(&'_ self) -> lockjaw::Contributions<'_, #inner_type> {
+                lockjaw::Contributions::new(self.#vec_provider_name())
+            }
+        });
+        Ok(result)
+    }
+
+    fn get_type(&self) -> &TypeData {
+        &self.type_
+    }
+
+    fn get_dependencies(&self) -> Vec<DependencyData> {
+        vec![DependencyData::from_type(&self.target)]
+    }
+
+    fn clone_box(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,82 @@
+/*
+Copyright 2020 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use crate::component_visibles;
+use crate::graph::ComponentSections;
+use crate::graph::Graph;
+use crate::nodes::node::Node;
+use crate::type_data::ProcessorTypeData;
+use lockjaw_common::type_data::TypeData;
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::any::Any;
+
+/// Provides a zero-sized type with its `Default` implementation, for components with
+/// `auto_default_zst` enabled.
+#[derive(Debug, Clone)]
+pub struct DefaultZstNode {
+    pub type_: TypeData,
+}
+
+impl DefaultZstNode {
+    pub fn for_type(type_: &TypeData) -> Option<Box<dyn Node>> {
+        if type_.field_ref || type_.trait_object {
+            return None;
+        }
+        Some(Box::new(DefaultZstNode {
+            type_: type_.clone(),
+        }))
+    }
+}
+
+impl Node for DefaultZstNode {
+    fn get_name(&self) -> String {
+        format!("{} (auto default zst)", self.type_.canonical_string_path())
+    }
+
+    fn generate_implementation(&self, graph: &Graph) -> Result<ComponentSections, TokenStream> {
+        let name_ident = self.get_identifier();
+        let type_path = component_visibles::visible_type(graph.manifest, &self.type_).syn_type();
+        let message = format!(
+            "{} is not bound, and cannot be auto provided by `auto_default_zst` since it is not zero-sized",
+            self.type_.readable()
+        );
+
+        let mut result = ComponentSections::new();
+        result.add_methods(quote! {
+            fn #name_ident(&self) -> #type_path{
+                const _: () = assert!(::lockjaw::__private::std::mem::size_of::<#type_path>() == 0, #message);
+                <#type_path as ::lockjaw::__private::std::default::Default>::default()
+            }
+        });
+
+        Ok(result)
+    }
+
+    fn get_type(&self) -> &TypeData {
+        &self.type_
+    }
+
+    fn clone_box(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
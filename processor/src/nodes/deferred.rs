@@ -0,0 +1,93 @@
+/*
+Copyright 2020 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use crate::component_visibles;
+use crate::error::compile_error;
+use crate::graph::{ComponentSections, Graph};
+use crate::nodes::node::Node;
+use crate::type_data::ProcessorTypeData;
+use lockjaw_common::manifest::{ComponentType, TypeRoot};
+use lockjaw_common::type_data::TypeData;
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::any::Any;
+
+/// Collects `lockjaw::Deferred<T>` contributions registered at runtime.
+#[derive(Debug, Clone)]
+pub struct DeferredNode {
+    pub type_: TypeData,
+    pub inner: TypeData,
+}
+
+impl DeferredNode {
+    pub fn for_type(type_: &TypeData) -> Option<Box<dyn Node>> {
+        let inner = type_.args.get(0)?;
+        Some(Box::new(DeferredNode {
+            type_: DeferredNode::deferred_type(inner),
+            inner: inner.clone(),
+        }))
+    }
+
+    pub fn deferred_type(type_: &TypeData) -> TypeData {
+        let mut deferred_type = TypeData::new();
+        deferred_type.root = TypeRoot::GLOBAL;
+        deferred_type.path = "lockjaw::Deferred".to_string();
+        deferred_type.args.push(type_.clone());
+        deferred_type
+    }
+}
+
+impl Node for DeferredNode {
+    fn get_name(&self) -> String {
+        format!("Deferred<{}>", self.inner.readable())
+    }
+
+    fn generate_implementation(&self, graph: &Graph) -> Result<ComponentSections, TokenStream> {
+        if graph.component.component_type != ComponentType::Component {
+            return compile_error(&format!(
+                "{} cannot be requested in subcomponent {}",
+                self.get_name(),
+                graph.component.type_data.readable()
+            ));
+        }
+        let name_ident = self.get_identifier();
+        let inner_type = component_visibles::visible_type(graph.manifest, &self.inner).syn_type();
+        let component_type =
+            component_visibles::visible_type(graph.manifest, &graph.component.type_data).syn_type();
+
+        let mut result = ComponentSections::new();
+        result.add_methods(quote! {
+            fn #name_ident(&'_ self) -> lockjaw::Deferred<#inner_type> {
+                lockjaw::collect_deferred::<dyn #component_type, #inner_type>(self)
+            }
+        });
+        Ok(result)
+    }
+
+    fn get_type(&self) -> &TypeData {
+        &self.type_
+    }
+
+    fn clone_box(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
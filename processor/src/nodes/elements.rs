@@ -0,0 +1,132 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use crate::component_visibles;
+use crate::error::compile_error;
+use crate::graph::{ComponentSections, Graph};
+use crate::nodes::node::{DependencyData, Node};
+use crate::nodes::vec::VecNode;
+use crate::type_data::ProcessorTypeData;
+use lockjaw_common::manifest::{MultibindingType, TypeRoot};
+use lockjaw_common::type_data::TypeData;
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::any::Any;
+
+/// Lazily iterates the contributions of a `Vec<T>` multibinding as `lockjaw::Elements<T>`.
+#[derive(Debug, Clone)]
+pub struct ElementsNode {
+    pub type_: TypeData,
+    pub inner: TypeData,
+    pub target: TypeData,
+}
+
+impl ElementsNode {
+    pub fn for_type(type_: &TypeData) -> Option<Box<dyn Node>> {
+        let inner = type_.args.get(0)?;
+        let mut vec_type = TypeData::new();
+        vec_type.root = TypeRoot::GLOBAL;
+        vec_type.path = "std::vec::Vec".to_string();
+        vec_type.args.push(inner.clone());
+        vec_type.qualifier = type_.qualifier.clone();
+        let mut elements_type = ElementsNode::elements_type(inner);
+        elements_type.qualifier = type_.qualifier.clone();
+        Some(Box::new(ElementsNode {
+            type_: elements_type,
+            inner: inner.clone(),
+            target: vec_type,
+        }))
+    }
+
+    pub fn elements_type(type_: &TypeData) -> TypeData {
+        let mut elements_type = TypeData::new();
+        elements_type.root = TypeRoot::GLOBAL;
+        elements_type.path = "lockjaw::Elements".to_string();
+        elements_type.args.push(type_.clone());
+        elements_type
+    }
+}
+
+impl Node for ElementsNode {
+    fn get_name(&self) -> String {
+        format!("Elements<{}>", self.inner.readable())
+    }
+
+    fn generate_implementation(&self, graph: &Graph) -> Result<ComponentSections, TokenStream> {
+        let name_ident = self.get_identifier();
+        let inner_type = component_visibles::visible_type(graph.manifest, &self.inner).syn_type();
+        let Some(vec_node) = graph
+            .map
+            .get(&self.target.identifier())
+            .and_then(|node| node.as_any().downcast_ref::<VecNode>())
+        else {
+            return compile_error(&format!(
+                "{} is not a multibinding, and cannot be iterated lazily as Elements<{}>",
+                self.target.readable(),
+                self.inner.readable()
+            ));
+        };
+        let mut arms = quote! {};
+        let mut len = 0usize;
+        for binding in &vec_node.bindings {
+            let ident = binding.type_data.identifier();
+            let contribution = match binding.multibinding_type {
+                MultibindingType::IntoVec => {
+                    quote! {lockjaw::ElementsContribution::Element(self.#ident())}
+                }
+                MultibindingType::ElementsIntoVec => {
+                    quote! {lockjaw::ElementsContribution::Elements(self.#ident())}
+                }
+                _ => continue,
+            };
+            arms = quote! {
+                #arms
+                #len => #contribution,
+            };
+            len += 1;
+        }
+
+        let mut result = ComponentSections::new();
+        result.add_methods(quote! {
+            fn #name_ident(&'_ self) -> lockjaw::Elements<'_, #inner_type> {
+                lockjaw::Elements::new(#len, move |index| match index {
+                    #arms
+                    _ => unreachable!(),
+                })
+            }
+        });
+        Ok(result)
+    }
+
+    fn get_type(&self) -> &TypeData {
+        &self.type_
+    }
+
+    fn get_dependencies(&self) -> Vec<DependencyData> {
+        vec![DependencyData::from_type(&self.target)]
+    }
+
+    fn clone_box(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
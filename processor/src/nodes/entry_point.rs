@@ -21,7 +21,7 @@ use crate::graph::{ComponentSections, Graph};
 use crate::manifest::ProcessorComponent;
 use crate::nodes::node::{DependencyData, Node};
 use crate::type_data::ProcessorTypeData;
-use crate::{component_visibles, entrypoints};
+use crate::{component_visibles, components, entrypoints};
 use lockjaw_common::manifest::{ComponentType, EntryPoint};
 use lockjaw_common::type_data::TypeData;
 use std::any::Any;
@@ -29,6 +29,7 @@ use std::any::Any;
 #[derive(Debug, Clone)]
 pub struct EntryPointNode {
     dependencies: Vec<TypeData>,
+    optional_dependencies: Vec<TypeData>,
     entry_point: EntryPoint,
 }
 
@@ -38,6 +39,13 @@ impl EntryPointNode {
             dependencies: entry_point
                 .provisions
                 .iter()
+                .filter(|dep| !dep.optional)
+                .map(|dep| dep.type_data.clone())
+                .collect(),
+            optional_dependencies: entry_point
+                .provisions
+                .iter()
+                .filter(|dep| dep.optional)
                 .map(|dep| dep.type_data.clone())
                 .collect(),
             entry_point: entry_point.clone(),
@@ -62,11 +70,32 @@ impl Node for EntryPointNode {
             let dependency_path =
                 component_visibles::visible_type(graph.manifest, &provision.type_data).syn_type();
             let provider_name = provision.type_data.identifier();
+            let doc = graph.doc_attributes(&provision.type_data);
+            let (return_type, body) = if !provision.optional {
+                (quote! {#dependency_path}, quote! { self.#provider_name() })
+            } else if provision.has_default {
+                if !graph.has_node(&provision.type_data) {
+                    // fall back to the default implementation in the trait.
+                    continue;
+                }
+                (quote! {#dependency_path}, quote! { self.#provider_name() })
+            } else if graph.has_node(&provision.type_data) {
+                (
+                    quote! {::std::option::Option<#dependency_path>},
+                    quote! { ::std::option::Option::Some(self.#provider_name()) },
+                )
+            } else {
+                (
+                    quote! {::std::option::Option<#dependency_path>},
+                    quote! { ::std::option::Option::None },
+                )
+            };
             provisions = quote! {
                 #provisions
-               fn #dependency_name(&self) -> #dependency_path {
-                  self.#provider_name()
-               }
+                #doc
+                fn #dependency_name(&self) -> #return_type {
+                    #body
+                }
             }
         }
 
@@ -82,7 +111,8 @@ impl Node for EntryPointNode {
         let component_impl_name = graph.component.impl_ident();
 
         let lifetime = if graph.component.component_type == ComponentType::Subcomponent {
-            quote! {<'a>}
+            let lifetime = components::component_lifetime();
+            quote! {<#lifetime>}
         } else {
             quote! {}
         };
@@ -126,6 +156,10 @@ impl Node for EntryPointNode {
         DependencyData::from_type_vec(&self.dependencies)
     }
 
+    fn get_optional_dependencies(&self) -> Vec<TypeData> {
+        self.optional_dependencies.clone()
+    }
+
     fn clone_box(&self) -> Box<dyn Node> {
         Box::new(self.clone())
     }
@@ -0,0 +1,102 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use crate::component_visibles;
+use crate::graph::{ComponentSections, Graph};
+use crate::nodes::component_lifetime::ComponentLifetimeNode;
+use crate::nodes::node::{DependencyData, Node};
+use crate::type_data::ProcessorTypeData;
+use lockjaw_common::manifest::TypeRoot;
+use lockjaw_common::type_data::TypeData;
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::any::Any;
+
+/// Creates `lockjaw::EventBus<E>` with the `Vec<Cl<dyn lockjaw::Subscriber<E>>>` multibinding.
+#[derive(Debug, Clone)]
+pub struct EventBusNode {
+    pub type_: TypeData,
+    pub event: TypeData,
+    pub subscribers: TypeData,
+}
+
+impl EventBusNode {
+    pub fn for_type(type_: &TypeData) -> Option<Box<dyn Node>> {
+        let event = type_.args.get(0)?;
+        let mut subscriber = TypeData::new();
+        subscriber.root = TypeRoot::GLOBAL;
+        subscriber.path = "lockjaw::Subscriber".to_string();
+        subscriber.args.push(event.clone());
+        subscriber.trait_object = true;
+
+        let mut subscribers = TypeData::new();
+        subscribers.root = TypeRoot::GLOBAL;
+        subscribers.path = "std::vec::Vec".to_string();
+        subscribers
+            .args
+            .push(ComponentLifetimeNode::component_lifetime_type(&subscriber));
+        subscribers.qualifier = type_.qualifier.clone();
+
+        let mut event_bus_type = TypeData::new();
+        event_bus_type.root = TypeRoot::GLOBAL;
+        event_bus_type.path = "lockjaw::EventBus".to_string();
+        event_bus_type.args.push(event.clone());
+        event_bus_type.qualifier = type_.qualifier.clone();
+        Some(Box::new(EventBusNode {
+            type_: event_bus_type,
+            event: event.clone(),
+            subscribers,
+        }))
+    }
+}
+
+impl Node for EventBusNode {
+    fn get_name(&self) -> String {
+        format!("EventBus<{}>", self.event.readable())
+    }
+
+    fn generate_implementation(&self, graph: &Graph) -> Result<ComponentSections, TokenStream> {
+        let name_ident = self.get_identifier();
+        let subscribers_provider_name = self.subscribers.identifier();
+        let event_type = component_visibles::visible_type(graph.manifest, &self.event).syn_type();
+
+        let mut result = ComponentSections::new();
+        result.add_methods(quote! {
+            fn #name_ident(&'_ self) -> lockjaw::EventBus<'_, #event_type> {
+                lockjaw::EventBus::new(self.#subscribers_provider_name())
+            }
+        });
+        Ok(result)
+    }
+
+    fn get_type(&self) -> &TypeData {
+        &self.type_
+    }
+
+    fn get_dependencies(&self) -> Vec<DependencyData> {
+        vec![DependencyData::from_type(&self.subscribers)]
+    }
+
+    fn clone_box(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,133 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use crate::component_visibles;
+use crate::graph::ComponentSections;
+use crate::graph::Graph;
+use crate::manifest::ProcessorComponent;
+use crate::nodes::injectable::InjectableNode;
+use crate::nodes::node::{DependencyData, Node};
+use crate::nodes::scoped::static_lifetime_type;
+use crate::type_data::ProcessorTypeData;
+use lockjaw_common::manifest::Injectable;
+use lockjaw_common::type_data::TypeData;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use std::any::Any;
+
+/// `Result<&T, E>` of a scoped `#[inject(fallible)]` injectable. Only a successfully constructed
+/// instance is stored in the component, and each request after a failure retries the constructor.
+#[derive(Debug, Clone)]
+pub struct FallibleScopedNode {
+    pub type_: TypeData,
+    pub dependencies: Vec<TypeData>,
+    pub target: TypeData,
+}
+
+impl FallibleScopedNode {
+    pub fn new(injectable: &Injectable, error: &TypeData) -> Box<dyn Node> {
+        let mut target = injectable.type_data.clone();
+        target.scopes.clear();
+        let mut ref_type = target.clone();
+        ref_type.field_ref = true;
+        Box::new(FallibleScopedNode {
+            type_: InjectableNode::fallible_type(&ref_type, error),
+            dependencies: vec![InjectableNode::fallible_type(&injectable.type_data, error)],
+            target,
+        })
+    }
+}
+
+impl Node for FallibleScopedNode {
+    fn get_name(&self) -> String {
+        format!("{} (fallible scoped)", self.type_.canonical_string_path())
+    }
+
+    fn generate_implementation(&self, graph: &Graph) -> Result<ComponentSections, TokenStream> {
+        let arg_provider_name = self.dependencies[0].identifier();
+        let once_name = format_ident!("once_{}", self.type_.identifier());
+        let name_ident = self.get_identifier();
+        let type_path = component_visibles::visible_type(graph.manifest, &self.type_).syn_type();
+        let once_type = static_lifetime_type(graph, &self.target);
+        let mut result = ComponentSections::new();
+        result.add_fields(quote! {
+            #once_name : lockjaw::Once<#once_type>,
+        });
+        result.add_ctor_params(quote! {#once_name : lockjaw::Once::new(),});
+        let readable = self.target.readable();
+        result.add_debug_fields(quote! {
+            scoped.insert(
+                #readable,
+                if self.#once_name.try_get().is_some() { "initialized" } else { "uninitialized" },
+            );
+        });
+
+        let component_name = graph.component.impl_ident();
+        result.add_methods(quote! {
+            fn #name_ident(&'_ self) -> #type_path{
+                // same as ScopedNode, the instance is stored with 'static lifetime and reassigned
+                // back to '_ (the component's lifetime) when returned.
+                unsafe{
+                    if let ::lockjaw::__private::std::option::Option::Some(result) = self.#once_name.try_get() {
+                        return ::lockjaw::__private::std::result::Result::Ok(
+                            ::lockjaw::__private::std::mem::transmute(result)
+                        );
+                    }
+                    let this: *const #component_name = ::lockjaw::__private::std::mem::transmute(self);
+                    match (&*this).#arg_provider_name() {
+                        ::lockjaw::__private::std::result::Result::Ok(value) => {
+                            let result = self.#once_name.get_or_init(|| value);
+                            ::lockjaw::__private::std::result::Result::Ok(
+                                ::lockjaw::__private::std::mem::transmute(result)
+                            )
+                        }
+                        ::lockjaw::__private::std::result::Result::Err(error) => {
+                            ::lockjaw::__private::std::result::Result::Err(error)
+                        }
+                    }
+                }
+            }
+        });
+        Ok(result)
+    }
+
+    fn can_depend(
+        &self,
+        _target_node: &dyn Node,
+        _ancestors: &Vec<String>,
+    ) -> Result<(), TokenStream> {
+        Ok(())
+    }
+
+    fn get_type(&self) -> &TypeData {
+        &self.type_
+    }
+
+    fn get_dependencies(&self) -> Vec<DependencyData> {
+        DependencyData::from_type_vec(&self.dependencies)
+    }
+
+    fn clone_box(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -15,10 +15,10 @@ limitations under the License.
 */
 
 use crate::component_visibles;
-use crate::graph::{ComponentSections, Graph};
+use crate::graph::{count_construction, ComponentSections, Graph};
 use crate::nodes::node::{DependencyData, Node};
 use crate::type_data::ProcessorTypeData;
-use lockjaw_common::manifest::Injectable;
+use lockjaw_common::manifest::{Injectable, TypeRoot};
 use lockjaw_common::type_data::TypeData;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
@@ -38,6 +38,8 @@ impl InjectableNode {
             let mut container = injectable.container.as_ref().unwrap().clone();
             container.args.push(injectable.type_data.clone());
             container
+        } else if let Some(ref error) = injectable.fallible {
+            InjectableNode::fallible_type(&injectable.type_data, error)
         } else {
             injectable.type_data.clone()
         };
@@ -51,6 +53,19 @@ impl InjectableNode {
             injectable: injectable.clone(),
         })
     }
+
+    /// `Result<T, E>` that an `#[inject(fallible)]` injectable is bound as. The scopes of `T` are
+    /// moved to the `Result`.
+    pub fn fallible_type(type_: &TypeData, error: &TypeData) -> TypeData {
+        let mut result_type = TypeData::new();
+        result_type.root = TypeRoot::GLOBAL;
+        result_type.path = "std::result::Result".to_string();
+        let mut ok = type_.clone();
+        result_type.scopes = std::mem::take(&mut ok.scopes);
+        result_type.args.push(ok);
+        result_type.args.push(error.clone());
+        result_type
+    }
 }
 
 impl Node for InjectableNode {
@@ -79,22 +94,38 @@ impl Node for InjectableNode {
             component_visibles::visible_type(graph.manifest, &self.injectable.type_data).syn_type();
         let ctor_name = format_ident!("{}", self.injectable.ctor_name);
         let mut result = ComponentSections::new();
+        let count = count_construction(self, &mut result);
         if self.injectable.container.is_some() {
-            let mut container = self.injectable.container.as_ref().unwrap().clone();
+            let container = component_visibles::visible_type(
+                graph.manifest,
+                self.injectable.container.as_ref().unwrap(),
+            );
+            let container_type = container.syn_type();
+            let mut container = container.clone();
             container.args.push(component_visibles::visible_type(
                 graph.manifest,
                 &self.injectable.type_data,
             ));
             let result_path = container.syn_type();
-            let container_type = self.injectable.container.as_ref().unwrap().syn_type();
             result.add_methods(quote! {
                 fn #name_ident(&'_ self) -> #result_path #lifetime{
+                    #count
                     #container_type::new(#injectable_path::#ctor_name(#ctor_params))
                 }
             });
+        } else if self.injectable.fallible.is_some() {
+            let result_path =
+                component_visibles::visible_type(graph.manifest, &self.type_).syn_type();
+            result.add_methods(quote! {
+                fn #name_ident(&'_ self) -> #result_path{
+                    #count
+                    #injectable_path::#ctor_name(#ctor_params)
+                }
+            });
         } else {
             result.add_methods(quote! {
                 fn #name_ident(&'_ self) -> #injectable_path #lifetime{
+                    #count
                     #injectable_path::#ctor_name(#ctor_params)
                 }
             });
@@ -106,6 +137,14 @@ impl Node for InjectableNode {
         &self.type_
     }
 
+    fn get_origin(&self) -> Option<&TypeData> {
+        Some(&self.injectable.type_data)
+    }
+
+    fn get_doc(&self) -> &str {
+        &self.injectable.doc
+    }
+
     fn get_dependencies(&self) -> Vec<DependencyData> {
         DependencyData::from_type_vec(&self.dependencies)
     }
@@ -0,0 +1,77 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use crate::graph::{ComponentSections, Graph};
+use crate::nodes::node::{DependencyData, Node};
+use lockjaw_common::type_data::TypeData;
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::any::Any;
+
+/// Creates `lockjaw::InstalledModules` with the names of the modules installed in the component.
+#[derive(Debug, Clone)]
+pub struct InstalledModulesNode {
+    pub type_: TypeData,
+}
+
+impl InstalledModulesNode {
+    pub fn for_type(type_: &TypeData) -> Option<Box<dyn Node>> {
+        if type_.qualifier.is_some() {
+            return None;
+        }
+        Some(Box::new(InstalledModulesNode {
+            type_: type_.clone(),
+        }))
+    }
+}
+
+impl Node for InstalledModulesNode {
+    fn get_name(&self) -> String {
+        "lockjaw::InstalledModules".to_string()
+    }
+
+    fn generate_implementation(&self, graph: &Graph) -> Result<ComponentSections, TokenStream> {
+        let name_ident = self.get_identifier();
+        let modules = graph.installed_module_names();
+
+        let mut result = ComponentSections::new();
+        result.add_methods(quote! {
+            fn #name_ident(&'_ self) -> ::lockjaw::InstalledModules {
+                ::lockjaw::InstalledModules::new(::lockjaw::__private::std::vec![#(#modules),*])
+            }
+        });
+        Ok(result)
+    }
+
+    fn get_type(&self) -> &TypeData {
+        &self.type_
+    }
+
+    fn get_dependencies(&self) -> Vec<DependencyData> {
+        Vec::new()
+    }
+
+    fn clone_box(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
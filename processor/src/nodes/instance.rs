@@ -0,0 +1,101 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use crate::component_visibles;
+use crate::graph::{ComponentSections, Graph};
+use crate::nodes::node::{DependencyData, Node};
+use crate::type_data::ProcessorTypeData;
+use lockjaw_common::type_data::TypeData;
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use std::any::Any;
+
+/// Binds `&T` to the value passed to `build_with()` for a type in the `instance_bindings` of the
+/// component. The value is moved into the component when it is built.
+#[derive(Debug, Clone)]
+pub struct InstanceNode {
+    pub type_: TypeData,
+    pub target: TypeData,
+}
+
+impl InstanceNode {
+    pub fn new(target: &TypeData) -> Box<dyn Node> {
+        let mut type_ = target.clone();
+        type_.field_ref = true;
+        Box::new(InstanceNode {
+            type_,
+            target: target.clone(),
+        })
+    }
+
+    fn field_name(&self) -> Ident {
+        format_ident!("lockjaw_instance_{}", self.target.identifier())
+    }
+}
+
+impl Node for InstanceNode {
+    fn get_name(&self) -> String {
+        format!("{} (instance)", self.type_.canonical_string_path())
+    }
+
+    fn generate_implementation(&self, graph: &Graph) -> Result<ComponentSections, TokenStream> {
+        let name_ident = self.get_identifier();
+        let field_name = self.field_name();
+        let type_path =
+            component_visibles::visible_ref_type(graph.manifest, &self.type_).syn_type();
+        let target_path = component_visibles::visible_type(graph.manifest, &self.target).syn_type();
+        let component_name = graph.component.type_data.readable();
+        let instance_name = self.target.readable();
+
+        let mut result = ComponentSections::new();
+        result.add_fields(quote! {
+            #field_name : ::lockjaw::__private::std::boxed::Box<#target_path>,
+        });
+        result.add_ctor_statements(quote! {
+            let #field_name = instances.take::<#target_path>().ok_or(
+                ::lockjaw::Error::MissingInstance {
+                    component: #component_name,
+                    instance: #instance_name,
+                }
+            )?;
+        });
+        result.add_ctor_params(quote! {#field_name,});
+        result.add_methods(quote! {
+            fn #name_ident(&'_ self) -> #type_path {
+                &*self.#field_name
+            }
+        });
+        Ok(result)
+    }
+
+    fn get_type(&self) -> &TypeData {
+        &self.type_
+    }
+
+    fn get_dependencies(&self) -> Vec<DependencyData> {
+        Vec::new()
+    }
+
+    fn clone_box(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -34,9 +34,12 @@ pub struct LazyNode {
 impl LazyNode {
     pub fn for_type(type_: &TypeData) -> Option<Box<dyn Node>> {
         let inner = type_.args.get(0).unwrap();
-        let provider = ProviderNode::provider_type(inner);
+        let mut provider = ProviderNode::provider_type(inner);
+        provider.qualifier = type_.qualifier.clone();
+        let mut lazy_type = LazyNode::lazy_type(inner);
+        lazy_type.qualifier = type_.qualifier.clone();
         Some(Box::new(Self {
-            type_: LazyNode::lazy_type(inner),
+            type_: lazy_type,
             dependencies: vec![provider.clone()],
             target: provider.clone(),
         }))
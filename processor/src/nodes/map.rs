@@ -17,12 +17,13 @@ limitations under the License.
 use crate::component_visibles;
 use crate::error::compile_error;
 use crate::graph::{ComponentSections, Graph};
+use crate::nodes::binds::BindsNode;
 use crate::nodes::node::{DependencyData, Node};
 use crate::type_data::ProcessorTypeData;
 use lockjaw_common::manifest::{MultibindingMapKey, TypeRoot};
 use lockjaw_common::type_data::TypeData;
-use proc_macro2::{Ident, TokenStream};
-use quote::quote;
+use proc_macro2::{Group, Ident, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
 use std::iter::Extend;
@@ -62,6 +63,28 @@ impl MapNode {
         self.bindings.insert(map_key.clone(), value_type.clone());
         self
     }
+
+    /// Names of the bindings that create a new value each time the map is requested, instead of
+    /// lending out an instance owned by the component.
+    pub fn unscoped_bindings(&self, graph: &Graph) -> Vec<String> {
+        let mut result: Vec<String> = self
+            .bindings
+            .values()
+            .filter_map(|binding| graph.map.get(&binding.identifier()))
+            .filter(|node| {
+                if !node.get_type().scopes.is_empty() {
+                    return false;
+                }
+                if let Some(binds_node) = node.as_any().downcast_ref::<BindsNode>() {
+                    return !binds_node.dependency.field_ref;
+                }
+                true
+            })
+            .map(|node| node.get_name())
+            .collect();
+        result.sort();
+        result
+    }
 }
 
 fn key_type(map_key: &MultibindingMapKey) -> Result<TypeData, TokenStream> {
@@ -69,10 +92,51 @@ fn key_type(map_key: &MultibindingMapKey) -> Result<TypeData, TokenStream> {
         MultibindingMapKey::String(_) => string_type(),
         MultibindingMapKey::I32(_) => i32_type(),
         MultibindingMapKey::Enum(ref enum_type, _) => enum_type.clone(),
+        MultibindingMapKey::Const(ref key_type, _) => key_type.clone(),
+        MultibindingMapKey::Expr(ref key_type, _, _) => key_type.clone(),
         _ => return compile_error("unable to handle key"),
     })
 }
 
+/// Restores the paths the manifest parser replaced with `lockjaw_key_path_N` placeholders.
+fn expr_key(graph: &Graph, expr: &str, paths: &Vec<TypeData>) -> Result<TokenStream, TokenStream> {
+    let tokens: TokenStream = expr
+        .parse()
+        .or_else(|_| compile_error(&format!("unable to parse key {}", expr)))?;
+    Ok(replace_key_paths(graph, tokens, paths))
+}
+
+fn replace_key_paths(graph: &Graph, tokens: TokenStream, paths: &Vec<TypeData>) -> TokenStream {
+    tokens
+        .into_iter()
+        .flat_map(|token| -> TokenStream {
+            match token {
+                TokenTree::Group(group) => {
+                    let mut new_group = Group::new(
+                        group.delimiter(),
+                        replace_key_paths(graph, group.stream(), paths),
+                    );
+                    new_group.set_span(group.span());
+                    TokenTree::Group(new_group).into()
+                }
+                TokenTree::Ident(ref ident) => {
+                    let index = ident
+                        .to_string()
+                        .strip_prefix("lockjaw_key_path_")
+                        .and_then(|index| index.parse::<usize>().ok());
+                    match index.and_then(|index| paths.get(index)) {
+                        Some(path) => component_visibles::visible_type(graph.manifest, path)
+                            .syn_type()
+                            .to_token_stream(),
+                        None => token.into(),
+                    }
+                }
+                _ => token.into(),
+            }
+        })
+        .collect()
+}
+
 fn map_type(key_type: &TypeData, value_type: &TypeData) -> Result<TypeData, TokenStream> {
     let mut map_type = TypeData::new();
     map_type.root = TypeRoot::GLOBAL;
@@ -110,7 +174,7 @@ impl Node for MapNode {
         for binding in &self.bindings {
             let key = match binding.0 {
                 MultibindingMapKey::String(ref key) => {
-                    quote! { #key.to_owned() }
+                    quote! { ::lockjaw::__private::std::borrow::ToOwned::to_owned(#key) }
                 }
                 MultibindingMapKey::I32(key) => {
                     quote! { #key }
@@ -120,6 +184,40 @@ impl Node for MapNode {
                         component_visibles::visible_type(graph.manifest, &value_type).syn_type();
                     quote! { #key }
                 }
+                MultibindingMapKey::Const(_, value) => {
+                    // const values can only be compared at runtime.
+                    let key = component_visibles::visible_type(graph.manifest, &value).syn_type();
+                    let ident = binding.1.identifier();
+                    let message = format!(
+                        "found duplicated key {} for {}",
+                        value.readable(),
+                        self.type_.readable()
+                    );
+                    into_maps = quote! {
+                        #into_maps
+                        if result.insert(::lockjaw::__private::std::convert::Into::into(#key), self.#ident()).is_some() {
+                            ::lockjaw::__private::std::panic!(#message);
+                        }
+                    };
+                    continue;
+                }
+                MultibindingMapKey::Expr(_, expr, paths) => {
+                    // arbitrary expressions can only be compared at runtime.
+                    let key = expr_key(graph, expr, paths)?;
+                    let ident = binding.1.identifier();
+                    let message = format!(
+                        "found duplicated key {} for {}",
+                        expr,
+                        self.type_.readable()
+                    );
+                    into_maps = quote! {
+                        #into_maps
+                        if result.insert(#key, self.#ident()).is_some() {
+                            ::lockjaw::__private::std::panic!(#message);
+                        }
+                    };
+                    continue;
+                }
                 _ => return compile_error(&format!("unable to handle key {:?}", binding.0)),
             };
             let ident = binding.1.identifier();
@@ -134,7 +232,7 @@ impl Node for MapNode {
             #[allow(unused_mut)]
             #[allow(dead_code)]
             fn #name_ident(&'_ self) -> #provides_type{
-                let mut result = HashMap::new();
+                let mut result = ::lockjaw::__private::std::collections::HashMap::new();
                 #into_maps
                 result
             }
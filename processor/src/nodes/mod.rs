@@ -14,19 +14,31 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+pub mod arc_binds;
 pub mod binds;
 pub mod binds_option_of;
 pub mod boxed;
+pub mod component_any;
 pub mod component_lifetime;
+pub mod contributions;
+pub mod default_zst;
+pub mod deferred;
+pub mod elements;
 pub mod entry_point;
+pub mod event_bus;
+pub mod fallible_scoped;
 pub mod injectable;
+pub mod installed_modules;
+pub mod instance;
 pub mod lazy;
 pub mod map;
 pub mod node;
 pub mod parent;
+pub mod post_build;
 pub mod provider;
 pub mod provides;
 pub mod provision;
 pub mod scoped;
 pub mod subcomponent;
 pub mod vec;
+pub mod weak;
@@ -21,10 +21,17 @@ use quote::format_ident;
 use crate::error::compile_error;
 use crate::graph::{ComponentSections, Graph};
 use crate::nodes::boxed::BoxedNode;
+use crate::nodes::component_any::ComponentAnyNode;
 use crate::nodes::component_lifetime::ComponentLifetimeNode;
+use crate::nodes::contributions::ContributionsNode;
+use crate::nodes::deferred::DeferredNode;
+use crate::nodes::elements::ElementsNode;
+use crate::nodes::event_bus::EventBusNode;
+use crate::nodes::installed_modules::InstalledModulesNode;
 use crate::nodes::lazy::LazyNode;
 use crate::nodes::provider::ProviderNode;
 use crate::nodes::scoped::ScopedNode;
+use crate::nodes::weak::WeakNode;
 use crate::type_data::ProcessorTypeData;
 use lockjaw_common::manifest::{BuilderModules, TypeRoot};
 use lockjaw_common::type_data::TypeData;
@@ -87,6 +94,16 @@ pub trait Node: Debug + Any {
         false
     }
 
+    /// The `#[injectable]` or `#[module]` that declared the binding, if any.
+    fn get_origin(&self) -> Option<&TypeData> {
+        None
+    }
+
+    /// Doc comments of the method declaring the binding.
+    fn get_doc(&self) -> &str {
+        ""
+    }
+
     fn clone_box(&self) -> Box<dyn Node>;
     fn as_any(&self) -> &dyn Any;
     fn as_mut_any(&mut self) -> &mut dyn Any;
@@ -122,18 +139,30 @@ impl dyn Node {
         map: &HashMap<Ident, Box<dyn Node>>,
         dependency: &TypeData,
     ) -> Option<Box<dyn Node>> {
+        if ComponentAnyNode::is_any_ref(dependency) {
+            return ComponentAnyNode::for_type(dependency);
+        }
         if dependency.field_ref {
             return Some(ScopedNode::for_type(dependency));
         }
         if dependency.root != TypeRoot::GLOBAL {
             return None;
         }
-        let path = format!("{}::{}", dependency.field_crate, dependency.path);
-        match path.as_str() {
-            "::std::boxed::Box" => BoxedNode::for_type(dependency),
-            "::lockjaw::Provider" => ProviderNode::for_type(dependency),
-            "::lockjaw::Lazy" => LazyNode::for_type(dependency),
-            "::lockjaw::Cl" => ComponentLifetimeNode::for_type(map, dependency),
+        // `field_crate` is only empty for prelude types, imported lockjaw types carry the crate
+        // name, so the global path is matched alone.
+        match dependency.path.as_str() {
+            "std::boxed::Box" => BoxedNode::for_type(map, dependency),
+            "lockjaw::Provider" => ProviderNode::for_type(dependency),
+            "lockjaw::Lazy" => LazyNode::for_type(dependency),
+            "lockjaw::Cl" => ComponentLifetimeNode::for_type(map, dependency),
+            "lockjaw::Deferred" => DeferredNode::for_type(dependency),
+            "lockjaw::Contributions" => ContributionsNode::for_type(dependency),
+            "lockjaw::Elements" => ElementsNode::for_type(dependency),
+            "lockjaw::EventBus" => EventBusNode::for_type(dependency),
+            "lockjaw::InstalledModules" => InstalledModulesNode::for_type(dependency),
+            "std::rc::Weak" | "std::sync::Weak" | "lockjaw::WeakCl" => {
+                WeakNode::for_type(map, dependency)
+            }
             _ => None,
         }
     }
@@ -15,6 +15,7 @@ limitations under the License.
 */
 
 use crate::component_visibles;
+use crate::graph;
 use crate::graph::{ComponentSections, Graph, MissingDependency};
 use crate::nodes::node::Node;
 use crate::type_data::ProcessorTypeData;
@@ -36,10 +37,11 @@ impl ParentNode {
         if parent_dep.multibinding_type != MultibindingType::None {
             type_.identifier_suffix.push_str("_parent");
         }
-        Ok(Box::new(ParentNode {
-            type_,
-            parent_type: parent_dep.type_data.clone(),
-        }))
+        let mut parent_type = parent_dep.type_data.clone();
+        if graph::is_from_parent(&parent_type) {
+            parent_type.qualifier = None;
+        }
+        Ok(Box::new(ParentNode { type_, parent_type }))
     }
 }
 
@@ -51,13 +53,27 @@ impl Node for ParentNode {
     fn generate_implementation(&self, graph: &Graph) -> Result<ComponentSections, TokenStream> {
         let name_ident = self.get_identifier();
         let parent_ident = self.parent_type.identifier();
+        // the subcomponent may also request the unqualified type from the parent.
+        let method_ident = if graph::is_from_parent(&self.type_) {
+            name_ident.clone()
+        } else {
+            parent_ident.clone()
+        };
         let syn_type = component_visibles::visible_type(graph.manifest, &self.type_).syn_type();
 
         let mut result = ComponentSections::new();
 
         result.add_methods(quote! {
             fn #name_ident(&'_ self) -> #syn_type{
-                self.parent.#parent_ident()
+                self.parent.#method_ident()
+            }
+        });
+        result.add_parent_methods(quote! {
+            fn #method_ident(&'_ self) -> #syn_type;
+        });
+        result.add_parent_method_impls(quote! {
+            fn #method_ident(&'_ self) -> #syn_type{
+                self.#parent_ident()
             }
         });
 
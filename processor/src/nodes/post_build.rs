@@ -0,0 +1,105 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use crate::graph::{ComponentSections, Graph};
+use crate::nodes::component_lifetime::ComponentLifetimeNode;
+use crate::nodes::node::{DependencyData, Node};
+use crate::type_data::ProcessorTypeData;
+use lockjaw_common::manifest::TypeRoot;
+use lockjaw_common::type_data::TypeData;
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use std::any::Any;
+
+/// Invokes the `Vec<Cl<dyn lockjaw::PostBuildListener>>` multibinding after the component is
+/// constructed.
+#[derive(Debug, Clone)]
+pub struct PostBuildNode {
+    pub listeners: TypeData,
+}
+
+impl PostBuildNode {
+    pub fn listeners_type() -> TypeData {
+        let mut listener = TypeData::new();
+        listener.root = TypeRoot::GLOBAL;
+        listener.path = "lockjaw::PostBuildListener".to_string();
+        listener.trait_object = true;
+
+        let mut listeners = TypeData::new();
+        listeners.root = TypeRoot::GLOBAL;
+        listeners.path = "std::vec::Vec".to_string();
+        listeners
+            .args
+            .push(ComponentLifetimeNode::component_lifetime_type(&listener));
+        listeners
+    }
+
+    pub fn new() -> Box<dyn Node> {
+        Box::new(PostBuildNode {
+            listeners: PostBuildNode::listeners_type(),
+        })
+    }
+}
+
+impl Node for PostBuildNode {
+    fn get_name(&self) -> String {
+        "post build listeners".to_string()
+    }
+
+    fn generate_implementation(&self, _graph: &Graph) -> Result<ComponentSections, TokenStream> {
+        let name_ident = self.get_identifier();
+        let listeners_provider_name = self.listeners.identifier();
+
+        let mut result = ComponentSections::new();
+        // the sort is stable, so listeners with the same priority run in the binding order.
+        result.add_methods(quote! {
+            fn #name_ident(&'_ self) {
+                let mut listeners = self.#listeners_provider_name();
+                listeners.sort_by_key(|listener| listener.priority());
+                for listener in &listeners {
+                    listener.on_post_build();
+                }
+            }
+        });
+        result.add_post_build_statements(quote! {
+            component.#name_ident();
+        });
+        Ok(result)
+    }
+
+    fn get_type(&self) -> &TypeData {
+        unimplemented!()
+    }
+
+    fn get_identifier(&self) -> Ident {
+        format_ident!("lockjaw_post_build")
+    }
+
+    fn get_dependencies(&self) -> Vec<DependencyData> {
+        vec![DependencyData::from_type(&self.listeners)]
+    }
+
+    fn clone_box(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -32,14 +32,26 @@ pub struct ProviderNode {
 
 impl ProviderNode {
     pub fn for_type(type_: &TypeData) -> Option<Box<dyn Node>> {
-        let inner = type_.args.get(0).unwrap();
+        let inner = ProviderNode::qualified_inner(type_);
+        let mut provider_type = ProviderNode::provider_type(type_.args.get(0).unwrap());
+        provider_type.qualifier = type_.qualifier.clone();
         Some(Box::new(ProviderNode {
-            type_: ProviderNode::provider_type(inner),
+            type_: provider_type,
             dependencies: vec![inner.clone()],
-            inner: inner.clone(),
+            inner,
         }))
     }
 
+    /// The type wrapped by `Provider<T>` or `Lazy<T>`. `#[qualified]` on the injection site
+    /// qualifies the wrapper, and is carried over to `T`.
+    fn qualified_inner(type_: &TypeData) -> TypeData {
+        let mut inner = type_.args.get(0).unwrap().clone();
+        if inner.qualifier.is_none() {
+            inner.qualifier = type_.qualifier.clone();
+        }
+        inner
+    }
+
     pub fn provider_type(type_: &TypeData) -> TypeData {
         let mut provider_type = TypeData::new();
         provider_type.root = TypeRoot::GLOBAL;
@@ -17,14 +17,14 @@ use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 
 use crate::component_visibles;
-use crate::graph::ComponentSections;
 use crate::graph::Graph;
+use crate::graph::{count_construction, ComponentSections};
 use crate::nodes::map::MapNode;
 use crate::nodes::node;
 use crate::nodes::node::{DependencyData, ModuleInstance, Node};
 use crate::nodes::vec::VecNode;
 use crate::type_data::ProcessorTypeData;
-use lockjaw_common::manifest::{Binding, BuilderModules, MultibindingType};
+use lockjaw_common::manifest::{Binding, BindingType, BuilderModules, MultibindingType};
 use lockjaw_common::type_data::TypeData;
 use std::any::Any;
 
@@ -61,7 +61,11 @@ impl ProvidesNode {
         match binding.multibinding_type {
             MultibindingType::IntoVec => {
                 let mut vec_node = VecNode::new(&binding.type_data);
-                vec_node.add_binding(&type_, &binding.multibinding_type);
+                vec_node.add_conditional_binding(
+                    &type_,
+                    &binding.multibinding_type,
+                    &binding.if_present,
+                );
                 result.push(vec_node);
             }
             MultibindingType::ElementsIntoVec => {
@@ -83,10 +87,16 @@ impl ProvidesNode {
 
 impl Node for ProvidesNode {
     fn get_name(&self) -> String {
+        let kind = if self.binding.binding_type == BindingType::Decorator {
+            "module decorator"
+        } else {
+            "module provides"
+        };
         format!(
-            "{}.{} (module provides)",
+            "{}.{} ({})",
             self.module_instance.type_.canonical_string_path(),
-            self.binding.name
+            self.binding.name,
+            kind
         )
     }
 
@@ -115,8 +125,10 @@ impl Node for ProvidesNode {
             invoke_module = quote! {self.#module_name.#module_method(#args)}
         }
         let mut result = ComponentSections::new();
+        let count = count_construction(self, &mut result);
         result.add_methods(quote! {
             fn #name_ident(&'_ self) -> #type_path{
+                #count
                 #invoke_module
             }
         });
@@ -127,6 +139,14 @@ impl Node for ProvidesNode {
         &self.type_
     }
 
+    fn get_origin(&self) -> Option<&TypeData> {
+        Some(&self.module_instance.type_)
+    }
+
+    fn get_doc(&self) -> &str {
+        &self.binding.doc
+    }
+
     fn get_dependencies(&self) -> Vec<DependencyData> {
         DependencyData::from_type_vec(&self.dependencies)
     }
@@ -18,8 +18,10 @@ use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
 
 use crate::component_visibles;
+use crate::error::compile_error;
 use crate::graph::{ComponentSections, Graph};
 use crate::nodes::node::{DependencyData, Node};
+use crate::nodes::scoped::ScopedNode;
 use crate::type_data::ProcessorTypeData;
 use lockjaw_common::manifest::{Component, Dependency};
 use lockjaw_common::type_data::TypeData;
@@ -34,14 +36,118 @@ pub struct ProvisionNode {
 
 impl ProvisionNode {
     pub fn new(dependency: Dependency, component: Component) -> Self {
+        let target = if dependency.parameters.is_empty() {
+            dependency.type_data.clone()
+        } else {
+            factory_type(&dependency.type_data)
+        };
         ProvisionNode {
-            dependencies: vec![dependency.type_data.clone()],
+            dependencies: vec![target],
             dependency,
             component,
         }
     }
 }
 
+/// The factory generated by `#[factory]` for the `#[injectable]` `type_`.
+fn factory_type(type_: &TypeData) -> TypeData {
+    let mut result = type_.clone();
+    result.path = format!("{}Factory", result.path);
+    result.args.clear();
+    result
+}
+
+impl ProvisionNode {
+    /// Provisions with parameters call the factory of the returned type, forwarding the parameters
+    /// as the `#[runtime]` arguments.
+    fn generate_with_parameters(&self, graph: &Graph) -> Result<ComponentSections, TokenStream> {
+        let mut result = ComponentSections::new();
+        let dependency_name = self.get_identifier();
+        let dependency_path =
+            component_visibles::visible_type(graph.manifest, &self.dependency.type_data).syn_type();
+        let factory_name = self.dependencies[0].identifier();
+        let doc = graph.doc_attributes(&self.dependencies[0]);
+        let mut parameters = quote! {};
+        let mut args = quote! {};
+        for parameter in &self.dependency.parameters {
+            let name = format_ident!("{}", parameter.name);
+            let parameter_type =
+                component_visibles::visible_type(graph.manifest, &parameter.type_data).syn_type();
+            let reference = if parameter.type_data.field_ref {
+                quote! {&}
+            } else {
+                quote! {}
+            };
+            parameters = quote! { #parameters #name: #reference #parameter_type, };
+            args = quote! { #args #name, };
+        }
+        result.add_trait_methods(quote! {
+           #doc
+           fn #dependency_name(&self, #parameters) -> #dependency_path {
+              self.#factory_name().lockjaw_create(#args)
+           }
+        });
+        Ok(result)
+    }
+
+    /// `&mut T` provisions hand out the instance stored by the [ScopedNode] of `&T`, relying on the
+    /// `&mut self` receiver for exclusive access.
+    fn generate_mutable(&self, graph: &Graph) -> Result<ComponentSections, TokenStream> {
+        let mut target = self.dependency.type_data.clone();
+        target.field_ref = false;
+        let is_scoped = graph
+            .map
+            .get(&target.identifier())
+            .map(|node| !node.get_type().scopes.is_empty())
+            .unwrap_or(false);
+        let is_scoped_ref = graph
+            .map
+            .get(&self.dependency.type_data.identifier())
+            .map(|node| node.as_any().is::<ScopedNode>())
+            .unwrap_or(false);
+        if !is_scoped || !is_scoped_ref {
+            return compile_error(&format!(
+                "{} returns &mut {}, which must be scoped to the component",
+                self.get_name(),
+                target.readable()
+            ));
+        }
+        if graph.has_lifetime(&target) {
+            return compile_error(&format!(
+                "{} returns &mut {}, which cannot have lifetimes",
+                self.get_name(),
+                target.readable()
+            ));
+        }
+
+        let mut result = ComponentSections::new();
+        let dependency_name = self.get_identifier();
+        let target_path = component_visibles::visible_type(graph.manifest, &target).syn_type();
+        let provider_name = self.dependency.type_data.identifier();
+        let once_name = format_ident!("once_{}", self.dependency.type_data.identifier());
+        let doc = graph.doc_attributes(&target);
+        let value = if graph.component.thread_safe {
+            let message = format!(
+                "{} is shared by Cl::to_arc(), and cannot be borrowed as mutable",
+                target.readable()
+            );
+            quote! {
+                ::lockjaw::__private::std::sync::Arc::get_mut(self.#once_name.get_mut().unwrap()).expect(#message)
+            }
+        } else {
+            quote! {self.#once_name.get_mut().unwrap()}
+        };
+        result.add_trait_methods(quote! {
+           #doc
+           fn #dependency_name(&mut self) -> &mut #target_path {
+              self.#provider_name();
+              #value
+           }
+        });
+        Ok(result)
+    }
+}
+
 impl Node for ProvisionNode {
     fn get_name(&self) -> String {
         format!(
@@ -52,12 +158,20 @@ impl Node for ProvisionNode {
     }
 
     fn generate_implementation(&self, graph: &Graph) -> Result<ComponentSections, TokenStream> {
+        if self.dependency.mutable {
+            return self.generate_mutable(graph);
+        }
+        if !self.dependency.parameters.is_empty() {
+            return self.generate_with_parameters(graph);
+        }
         let mut result = ComponentSections::new();
         let dependency_name = self.get_identifier();
         let dependency_path =
             component_visibles::visible_type(graph.manifest, &self.dependency.type_data).syn_type();
         let provider_name = self.dependency.type_data.identifier();
+        let doc = graph.doc_attributes(&self.dependency.type_data);
         result.add_trait_methods(quote! {
+           #doc
            fn #dependency_name(&self) -> #dependency_path {
               self.#provider_name()
            }
@@ -14,13 +14,15 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 use crate::component_visibles;
+use crate::error::compile_error;
 use crate::graph::ComponentSections;
 use crate::graph::Graph;
 use crate::manifest::ProcessorComponent;
+use crate::nodes::map::MapNode;
 use crate::nodes::node::{DependencyData, Node};
 use crate::type_data::ProcessorTypeData;
 use lockjaw_common::type_data::TypeData;
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
 use std::any::Any;
 
@@ -66,45 +68,80 @@ impl Node for ScopedNode {
         let type_path =
             component_visibles::visible_ref_type(graph.manifest, &self.type_).syn_type();
         let mut result = ComponentSections::new();
-        let once_inner_type =
-            if !self.target.args.is_empty() && graph.has_lifetime(&self.target.args[0]) {
-                let mut container = self.target.clone();
-                container.args = Vec::new();
-                let container_type = container.syn_type();
-                let target_type = self.target.args[0].syn_type();
-                quote! {
-                    #container_type<#target_type<'static>>
-                }
-            } else {
-                let lifetime = if graph.has_lifetime(&self.target) {
-                    //  effectively component lifetime since the component owns it.
-                    quote! {<'static>}
-                } else {
-                    quote! {}
-                };
-                let once_type =
-                    component_visibles::visible_type(graph.manifest, &self.target).syn_type();
-                quote! {#once_type #lifetime}
-            };
+        if let Some(map_node) = graph
+            .map
+            .get(&self.target.identifier())
+            .and_then(|node| node.as_any().downcast_ref::<MapNode>())
+        {
+            let unscoped_bindings = map_node.unscoped_bindings(graph);
+            if !unscoped_bindings.is_empty() {
+                return compile_error(&format!(
+                    "{} is cached in the component, which requires every #[into_map] binding of \
+                    {} to be scoped, but these are not:\n\t{}",
+                    self.type_.readable(),
+                    self.target.readable(),
+                    unscoped_bindings.join("\n\t")
+                ));
+            }
+        }
+        let once_inner_type = static_lifetime_type(graph, &self.target);
+        let arc_backed = graph.component.thread_safe;
+        let (once_type, once_value) = if arc_backed {
+            (
+                quote! {::lockjaw::__private::std::sync::Arc<#once_inner_type>},
+                quote! {::lockjaw::__private::std::sync::Arc::new((&*this).#arg_provider_name())},
+            )
+        } else {
+            (
+                quote! {#once_inner_type},
+                quote! {(&*this).#arg_provider_name()},
+            )
+        };
         result.add_fields(quote! {
-            #once_name : lockjaw::Once<#once_inner_type>,
+            #once_name : lockjaw::Once<#once_type>,
         });
         result.add_ctor_params(quote! {#once_name : lockjaw::Once::new(),});
+        let readable = self.target.readable();
+        result.add_debug_fields(quote! {
+            scoped.insert(
+                #readable,
+                if self.#once_name.try_get().is_some() { "initialized" } else { "uninitialized" },
+            );
+        });
 
         let component_name = graph.component.impl_ident();
+        let value_ref = if arc_backed {
+            quote! {&**result}
+        } else {
+            quote! {result}
+        };
         result.add_methods(quote! {
             fn #name_ident(&'_ self) -> #type_path{
                 // prevent self from being borrowed into once, which has 'static lifetime, but in
                 // practice limited to the component's lifetime.
                 // safe since lambda in Once.get() is invoked immediately.
                 unsafe{
-                    let this: *const #component_name = ::std::mem::transmute(self);
-                    let result = self.#once_name.get(|| (&*this).#arg_provider_name());
+                    let this: *const #component_name = ::lockjaw::__private::std::mem::transmute(self);
+                    let result = self.#once_name.get_or_init(|| #once_value);
                     // erases the 'static lifetime on Once, and reassign it back to '_ (the component's lifetime)
-                    std::mem::transmute(result)
+                    ::lockjaw::__private::std::mem::transmute(#value_ref)
                 }
             }
         });
+        if arc_backed {
+            let arc_ident = arc_ident(&self.type_);
+            let target_path =
+                component_visibles::visible_type(graph.manifest, &self.target).syn_type();
+            result.add_methods(quote! {
+                fn #arc_ident(&'_ self) -> ::lockjaw::__private::std::sync::Arc<#target_path>{
+                    unsafe{
+                        let this: *const #component_name = ::lockjaw::__private::std::mem::transmute(self);
+                        let result = self.#once_name.get_or_init(|| #once_value);
+                        ::lockjaw::__private::std::mem::transmute(result.clone())
+                    }
+                }
+            });
+        }
         Ok(result)
     }
 
@@ -135,3 +172,51 @@ impl Node for ScopedNode {
         self
     }
 }
+
+/// Whether the scoped binding `ref_type` is stored in an `Arc`, which is the case in `thread_safe`
+/// components so `Cl::to_arc()` can hand out the instance.
+pub fn is_arc_backed(graph: &Graph, ref_type: &TypeData) -> bool {
+    graph.component.thread_safe
+        && graph
+            .map
+            .get(&ref_type.identifier())
+            .is_some_and(|node| node.as_any().downcast_ref::<ScopedNode>().is_some())
+}
+
+/// Method returning the `Arc` an arc backed scoped binding is stored in.
+pub fn arc_ident(ref_type: &TypeData) -> Ident {
+    format_ident!("arc_{}", ref_type.identifier())
+}
+
+/// Renders `type_` with all lifetimes being `'static`, which are effectively the component's
+/// lifetime since the component owns the instance.
+pub fn static_lifetime_type(graph: &Graph, type_: &TypeData) -> TokenStream {
+    if type_.path == "lockjaw::Cl" || type_.path == "lockjaw::EventBus" {
+        let mut container = type_.clone();
+        container.args = Vec::new();
+        let container_type = container.syn_type();
+        let inner = component_visibles::visible_type(graph.manifest, &type_.args[0]).syn_type();
+        return quote! {#container_type<'static, #inner>};
+    }
+    if graph.has_lifetime(type_) {
+        let type_path = component_visibles::visible_type(graph.manifest, type_).syn_type();
+        return quote! {#type_path<'static>};
+    }
+    if type_.args.iter().any(|arg| contains_lifetime(graph, arg)) {
+        let mut container = type_.clone();
+        container.args = Vec::new();
+        let container_type = container.syn_type();
+        let args: Vec<TokenStream> = type_
+            .args
+            .iter()
+            .map(|arg| static_lifetime_type(graph, arg))
+            .collect();
+        return quote! {#container_type<#(#args),*>};
+    }
+    let type_path = component_visibles::visible_type(graph.manifest, type_).syn_type();
+    quote! {#type_path}
+}
+
+fn contains_lifetime(graph: &Graph, type_: &TypeData) -> bool {
+    graph.has_lifetime(type_) || type_.args.iter().any(|arg| contains_lifetime(graph, arg))
+}
@@ -15,17 +15,19 @@ limitations under the License.
 */
 
 use crate::component_visibles;
+use crate::components;
 use crate::error::CompileError;
-use crate::graph::{build_graph, ComponentSections, Graph};
+use crate::graph::{build_graph, ComponentSections, Graph, MissingDependency};
 use crate::manifest::ProcessorComponent;
 use crate::nodes::component_lifetime::ComponentLifetimeNode;
 use crate::nodes::map::MapNode;
 use crate::nodes::node::{DependencyData, Node};
+use crate::nodes::parent::ParentNode;
 use crate::nodes::vec::VecNode;
 use crate::type_data::ProcessorTypeData;
-use lockjaw_common::manifest::{Component, Manifest, MultibindingType};
+use lockjaw_common::manifest::{Component, ComponentType, Manifest, MultibindingType};
 use lockjaw_common::type_data::TypeData;
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
 use std::any::Any;
 
@@ -35,26 +37,39 @@ pub struct SubcomponentNode {
     pub builder_type: TypeData,
     pub dependencies: Vec<DependencyData>,
     pub token_stream: TokenStream,
+    /// [ComponentSections::warnings] of the subcomponent, passed on to the parent.
+    pub warnings: TokenStream,
 }
 
 impl SubcomponentNode {
     pub fn new(
         manifest: &Manifest,
         component_type: &TypeData,
-        parent_component_type: &TypeData,
+        parent: &Graph,
         parent_multibinding_nodes: &Vec<Box<dyn Node>>,
     ) -> Result<Vec<Box<dyn Node>>, TokenStream> {
-        let subcomponent = find_component(manifest, component_type).map_compile_error(&format!(
-            "unable to find component {}",
-            component_type.readable()
-        ))?;
-        let mut builder_type = component_type.clone();
-        builder_type.path.push_str("Builder");
-        builder_type.trait_object = true;
-        let type_ = ComponentLifetimeNode::component_lifetime_type(&builder_type);
-        let (graph, missing_deps) =
+        let mut unqualified_component_type = component_type.clone();
+        unqualified_component_type.qualifier = None;
+        let subcomponent = find_component(manifest, &unqualified_component_type)
+            .map_compile_error(&format!(
+                "unable to find component {}",
+                component_type.readable()
+            ))?;
+        let builder_type = builder_type(&unqualified_component_type);
+        let mut type_ = ComponentLifetimeNode::component_lifetime_type(&builder_type);
+        type_.qualifier = component_type.qualifier.clone();
+        let (mut graph, mut missing_deps) =
             build_graph(manifest, &subcomponent, parent_multibinding_nodes)?;
-
+        if let Some(ref qualifier) = component_type.qualifier {
+            qualify_parent_dependencies(qualifier, parent, &mut graph, &mut missing_deps);
+        }
+        let (token_stream, warnings) = generate_component(
+            manifest,
+            &subcomponent,
+            &graph,
+            &parent.component,
+            &builder_type,
+        )?;
         let mut nodes: Vec<Box<dyn Node>> = Vec::new();
         nodes.push(Box::new(SubcomponentNode {
             type_,
@@ -66,13 +81,8 @@ impl SubcomponentNode {
                     message: md.to_message(),
                 })
                 .collect(),
-            token_stream: generate_component(
-                manifest,
-                &subcomponent,
-                &graph,
-                parent_component_type,
-                &builder_type,
-            )?,
+            token_stream,
+            warnings,
         }));
         for dep in missing_deps.iter() {
             match dep.multibinding_type {
@@ -89,23 +99,70 @@ impl SubcomponentNode {
     }
 }
 
+/// Requests dependencies of a subcomponent installed with `subcomponent_qualifier` from the parent
+/// with the same qualifier, if the parent binds them. This allows each instance of the subcomponent
+/// to be configured differently.
+fn qualify_parent_dependencies(
+    qualifier: &TypeData,
+    parent: &Graph,
+    graph: &mut Graph,
+    missing_deps: &mut Vec<MissingDependency>,
+) {
+    for missing_dep in missing_deps.iter_mut() {
+        if missing_dep.multibinding_type != MultibindingType::None
+            || missing_dep.type_data.qualifier.is_some()
+        {
+            continue;
+        }
+        let mut qualified_type = missing_dep.type_data.clone();
+        qualified_type.qualifier = Some(Box::new(qualifier.clone()));
+        if !parent.has_node(&qualified_type) {
+            continue;
+        }
+        // both `T` and `#[qualified(lockjaw::FromParent)] T` are requested from the parent as `T`.
+        for parent_node in graph
+            .map
+            .values_mut()
+            .filter_map(|node| node.as_mut_any().downcast_mut::<ParentNode>())
+            .filter(|parent_node| parent_node.parent_type == missing_dep.type_data)
+        {
+            parent_node.parent_type = qualified_type.clone();
+        }
+        missing_dep.type_data = qualified_type;
+    }
+}
+
+/// The `dyn FooBuilder` trait generated for the subcomponent `Foo`.
+pub fn builder_type(component_type: &TypeData) -> TypeData {
+    let mut builder_type = component_type.clone();
+    builder_type.path.push_str("Builder");
+    builder_type.trait_object = true;
+    builder_type
+}
+
+fn builder_impl_ident() -> Ident {
+    format_ident!("SubcomponentBuilderImpl")
+}
+
+/// Generates the subcomponent, returning the code and its [ComponentSections::warnings].
 fn generate_component(
     manifest: &Manifest,
     component: &Component,
     graph: &Graph,
-    parent_component_type: &TypeData,
+    parent_component: &Component,
     builder_type: &TypeData,
-) -> Result<TokenStream, TokenStream> {
+) -> Result<(TokenStream, TokenStream), TokenStream> {
     let component_name =
         component_visibles::visible_type(graph.manifest, &component.type_data).syn_type();
     let component_impl_name = component.impl_ident();
 
-    let component_builder_impl_name = format_ident!("SubcomponentBuilderImpl",);
+    let component_builder_impl_name = builder_impl_ident();
 
     let mut component_sections = ComponentSections::new();
 
-    component_sections.merge(graph.generate_modules(manifest));
     component_sections.merge(graph.generate_provisions(component)?);
+    // modules are dropped after the bindings they provide.
+    component_sections.merge(graph.generate_modules(manifest));
 
     let fields = &component_sections.fields;
     let ctor_params = &component_sections.ctor_params;
@@ -113,7 +170,22 @@ fn generate_component(
     let methods = &component_sections.methods;
     let trait_methods = &component_sections.trait_methods;
     let items = &component_sections.items;
-    let parent_impl_type = format_ident!("{}Impl", parent_component_type.identifier().to_string());
+    let parent_methods = &component_sections.parent_methods;
+    let parent_method_impls = &component_sections.parent_method_impls;
+    let lifetime = components::component_lifetime();
+    let debug_impl = graph.generate_debug_impl(
+        &quote! {#component_impl_name<#lifetime>},
+        &quote! {<#lifetime>},
+        &component_sections.debug_fields,
+    );
+    let parent_impl_type = parent_component.impl_ident();
+    // the parent impl is generic over the component lifetime if it is also a subcomponent, which
+    // cannot be named by items nested in its methods.
+    let parent_impl_lifetime = if parent_component.component_type == ComponentType::Subcomponent {
+        quote! {<'_>}
+    } else {
+        quote! {}
+    };
 
     let mut builder_type_without_dyn =
         component_visibles::visible_type(graph.manifest, &builder_type).clone();
@@ -128,46 +200,64 @@ fn generate_component(
         quote! {}
     };
 
+    // the subcomponent holds the parent as `lockjaw_parent`, which only exposes the bindings the
+    // subcomponent requests, instead of the concrete parent implementation.
     let component_impl = quote! {
         #[allow(non_snake_case)]
         #[allow(non_camel_case_types)]
         #[allow(dead_code)]
-        struct #component_impl_name<'a> {
-            parent: &'a #parent_impl_type,
+        struct #component_impl_name<#lifetime> {
+            parent: &#lifetime dyn lockjaw_parent,
             #fields
         }
         #[allow(non_snake_case)]
-        impl <'a> #component_impl_name<'a> {
+        impl <#lifetime> #component_impl_name<#lifetime> {
             #methods
         }
         #[allow(non_snake_case)]
-        impl <'a> #component_name<'a> for #component_impl_name<'a> {
+        impl <#lifetime> #component_name<#lifetime> for #component_impl_name<#lifetime> {
             #trait_methods
         }
+        #debug_impl
 
         #items
 
         #[allow(non_snake_case)]
         #[allow(non_camel_case_types)]
-        struct #component_builder_impl_name<'a> {
-            parent: &'a #parent_impl_type,
+        #[allow(dead_code)]
+        trait lockjaw_parent {
+            #parent_methods
         }
 
         #[allow(non_snake_case)]
-        impl <'a> #builder_syn_type<'a> for #component_builder_impl_name<'a> {
+        impl lockjaw_parent for #parent_impl_type #parent_impl_lifetime {
+            #parent_method_impls
+        }
 
-            fn build(&self, #builder_param) -> lockjaw::Cl<'a, dyn #component_name<'a>> {
+        #[allow(non_snake_case)]
+        #[allow(non_camel_case_types)]
+        struct #component_builder_impl_name<#lifetime> {
+            parent: &#lifetime dyn lockjaw_parent,
+        }
+
+        #[allow(non_snake_case)]
+        impl <#lifetime> #builder_syn_type<#lifetime> for #component_builder_impl_name<#lifetime> {
+
+            fn build(&self, #builder_param) -> lockjaw::Cl<#lifetime, dyn #component_name<#lifetime>> {
                 #ctor_statements
-                lockjaw::Cl::Val(::std::boxed::Box::new(#component_impl_name{parent: self.parent, #ctor_params}))
+                lockjaw::Cl::Val(::lockjaw::__private::std::boxed::Box::new(#component_impl_name{parent: self.parent, #ctor_params}))
             }
         }
 
-        lockjaw::Cl::Val(::std::boxed::Box::new(#component_builder_impl_name {parent: self}))
+        lockjaw::Cl::Val(::lockjaw::__private::std::boxed::Box::new(#component_builder_impl_name {parent: self}))
     };
 
-    Ok(quote! {
-        #component_impl
-    })
+    Ok((
+        quote! {
+            #component_impl
+        },
+        component_sections.warnings,
+    ))
 }
 
 fn find_component(manifest: &Manifest, component_type: &TypeData) -> Option<Component> {
@@ -199,6 +289,7 @@ impl Node for SubcomponentNode {
                 #impl_tokens
             }
         });
+        component_sections.add_warnings(self.warnings.clone());
 
         Ok(component_sections)
     }
@@ -231,3 +322,63 @@ impl Node for SubcomponentNode {
         self
     }
 }
+
+/// Provides `Cl<dyn FooBuilder>` inside the subcomponent `Foo` itself, so it can spawn siblings that
+/// share the same parent.
+///
+/// The builder impl is generated in the same block as the subcomponent impl by
+/// [SubcomponentNode], so it is directly reachable here.
+#[derive(Debug, Clone)]
+pub struct SubcomponentSelfBuilderNode {
+    pub type_: TypeData,
+    pub builder_type: TypeData,
+}
+
+impl SubcomponentSelfBuilderNode {
+    pub fn new(component_type: &TypeData) -> Box<dyn Node> {
+        let builder_type = builder_type(component_type);
+        Box::new(SubcomponentSelfBuilderNode {
+            type_: ComponentLifetimeNode::component_lifetime_type(&builder_type),
+            builder_type,
+        })
+    }
+}
+
+impl Node for SubcomponentSelfBuilderNode {
+    fn get_name(&self) -> String {
+        format!("{} (subcomponent self builder)", self.type_.readable())
+    }
+
+    fn generate_implementation(&self, graph: &Graph) -> Result<ComponentSections, TokenStream> {
+        let mut component_sections = ComponentSections::new();
+
+        let name_ident = self.get_identifier();
+        let type_path =
+            component_visibles::visible_type(graph.manifest, &self.builder_type).syn_type();
+        let builder_impl_name = builder_impl_ident();
+
+        component_sections.add_methods(quote! {
+            fn #name_ident(&'_ self) -> ::lockjaw::Cl<'_, #type_path>{
+                lockjaw::Cl::Val(::lockjaw::__private::std::boxed::Box::new(#builder_impl_name {parent: self.parent}))
+            }
+        });
+
+        Ok(component_sections)
+    }
+
+    fn get_type(&self) -> &TypeData {
+        &self.type_
+    }
+
+    fn clone_box(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
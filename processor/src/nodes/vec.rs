@@ -29,6 +29,8 @@ use std::iter::Extend;
 pub struct VecBinding {
     pub type_data: TypeData,
     pub multibinding_type: MultibindingType,
+    /// Only contributes if this type is bound in the component.
+    pub if_present: Option<TypeData>,
 }
 
 #[derive(Debug, Clone)]
@@ -49,13 +51,31 @@ impl VecNode {
         &mut self,
         type_data: &TypeData,
         multibinding_type: &MultibindingType,
+    ) -> &mut Self {
+        self.add_conditional_binding(type_data, multibinding_type, &None)
+    }
+
+    pub fn add_conditional_binding(
+        &mut self,
+        type_data: &TypeData,
+        multibinding_type: &MultibindingType,
+        if_present: &Option<TypeData>,
     ) -> &mut Self {
         self.bindings.push(VecBinding {
             type_data: type_data.clone(),
             multibinding_type: multibinding_type.clone(),
+            if_present: if_present.clone(),
         });
         self
     }
+
+    /// Drops contributions whose `if_present` type is not bound in `graph`.
+    pub fn retain_present(&mut self, graph: &Graph) {
+        self.bindings.retain(|binding| match binding.if_present {
+            Some(ref if_present) => graph.has_node(if_present),
+            None => true,
+        });
+    }
 }
 
 fn vec_type(type_data: &TypeData) -> TypeData {
@@ -100,7 +120,7 @@ impl Node for VecNode {
             #[allow(unused_mut)]
             #[allow(dead_code)]
             fn #name_ident(&'_ self) -> #provides_type{
-                let mut result = vec![#into_vecs];
+                let mut result = ::lockjaw::__private::std::vec![#into_vecs];
                 #elements_into_vecs;
                 result
             }
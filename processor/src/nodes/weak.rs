@@ -0,0 +1,154 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+use crate::component_visibles;
+use crate::error::compile_error;
+use crate::graph::{ComponentSections, Graph};
+use crate::nodes::node::{DependencyData, Node};
+use crate::nodes::scoped;
+use crate::type_data::ProcessorTypeData;
+use lockjaw_common::manifest::TypeRoot;
+use lockjaw_common::type_data::TypeData;
+use proc_macro2::{Ident, TokenStream};
+use quote::quote;
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Where the weak reference is downgraded from.
+#[derive(Debug, Clone, PartialEq)]
+enum WeakSource {
+    /// A scoped binding with `container: Rc`.
+    RcContainer,
+    /// A scoped binding with `container: Arc`.
+    ArcContainer,
+    /// A binding scoped in a `thread_safe` component, which is stored in an `Arc`.
+    ArcBacked,
+}
+
+/// Provides `std::rc::Weak<T>`, `std::sync::Weak<T>` and `lockjaw::WeakCl<T>` for scoped bindings
+/// stored in an `Rc` or `Arc`, which do not keep the instance alive.
+#[derive(Debug, Clone)]
+pub struct WeakNode {
+    pub type_: TypeData,
+    pub dependency: TypeData,
+    source: WeakSource,
+}
+
+impl WeakNode {
+    pub fn for_type(
+        map: &HashMap<Ident, Box<dyn Node>>,
+        type_: &TypeData,
+    ) -> Option<Box<dyn Node>> {
+        let target = type_.args.first()?;
+        let (source, dependency) = if type_.path == "std::rc::Weak" {
+            (
+                WeakSource::RcContainer,
+                ref_type(&container("std::rc::Rc", target)),
+            )
+        } else {
+            let arc = container("std::sync::Arc", target);
+            if map.contains_key(&arc.identifier()) {
+                (WeakSource::ArcContainer, ref_type(&arc))
+            } else {
+                (WeakSource::ArcBacked, ref_type(target))
+            }
+        };
+        Some(Box::new(WeakNode {
+            type_: type_.clone(),
+            dependency,
+            source,
+        }))
+    }
+}
+
+fn container(path: &str, target: &TypeData) -> TypeData {
+    let mut container = TypeData::new();
+    container.root = TypeRoot::GLOBAL;
+    container.path = path.to_owned();
+    container.args.push(target.clone());
+    container
+}
+
+fn ref_type(type_: &TypeData) -> TypeData {
+    let mut ref_type = type_.clone();
+    ref_type.field_ref = true;
+    ref_type
+}
+
+impl Node for WeakNode {
+    fn get_name(&self) -> String {
+        self.type_.canonical_string_path()
+    }
+
+    fn generate_implementation(&self, graph: &Graph) -> Result<ComponentSections, TokenStream> {
+        let name_ident = self.get_identifier();
+        let type_path = component_visibles::visible_type(graph.manifest, &self.type_).syn_type();
+        let dependency_name = self.dependency.identifier();
+        let weak = match self.source {
+            WeakSource::RcContainer => quote! {
+                ::lockjaw::__private::std::rc::Rc::downgrade(self.#dependency_name())
+            },
+            WeakSource::ArcContainer => quote! {
+                ::lockjaw::__private::std::sync::Arc::downgrade(self.#dependency_name())
+            },
+            WeakSource::ArcBacked => {
+                if !scoped::is_arc_backed(graph, &self.dependency) {
+                    return compile_error(&format!(
+                        "{} requires {} to be scoped in a thread_safe component, or scoped with \
+                        `container: Arc`",
+                        self.type_.readable(),
+                        self.type_.args[0].readable()
+                    ));
+                }
+                let arc_ident = scoped::arc_ident(&self.dependency);
+                quote! {
+                    ::lockjaw::__private::std::sync::Arc::downgrade(&self.#arc_ident())
+                }
+            }
+        };
+        let weak = if self.type_.path == "lockjaw::WeakCl" {
+            quote! {::lockjaw::WeakCl::new(#weak)}
+        } else {
+            weak
+        };
+
+        let mut result = ComponentSections::new();
+        result.add_methods(quote! {
+            fn #name_ident(&'_ self) -> #type_path {
+                #weak
+            }
+        });
+        Ok(result)
+    }
+
+    fn get_type(&self) -> &TypeData {
+        &self.type_
+    }
+
+    fn get_dependencies(&self) -> Vec<DependencyData> {
+        vec![DependencyData::from_type(&self.dependency)]
+    }
+
+    fn clone_box(&self) -> Box<dyn Node> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
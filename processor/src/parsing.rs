@@ -15,8 +15,8 @@ limitations under the License.
 */
 
 use crate::error::{spanned_compile_error, CompileError};
-use proc_macro2::{Span, TokenStream};
-use std::collections::HashMap;
+use proc_macro2::{Span, TokenStream, TokenTree};
+use std::collections::{HashMap, HashSet};
 use syn::parse::Parser;
 #[allow(unused_imports)] // somehow rust think this is unused.
 use syn::spanned::Spanned;
@@ -45,6 +45,28 @@ pub fn has_attribute(attrs: &Vec<syn::Attribute>, attr: &str) -> bool {
     attrs.iter().find(|a| is_attribute(a, attr)).is_some()
 }
 
+/// Names of the type and const parameters of `generics`, which lockjaw cannot choose a type for.
+pub fn generic_type_params(generics: &syn::Generics) -> HashSet<String> {
+    generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            syn::GenericParam::Type(type_param) => Some(type_param.ident.to_string()),
+            syn::GenericParam::Const(const_param) => Some(const_param.ident.to_string()),
+            syn::GenericParam::Lifetime(_) => None,
+        })
+        .collect()
+}
+
+/// Whether any identifier in `tokens` is one of `idents`.
+pub fn mentions_idents(tokens: TokenStream, idents: &HashSet<String>) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => idents.contains(&ident.to_string()),
+        TokenTree::Group(group) => mentions_idents(group.stream(), idents),
+        _ => false,
+    })
+}
+
 pub fn get_parenthesized_field_values(
     meta: &Meta,
 ) -> Result<HashMap<String, FieldValue>, TokenStream> {
@@ -74,6 +96,8 @@ pub enum FieldValue {
     Path(Span, syn::Path),
     Array(Span, Vec<FieldValue>),
     FieldValues(Span, HashMap<String, FieldValue>),
+    /// Any other expression, e.g. `key: MyKey::new("a", 3)`.
+    Expr(Span, syn::Expr),
 }
 
 impl FieldValue {
@@ -86,6 +110,7 @@ impl FieldValue {
             FieldValue::Path(ref span, _) => span.clone(),
             FieldValue::Array(ref span, _) => span.clone(),
             FieldValue::FieldValues(ref span, _) => span.clone(),
+            FieldValue::Expr(ref span, _) => span.clone(),
         }
     }
 
@@ -107,6 +132,64 @@ impl FieldValue {
     }
 }
 
+/// Removes `key: [Type, ...]` from the `key: value` pairs of an attribute and parses the types,
+/// since types like `dyn Foo` are not expressions and cannot be parsed as a `FieldValue`.
+pub fn take_type_array(
+    attr: TokenStream,
+    key: &str,
+) -> Result<(TokenStream, Vec<syn::Type>), TokenStream> {
+    let tokens: Vec<TokenTree> = attr.into_iter().collect();
+    for i in 0..tokens.len() {
+        let TokenTree::Ident(ref ident) = tokens[i] else {
+            continue;
+        };
+        if ident != key || (i > 0 && !is_comma(&tokens[i - 1])) {
+            continue;
+        }
+        let Some(TokenTree::Punct(colon)) = tokens.get(i + 1) else {
+            continue;
+        };
+        if colon.as_char() != ':' {
+            continue;
+        }
+        let group = match tokens.get(i + 2) {
+            Some(TokenTree::Group(group))
+                if group.delimiter() == proc_macro2::Delimiter::Bracket =>
+            {
+                group
+            }
+            _ => {
+                return spanned_compile_error(
+                    ident.span(),
+                    &format!("[type, ...] expected for {}", key),
+                )
+            }
+        };
+        let types = syn::punctuated::Punctuated::<syn::Type, syn::Token![,]>::parse_terminated
+            .parse2(group.stream())
+            .map_spanned_compile_error(
+                group.span(),
+                &format!("[type, ...] expected for {}", key),
+            )?;
+        let end = if tokens.get(i + 3).is_some_and(is_comma) {
+            i + 4
+        } else {
+            i + 3
+        };
+        let remaining: TokenStream = tokens[..i]
+            .iter()
+            .chain(tokens[end..].iter())
+            .cloned()
+            .collect();
+        return Ok((remaining, types.into_iter().collect()));
+    }
+    Ok((tokens.into_iter().collect(), Vec::new()))
+}
+
+fn is_comma(token: &TokenTree) -> bool {
+    matches!(token, TokenTree::Punct(punct) if punct.as_char() == ',')
+}
+
 /// Converts #[attr(key1 : "value1", key2 : value2)] to key-value map.
 pub fn get_attribute_field_values(
     attr: TokenStream,
@@ -158,6 +241,15 @@ fn parse_field_value(expr: &syn::Expr, span: Span) -> Result<FieldValue, TokenSt
             )),
             _ => spanned_compile_error(span, &format!("unable to handle literal value {:?}", lit)),
         },
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr: ref inner,
+            ..
+        }) => match parse_field_value(inner, span)? {
+            FieldValue::IntLiteral(span, value) => Ok(FieldValue::IntLiteral(span, -value)),
+            FieldValue::FloatLiteral(span, value) => Ok(FieldValue::FloatLiteral(span, -value)),
+            _ => Ok(FieldValue::Expr(span, expr.clone())),
+        },
         syn::Expr::Path(ref path) => Ok(FieldValue::Path(span, path.path.clone())),
         syn::Expr::Array(ref array) => {
             let mut values: Vec<FieldValue> = Vec::new();
@@ -170,7 +262,7 @@ fn parse_field_value(expr: &syn::Expr, span: Span) -> Result<FieldValue, TokenSt
             span,
             parse_punctuated_field_values(&struct_.fields)?,
         )),
-        _ => spanned_compile_error(span, &format!("invalid field value {:?}", expr)),
+        _ => Ok(FieldValue::Expr(span, expr.clone())),
     }
 }
 
@@ -14,7 +14,7 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use crate::error::CompileError;
+use crate::error::{spanned_compile_error, CompileError};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::spanned::Spanned;
@@ -24,8 +24,12 @@ pub fn handle_qualifier_attribute(
     input: TokenStream,
 ) -> Result<TokenStream, TokenStream> {
     let span = input.span();
-    let item: syn::ItemStruct =
-        syn::parse2(input).map_spanned_compile_error(span, "struct block expected")?;
+    let item: syn::Item =
+        syn::parse2(input).map_spanned_compile_error(span, "struct or enum block expected")?;
+    match item {
+        syn::Item::Struct(_) | syn::Item::Enum(_) => {}
+        _ => return spanned_compile_error(span, "struct or enum block expected"),
+    }
 
     Ok(quote! {
         #item
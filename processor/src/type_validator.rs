@@ -16,32 +16,95 @@ limitations under the License.
 
 use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote, quote_spanned};
+use syn::punctuated::Punctuated;
+use syn::{Token, TypeParamBound};
 
 pub struct TypeValidator {
     token_stream: TokenStream,
+    statements: TokenStream,
 }
 
 impl TypeValidator {
     pub fn new() -> Self {
         TypeValidator {
             token_stream: TokenStream::new(),
+            statements: TokenStream::new(),
         }
     }
 
+    /// Qualifiers can be a struct or an enum variant, which only share the struct pattern syntax.
+    pub fn add_qualifier_path(&mut self, path: &syn::Path, span: Span) {
+        let pattern_check = quote_spanned! {span =>
+            #[allow(unreachable_patterns)]
+            let _ = |qualifier| match qualifier {
+                #path { .. } => {}
+                _ => {}
+            };
+        };
+        let statements = self.statements.clone();
+        self.statements = quote! { #statements #pattern_check }
+    }
+
+    /// Checks the type implements the traits in the bounds, lifetime bounds are ignored.
+    pub fn add_impl_bounds(
+        &mut self,
+        type_: &syn::Type,
+        bounds: &Punctuated<TypeParamBound, Token![+]>,
+        span: Span,
+    ) {
+        let traits = bounds.iter().filter_map(|bound| match bound {
+            TypeParamBound::Trait(trait_) => Some(trait_),
+            _ => None,
+        });
+        let bound_check = quote_spanned! {span =>
+            {
+                fn assert_impl<T: ?Sized #(+ #traits)*>() {}
+                assert_impl::<#type_>();
+            }
+        };
+        let statements = self.statements.clone();
+        self.statements = quote! { #statements #bound_check }
+    }
+
+    /// Checks the type is `Send + Sync`, reporting `message` otherwise.
+    pub fn add_thread_safe(&mut self, type_: &syn::Type, span: Span, message: &str) {
+        let thread_safe_check = quote_spanned! {span =>
+            {
+                #[diagnostic::on_unimplemented(message = #message, label = "not Send + Sync")]
+                trait ThreadSafeContainer {}
+                impl<T: ?Sized + ::lockjaw::__private::std::marker::Send + ::lockjaw::__private::std::marker::Sync> ThreadSafeContainer for T {}
+                fn assert_thread_safe<T: ?Sized + ThreadSafeContainer>() {}
+                assert_thread_safe::<#type_>();
+            }
+        };
+        let statements = self.statements.clone();
+        self.statements = quote! { #statements #thread_safe_check }
+    }
+
     pub fn add_path(&mut self, path: &syn::Path, span: Span) {
-        let type_check = quote_spanned! {span => _ : Box<#path>, };
+        let type_check =
+            quote_spanned! {span => _ : ::lockjaw::__private::std::boxed::Box<#path>, };
         let tokens = self.token_stream.clone();
         self.token_stream = quote! { #tokens #type_check}
     }
 
     pub fn add_path_and_arg(&mut self, path: &syn::Path, span: Span, arg: &syn::Type) {
-        let type_check = quote_spanned! {span => _ : Box<#path<#arg>>, };
+        let type_check =
+            quote_spanned! {span => _ : ::lockjaw::__private::std::boxed::Box<#path<#arg>>, };
+        let tokens = self.token_stream.clone();
+        self.token_stream = quote! { #tokens #type_check}
+    }
+
+    pub fn add_type(&mut self, type_: &syn::Type, span: Span) {
+        let type_check =
+            quote_spanned! {span => _ : ::lockjaw::__private::std::boxed::Box<#type_>, };
         let tokens = self.token_stream.clone();
         self.token_stream = quote! { #tokens #type_check}
     }
 
     pub fn add_dyn_path(&mut self, path: &syn::Path, span: Span) {
-        let type_check = quote_spanned! {span => _ : Box<dyn #path>, };
+        let type_check =
+            quote_spanned! {span => _ : ::lockjaw::__private::std::boxed::Box<dyn #path>, };
         let tokens = self.token_stream.clone();
         self.token_stream = quote! { #tokens #type_check}
     }
@@ -49,9 +112,12 @@ impl TypeValidator {
     pub fn validate(&self, name: String) -> TokenStream {
         let ident = format_ident!("lockjaw_type_validator_{}", name);
         let types = self.token_stream.clone();
+        let statements = self.statements.clone();
         quote! {
             #[doc(hidden)]
-            fn #ident(#types) {}
+            fn #ident(#types) {
+                #statements
+            }
         }
     }
 }
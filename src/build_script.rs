@@ -16,16 +16,34 @@ limitations under the License.
 
 #![allow(dead_code)]
 
-pub(crate) fn build_manifest() {
-    let dep_manifest = lockjaw_common::manifest_parser::build_manifest();
+use crate::BuildScriptOptions;
+use lockjaw_common::environment::{
+    lockjaw_output_dir, normalize_path, LOCKJAW_OUTPUT_DIR_ENV, LOCKJAW_STRICT_PARSE_ENV,
+};
+use lockjaw_common::manifest_parser::source_files;
+use lockjaw_common::timings::LOCKJAW_TIMINGS_ENV;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
 
-    let dep_manifest_path = format!("{}/dep_manifest.json", std::env::var("OUT_DIR").unwrap());
+pub(crate) fn build_manifest(options: &BuildScriptOptions) {
+    let dep_manifest = lockjaw_common::manifest_parser::build_manifest(options.include_tests);
+    let dep_manifest_json =
+        serde_json::to_string_pretty(&dep_manifest).expect("cannot serialize manifest");
 
-    std::fs::write(
-        &dep_manifest_path,
-        serde_json::to_string_pretty(&dep_manifest).expect("cannot serialize manifest"),
-    )
-    .expect("cannot write manifest");
+    let output_dir = lockjaw_output_dir()
+        .expect("OUT_DIR not found, lockjaw::build_script() must be called in build.rs");
+    std::fs::create_dir_all(&output_dir).expect("cannot create lockjaw output dir");
+
+    // content addressed so builds sharing a relocated output dir (sccache, remote builds, multiple
+    // profiles) never read each other's manifest.
+    let mut hasher = DefaultHasher::new();
+    dep_manifest_json.hash(&mut hasher);
+    let dep_manifest_path = output_dir.join(format!("dep_manifest_{:016x}.json", hasher.finish()));
+
+    std::fs::write(&dep_manifest_path, dep_manifest_json).expect("cannot write manifest");
+    remove_stale_manifests(&output_dir, &dep_manifest_path);
+    let dep_manifest_path = normalize_path(&dep_manifest_path);
 
     /*
     log!(
@@ -35,6 +53,37 @@ pub(crate) fn build_manifest() {
     */
     println!(
         "cargo::rustc-env=LOCKJAW_DEP_MANIFEST={}",
-        &dep_manifest_path
-    )
+        dep_manifest_path.display()
+    );
+
+    // once any of these is printed, cargo stops rerunning the build script for every change in the
+    // package, so all inputs must be listed.
+    for env in [
+        LOCKJAW_OUTPUT_DIR_ENV,
+        LOCKJAW_STRICT_PARSE_ENV,
+        LOCKJAW_TIMINGS_ENV,
+    ] {
+        println!("cargo::rerun-if-env-changed={}", env);
+    }
+    for source_file in source_files() {
+        println!("cargo::rerun-if-changed={}", source_file.display());
+    }
+}
+
+/// Removes manifests written by previous runs, so the output dir does not grow with every change.
+/// Failures are ignored since the stale manifests are never read.
+fn remove_stale_manifests(output_dir: &Path, current: &Path) {
+    let Ok(entries) = std::fs::read_dir(output_dir) else {
+        return;
+    };
+    for path in entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+    {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        if file_name.starts_with("dep_manifest_") && file_name.ends_with(".json") && path != current
+        {
+            let _ = std::fs::remove_file(&path);
+        }
+    }
 }
@@ -0,0 +1,98 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::any::{Any, TypeId};
+use std::sync::Mutex;
+
+struct Cast {
+    component: TypeId,
+    target: TypeId,
+    cast: Box<dyn Any + Send>,
+}
+
+static CASTS: Mutex<Vec<Cast>> = Mutex::new(Vec::new());
+
+#[doc(hidden)]
+pub fn register_component_cast<T: ?Sized + 'static>(
+    component: TypeId,
+    cast: for<'a> fn(&'a dyn Any) -> &'a T,
+) {
+    let mut casts = CASTS.lock().unwrap();
+    if casts
+        .iter()
+        .any(|c| c.component == component && c.target == TypeId::of::<T>())
+    {
+        return;
+    }
+    casts.push(Cast {
+        component,
+        target: TypeId::of::<T>(),
+        cast: Box::new(cast),
+    });
+}
+
+/// Recovers the component trait or an [`#[entry_point]`](crate::entry_point) installed in the
+/// component, from the component type-erased as `&dyn Any`.
+///
+/// A component can be type-erased by adding a `fn as_any(&self) -> &dyn Any` method, which returns
+/// the component itself. This allows frameworks holding components of different types to recover
+/// them without `unsafe` code.
+///
+/// Returns `None` if `component` is not a component, or `T` is not the component trait or an entry
+/// point installed in it.
+///
+/// Only available for [`#[component]`](crate::component) and
+/// [`#[define_component]`](crate::define_component), not subcomponents.
+///
+/// ```
+/// # use lockjaw::*;
+/// # use std::any::Any;
+/// #[define_component]
+/// pub trait MyComponent {
+///     fn as_any(&self) -> &dyn Any;
+/// }
+///
+/// #[entry_point(install_in: MyComponent)]
+/// pub trait MyEntryPoint {
+///     fn i(&self) -> i32;
+/// }
+/// # struct MyModule {}
+/// # #[module(install_in: MyComponent)]
+/// # impl MyModule {
+/// #     #[provides]
+/// #     pub fn provide_i(&self) -> i32 {
+/// #         42
+/// #     }
+/// # }
+///
+/// pub fn main() {
+///     let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+///     let any: &dyn Any = component.as_any();
+///     let entry_point = downcast_component::<dyn MyEntryPoint>(any).unwrap();
+///     assert_eq!(entry_point.i(), 42);
+/// }
+/// lockjaw::epilogue!();
+/// ```
+pub fn downcast_component<T: ?Sized + 'static>(component: &dyn Any) -> Option<&T> {
+    let cast = *CASTS
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|c| c.component == component.type_id() && c.target == TypeId::of::<T>())?
+        .cast
+        .downcast_ref::<for<'a> fn(&'a dyn Any) -> &'a T>()?;
+    Some(cast(component))
+}
@@ -13,7 +13,9 @@ WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 See the License for the specific language governing permissions and
 limitations under the License.
 */
-use std::ops::Deref;
+use alloc::boxed::Box;
+use alloc::sync::{Arc, Weak};
+use core::ops::Deref;
 
 /// "Component Lifetime". Wrapper around an injection that may be scoped(owned by the component) or freestanding(owned by
 /// the item injecting it). Deref to access the content.
@@ -25,9 +27,44 @@ use std::ops::Deref;
 /// # Lifetime
 ///
 /// `Cl`\'s lifetime is bounded by the component providing it.
+///
+/// # Thread safety
+///
+/// In a [`thread_safe`](crate::component#thread_safe) component scoped bindings are stored in an
+/// [`Arc`], and injected as `Cl::Shared`. [`to_arc()`](#method.to_arc) returns a handle to the
+/// instance which can outlive the borrow of the component, without cloning the instance itself.
+/// [`downgrade()`](#method.downgrade) returns a [`WeakCl`] which does not keep the instance alive.
 pub enum Cl<'a, T: ?Sized + 'a> {
     Val(Box<T>),
     Ref(&'a T),
+    Shared(Arc<T>),
+}
+
+impl<T: ?Sized> Cl<'_, T> {
+    /// Returns the [`Arc`] the instance is stored in, or `None` if the instance is not shared
+    /// through an `Arc`.
+    ///
+    /// The instance is shared if it is scoped to a
+    /// [`thread_safe`](crate::component#thread_safe) component.
+    pub fn to_arc(&self) -> Option<Arc<T>> {
+        match self {
+            Cl::Shared(arc) => Some(Arc::clone(arc)),
+            _ => None,
+        }
+    }
+
+    /// Returns a [`WeakCl`] to the instance, or `None` if the instance is not shared through an
+    /// `Arc`.
+    ///
+    /// Unlike [`to_arc()`](#method.to_arc), the `WeakCl` does not keep the instance alive after
+    /// the component is dropped, so it can be held by objects the instance itself owns, such as
+    /// listener registries, without creating a reference cycle.
+    pub fn downgrade(&self) -> Option<WeakCl<T>> {
+        match self {
+            Cl::Shared(arc) => Some(WeakCl::new(Arc::downgrade(arc))),
+            _ => None,
+        }
+    }
 }
 
 impl<T: ?Sized> Deref for Cl<'_, T> {
@@ -37,6 +74,35 @@ impl<T: ?Sized> Deref for Cl<'_, T> {
         match self {
             Cl::Val(val) => val.deref(),
             Cl::Ref(r) => r,
+            Cl::Shared(arc) => arc.deref(),
+        }
+    }
+}
+
+/// Weak reference to a scoped binding stored in an [`Arc`], which does not keep the instance alive.
+///
+/// Created with [`Cl::downgrade()`], or injected as `WeakCl<T>` for a binding scoped in a
+/// [`thread_safe`](crate::component#thread_safe) component, or scoped with `container: Arc`.
+pub struct WeakCl<T: ?Sized> {
+    weak: Weak<T>,
+}
+
+impl<T: ?Sized> WeakCl<T> {
+    #[doc(hidden)]
+    pub fn new(weak: Weak<T>) -> Self {
+        WeakCl { weak }
+    }
+
+    /// Returns the instance, or `None` if the component owning it has been dropped.
+    pub fn upgrade(&self) -> Option<Arc<T>> {
+        self.weak.upgrade()
+    }
+}
+
+impl<T: ?Sized> Clone for WeakCl<T> {
+    fn clone(&self) -> Self {
+        WeakCl {
+            weak: Weak::clone(&self.weak),
         }
     }
 }
@@ -0,0 +1,119 @@
+/*
+Copyright 2020 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! [`Clock`] abstracts the current time so time dependent logic can be tested.
+//!
+//! List [`SystemClockModule`] in the production component, and [`ManualClockModule`] in the test
+//! component to control the time with [`ManualClock`].
+//!
+//! ```
+//! # use lockjaw::*;
+//! use lockjaw::contrib::clock::{Clock, ManualClock, ManualClockModule};
+//! use std::time::{Duration, SystemTime};
+//!
+//! #[component(modules: [ManualClockModule])]
+//! pub trait TestComponent {
+//!     fn clock(&self) -> Cl<dyn Clock>;
+//!     fn manual_clock(&self) -> &ManualClock;
+//! }
+//!
+//! pub fn main() {
+//!     let component: Box<dyn TestComponent> = <dyn TestComponent>::new();
+//!     component.manual_clock().advance(Duration::from_secs(60));
+//!     assert_eq!(
+//!         component.clock().now(),
+//!         SystemTime::UNIX_EPOCH + Duration::from_secs(60)
+//!     );
+//! }
+//! epilogue!();
+//! ```
+
+use lockjaw::{injectable, module, Cl, Singleton};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+/// Source of the current time.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+}
+
+/// [`Clock`] reading the system time.
+pub struct SystemClock {}
+
+#[injectable]
+impl SystemClock {
+    #[inject]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// [`Clock`] that only moves when told to, starting at [`SystemTime::UNIX_EPOCH`].
+///
+/// Scoped to [`Singleton`], so the instance requested by a test is the one bound to `Clock`.
+pub struct ManualClock {
+    now: Mutex<SystemTime>,
+}
+
+#[injectable(scope: Singleton)]
+impl ManualClock {
+    #[inject]
+    pub fn new() -> Self {
+        Self {
+            now: Mutex::new(SystemTime::UNIX_EPOCH),
+        }
+    }
+
+    /// Moves the time forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+
+    /// Sets the current time.
+    pub fn set(&self, now: SystemTime) {
+        *self.now.lock().unwrap() = now;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> SystemTime {
+        *self.now.lock().unwrap()
+    }
+}
+
+/// Binds `Cl<dyn Clock>` to [`SystemClock`].
+pub struct SystemClockModule {}
+
+#[module]
+impl SystemClockModule {
+    #[binds]
+    pub fn bind_clock(_impl: SystemClock) -> Cl<dyn Clock> {}
+}
+
+/// Binds `Cl<dyn Clock>` to the [`ManualClock`] of the component.
+pub struct ManualClockModule {}
+
+#[module]
+impl ManualClockModule {
+    #[binds]
+    pub fn bind_clock(_impl: &ManualClock) -> Cl<dyn Clock> {}
+}
@@ -0,0 +1,23 @@
+/*
+Copyright 2020 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Reusable bindings for common needs, which also serve as references of how to package modules
+//! for other crates.
+//!
+//! The build script reads lockjaw as a dependency, so the [`#[module]`](crate::module)s here can be
+//! listed in [`modules`](crate::component#modules) like the modules of any other crate.
+
+pub mod clock;
@@ -0,0 +1,115 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+/// Borrows a multibinding collection that is created once and cached in the component.
+///
+/// Requesting `Vec<T>` creates a new `Vec` each time, calling every `#[into_vec]` and
+/// `#[elements_into_vec]` binding of `T`. `Contributions<'_, T>` instead collects them on the first
+/// request and lends out the same collection afterwards, so hot paths iterating plugins do not
+/// allocate on each call.
+///
+/// Since the collection is shared, the contributed bindings are only created once, similar to
+/// [`Lazy`](crate::Lazy).
+///
+/// ```
+/// # use lockjaw::*;
+/// pub struct MyModule {}
+///
+/// #[module]
+/// impl MyModule {
+///     #[provides]
+///     #[into_vec]
+///     pub fn provide_foo() -> String {
+///         "foo".to_owned()
+///     }
+///
+///     #[provides]
+///     #[into_vec]
+///     pub fn provide_bar() -> String {
+///         "bar".to_owned()
+///     }
+/// }
+///
+/// #[component(modules: [MyModule])]
+/// pub trait MyComponent {
+///     fn strings(&self) -> Contributions<'_, String>;
+/// }
+///
+/// pub fn main() {
+///     let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+///     let strings = component.strings();
+///     assert_eq!(strings.len(), 2);
+///     assert!(strings.iter().any(|s| s == "foo"));
+///     assert!(std::ptr::eq(strings.as_slice(), component.strings().as_slice()));
+/// }
+/// lockjaw::epilogue!();
+/// ```
+pub struct Contributions<'c, T> {
+    items: &'c [T],
+}
+
+impl<'c, T> Contributions<'c, T> {
+    #[doc(hidden)]
+    pub fn new(items: &'c [T]) -> Self {
+        Contributions { items }
+    }
+
+    pub fn iter(&self) -> core::slice::Iter<'c, T> {
+        self.items.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&'c T> {
+        self.items.get(index)
+    }
+
+    pub fn as_slice(&self) -> &'c [T] {
+        self.items
+    }
+}
+
+impl<T> Clone for Contributions<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Contributions<'_, T> {}
+
+impl<'c, T> IntoIterator for Contributions<'c, T> {
+    type Item = &'c T;
+    type IntoIter = core::slice::Iter<'c, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+impl<'a, 'c, T> IntoIterator for &'a Contributions<'c, T> {
+    type Item = &'c T;
+    type IntoIter = core::slice::Iter<'c, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
@@ -0,0 +1,212 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::any::{Any, TypeId};
+use std::sync::Mutex;
+
+/// Values of `T` produced by factories registered with [`submit_deferred!`](crate::submit_deferred),
+/// by crates the component may not know about when it is generated.
+///
+/// Crates that may or may not be linked (e.g. optional DLC) cannot participate in the compile time
+/// dependency graph, since the root crate has to know about their `#[module]`s when generating the
+/// component. Instead they can submit factories for `T` against a
+/// [`#[define_component]`](crate::define_component), which are registered when the program starts
+/// if the crate is linked, and anything in the component can request `Deferred<T>` to collect
+/// whatever has been registered.
+///
+/// This is a registry of values, not deferred modules: `#[module]`s of a crate the component does
+/// not know about are never installed, so their bindings cannot be requested or override bindings
+/// of the component, and `Deferred<T>` is the only way to reach what the crate contributes.
+///
+/// Each factory receives the component, and can use [`#[entry_point]`](crate::entry_point)s declared
+/// in crates the root does know about to access bindings.
+///
+/// Registered factories are called each time `Deferred<T>` is requested. Factories for code loaded
+/// after startup can be added with [`register_deferred()`].
+///
+/// `Deferred<T>` is not available in subcomponents.
+///
+/// ```
+/// # use lockjaw::*;
+/// pub trait Plugin {
+///     fn name(&self) -> String;
+/// }
+///
+/// #[define_component]
+/// pub trait MyComponent {
+///     fn plugins(&self) -> Deferred<Box<dyn Plugin>>;
+/// }
+///
+/// // in a crate that may not be linked
+/// struct DlcPlugin {}
+///
+/// impl Plugin for DlcPlugin {
+///     fn name(&self) -> String {
+///         "dlc".to_owned()
+///     }
+/// }
+///
+/// lockjaw::submit_deferred!(dyn MyComponent, Box<dyn Plugin>, |_component| {
+///     Box::new(DlcPlugin {})
+/// });
+///
+/// pub fn main() {
+///     let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+///     let names: Vec<String> = component.plugins().iter().map(|p| p.name()).collect();
+///     assert_eq!(names, vec!["dlc"]);
+/// }
+/// lockjaw::epilogue!();
+/// ```
+pub struct Deferred<T> {
+    items: Vec<T>,
+}
+
+impl<T> Deferred<T> {
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.items.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.items
+    }
+}
+
+impl<T> IntoIterator for Deferred<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+/// Registers a factory contributing to [`Deferred<T>`](Deferred) in a component when the program
+/// starts, if the crate containing it is linked.
+///
+/// ```ignore
+/// lockjaw::submit_deferred!(dyn MyComponent, Box<dyn Plugin>, |component| {
+///     Box::new(MyPlugin::new(component))
+/// });
+/// ```
+///
+/// The component should be `dyn` of a [`#[define_component]`](crate::define_component) trait, and the
+/// factory a closure not capturing anything, receiving `&dyn MyComponent`.
+///
+/// A crate that nothing refers to may be dropped by the linker even if it is a dependency, `use` it
+/// (e.g. `use my_dlc as _;`) in the binary to make sure its factories are registered.
+#[macro_export]
+macro_rules! submit_deferred {
+    ($component:ty, $item:ty, $factory:expr $(,)?) => {
+        const _: () = {
+            struct Factory;
+
+            impl $crate::DeferredFactory for Factory {
+                type Component = $component;
+                type Item = $item;
+
+                fn create(component: &Self::Component) -> Self::Item {
+                    let factory: fn(&Self::Component) -> Self::Item = $factory;
+                    factory(component)
+                }
+            }
+
+            $crate::__private::inventory::submit! {
+                $crate::DeferredRegistration::of::<Factory>()
+            }
+        };
+    };
+}
+
+#[doc(hidden)]
+pub trait DeferredFactory: 'static {
+    type Component: ?Sized + 'static;
+    type Item: 'static;
+
+    fn create(component: &Self::Component) -> Self::Item;
+}
+
+#[doc(hidden)]
+pub struct DeferredRegistration {
+    component: fn() -> TypeId,
+    item: fn() -> TypeId,
+    factory: fn() -> Box<dyn Any>,
+}
+
+impl DeferredRegistration {
+    pub const fn of<F: DeferredFactory>() -> Self {
+        DeferredRegistration {
+            component: TypeId::of::<F::Component>,
+            item: TypeId::of::<F::Item>,
+            factory: erased_factory::<F>,
+        }
+    }
+}
+
+fn erased_factory<F: DeferredFactory>() -> Box<dyn Any> {
+    Box::new(F::create as fn(&F::Component) -> F::Item)
+}
+
+inventory::collect!(DeferredRegistration);
+
+struct Registration {
+    component: TypeId,
+    item: TypeId,
+    factory: Box<dyn Any + Send>,
+}
+
+static REGISTRY: Mutex<Vec<Registration>> = Mutex::new(Vec::new());
+
+/// Registers a factory contributing to [`Deferred<T>`](Deferred) in the component `C` at runtime,
+/// which should be `dyn` of a [`#[define_component]`](crate::define_component) trait.
+///
+/// Prefer [`submit_deferred!`](crate::submit_deferred), which registers the factory as soon as the
+/// crate is linked. This is for code that is only loaded later.
+pub fn register_deferred<C: ?Sized + 'static, T: 'static>(factory: fn(&C) -> T) {
+    REGISTRY.lock().unwrap().push(Registration {
+        component: TypeId::of::<C>(),
+        item: TypeId::of::<T>(),
+        factory: Box::new(factory),
+    });
+}
+
+#[doc(hidden)]
+pub fn collect_deferred<C: ?Sized + 'static, T: 'static>(component: &C) -> Deferred<T> {
+    let mut factories: Vec<fn(&C) -> T> = inventory::iter::<DeferredRegistration>
+        .into_iter()
+        .filter(|r| (r.component)() == TypeId::of::<C>() && (r.item)() == TypeId::of::<T>())
+        .map(|r| *(r.factory)().downcast::<fn(&C) -> T>().unwrap())
+        .collect();
+    // copy the factories out so they can register more items without deadlocking.
+    factories.extend(
+        REGISTRY
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| r.component == TypeId::of::<C>() && r.item == TypeId::of::<T>())
+            .map(|r| *r.factory.downcast_ref::<fn(&C) -> T>().unwrap()),
+    );
+    Deferred {
+        items: factories.iter().map(|factory| factory(component)).collect(),
+    }
+}
@@ -0,0 +1,114 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// Lazily iterates a `Vec<T>` multibinding, only creating each element when it is reached.
+///
+/// Requesting `Vec<T>` calls every `#[into_vec]` and `#[elements_into_vec]` binding of `T` upfront.
+/// `Elements<'_, T>` instead calls each binding when the iteration reaches it, so consumers that
+/// stop early (e.g. with [`find()`](Iterator::find)) do not create the remaining elements. The
+/// elements are not cached, iterating again creates them again.
+///
+/// Component provisions can also request it as `impl Iterator<Item = T> + '_`.
+///
+/// ```
+/// # use lockjaw::*;
+/// pub struct MyModule {}
+///
+/// #[module]
+/// impl MyModule {
+///     #[provides]
+///     #[into_vec]
+///     pub fn provide_foo() -> String {
+///         "foo".to_owned()
+///     }
+///
+///     #[provides]
+///     #[into_vec]
+///     pub fn provide_bar() -> String {
+///         "bar".to_owned()
+///     }
+/// }
+///
+/// #[component(modules: [MyModule])]
+/// pub trait MyComponent {
+///     fn strings(&self) -> impl Iterator<Item = String> + '_;
+/// }
+///
+/// pub fn main() {
+///     let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+///     let mut strings: Vec<String> = component.strings().collect();
+///     strings.sort();
+///     assert_eq!(strings, vec!["bar", "foo"]);
+/// }
+/// lockjaw::epilogue!();
+/// ```
+pub struct Elements<'c, T> {
+    next: usize,
+    len: usize,
+    contribution: Box<dyn Fn(usize) -> ElementsContribution<T> + 'c>,
+    pending: Option<alloc::vec::IntoIter<T>>,
+}
+
+/// A single contribution to a `Vec<T>` multibinding.
+#[doc(hidden)]
+pub enum ElementsContribution<T> {
+    /// `#[into_vec]`
+    Element(T),
+    /// `#[elements_into_vec]`
+    Elements(Vec<T>),
+}
+
+impl<'c, T> Elements<'c, T> {
+    /// `contribution` is called with each index in `0..len` as the iteration reaches it.
+    #[doc(hidden)]
+    pub fn new(len: usize, contribution: impl Fn(usize) -> ElementsContribution<T> + 'c) -> Self {
+        Elements {
+            next: 0,
+            len,
+            contribution: Box::new(contribution),
+            pending: None,
+        }
+    }
+}
+
+impl<T> Iterator for Elements<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            if let Some(ref mut pending) = self.pending {
+                if let Some(element) = pending.next() {
+                    return Some(element);
+                }
+                self.pending = None;
+            }
+            if self.next == self.len {
+                return None;
+            }
+            let index = self.next;
+            self.next += 1;
+            match (self.contribution)(index) {
+                ElementsContribution::Element(element) => return Some(element),
+                ElementsContribution::Elements(elements) => {
+                    self.pending = Some(elements.into_iter())
+                }
+            }
+        }
+    }
+}
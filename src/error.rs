@@ -0,0 +1,85 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::fmt::{Display, Formatter};
+
+#[cfg(not(feature = "std"))]
+use core::error::Error as StdError;
+#[cfg(feature = "std")]
+use std::error::Error as StdError;
+
+/// Reasons [`try_build()`](crate::component#try_build) can fail to create a component.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The component was not generated by [`epilogue!()`](crate::epilogue), for example because it
+    /// was pruned by `prune_components`, or is built inside a doctest.
+    NotGenerated {
+        /// Name of the component trait.
+        component: &'static str,
+    },
+    /// A binding failed to initialize while the component was being built.
+    Binding {
+        /// Name of the binding that failed.
+        binding: String,
+        source: Box<dyn StdError + Send + Sync>,
+    },
+    /// A type listed in `instance_bindings` was not added to the
+    /// [`Instances`](crate::Instances) passed to `build_with()`.
+    MissingInstance {
+        /// Name of the component trait.
+        component: &'static str,
+        /// The type without an instance.
+        instance: &'static str,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::NotGenerated { component } => write!(
+                f,
+                "{} was not generated by lockjaw::epilogue!(), it may have been pruned by \
+                `prune_components` or built inside a doctest",
+                component
+            ),
+            Error::Binding { binding, source } => {
+                write!(f, "unable to initialize {}: {}", binding, source)
+            }
+            Error::MissingInstance {
+                component,
+                instance,
+            } => write!(
+                f,
+                "{} is in the instance_bindings of {}, but is not in the Instances passed to \
+                build_with()",
+                instance, component
+            ),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::NotGenerated { .. } => None,
+            Error::Binding { source, .. } => Some(source.as_ref()),
+            Error::MissingInstance { .. } => None,
+        }
+    }
+}
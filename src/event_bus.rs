@@ -0,0 +1,115 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use crate::Cl;
+use alloc::vec::Vec;
+
+/// Receives events published to an [`EventBus<E>`](EventBus).
+///
+/// Subscribers are contributed by binding them into `Vec<Cl<dyn Subscriber<E>>>` with
+/// [`#[into_vec]`](crate::module_attributes::into_vec).
+pub trait Subscriber<E> {
+    fn on_event(&self, event: &E);
+}
+
+impl<E, F> Subscriber<E> for F
+where
+    F: Fn(&E),
+{
+    fn on_event(&self, event: &E) {
+        self(event)
+    }
+}
+
+/// Delivers events of type `E` to every [`Subscriber<E>`](Subscriber) bound in the component.
+///
+/// Requesting `EventBus<E>` collects the `#[into_vec]` bindings of `Cl<dyn Subscriber<E>>`, so
+/// components can notify listeners such as startup hooks without collecting them by hand.
+/// Requesting `&EventBus<E>` shares a single bus stored in the component, which is created with the
+/// subscribers the first time it is requested.
+///
+/// ```
+/// # use lockjaw::*;
+/// # use std::cell::RefCell;
+/// pub struct Started {}
+///
+/// pub struct Logger {
+///     log: RefCell<Vec<String>>,
+/// }
+///
+/// #[injectable(scope: crate::MyComponent)]
+/// impl Logger {
+///     #[inject]
+///     pub fn new() -> Self {
+///         Self {
+///             log: RefCell::new(Vec::new()),
+///         }
+///     }
+/// }
+///
+/// impl Subscriber<Started> for Logger {
+///     fn on_event(&self, _event: &Started) {
+///         self.log.borrow_mut().push("started".to_owned());
+///     }
+/// }
+///
+/// pub struct MyModule {}
+///
+/// #[module]
+/// impl MyModule {
+///     #[binds]
+///     #[into_vec]
+///     pub fn bind_logger(impl_: &crate::Logger) -> Cl<dyn Subscriber<crate::Started>> {}
+/// }
+///
+/// #[component(modules: [MyModule])]
+/// pub trait MyComponent {
+///     fn event_bus(&self) -> &EventBus<crate::Started>;
+///     fn logger(&self) -> &crate::Logger;
+/// }
+///
+/// pub fn main() {
+///     let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+///     component.event_bus().publish(&Started {});
+///     assert_eq!(*component.logger().log.borrow(), vec!["started"]);
+/// }
+/// lockjaw::epilogue!();
+/// ```
+pub struct EventBus<'c, E> {
+    subscribers: Vec<Cl<'c, dyn Subscriber<E> + 'c>>,
+}
+
+impl<'c, E> EventBus<'c, E> {
+    #[doc(hidden)]
+    pub fn new(subscribers: Vec<Cl<'c, dyn Subscriber<E> + 'c>>) -> Self {
+        EventBus { subscribers }
+    }
+
+    /// Delivers `event` to every subscriber.
+    pub fn publish(&self, event: &E) {
+        for subscriber in &self.subscribers {
+            subscriber.on_event(event);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.subscribers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.subscribers.is_empty()
+    }
+}
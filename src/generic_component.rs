@@ -0,0 +1,53 @@
+/*
+Copyright 2020 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use std::any::TypeId;
+use std::sync::Mutex;
+
+/// Builders of each instantiation of a generic component, keyed by the `TypeId` of
+/// `dyn Component<T>`.
+///
+/// A non-generic component stores the address of its builder in a `static mut`, which cannot be
+/// generic, so the root crate registers the builder of every instantiation here instead.
+#[doc(hidden)]
+pub struct GenericComponentBuilders {
+    builders: Mutex<Vec<(TypeId, usize)>>,
+}
+
+impl GenericComponentBuilders {
+    pub const fn new() -> Self {
+        GenericComponentBuilders {
+            builders: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn register(&self, component: TypeId, builder: *const ()) {
+        let mut builders = self.builders.lock().unwrap();
+        if builders.iter().any(|(id, _)| *id == component) {
+            return;
+        }
+        builders.push((component, builder as usize));
+    }
+
+    pub fn get(&self, component: TypeId) -> Option<*const ()> {
+        self.builders
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(id, _)| *id == component)
+            .map(|(_, builder)| *builder as *const ())
+    }
+}
@@ -0,0 +1,77 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use alloc::vec::Vec;
+
+/// Names of the modules installed in the component, available in every component without binding
+/// it.
+///
+/// The names are the paths of the modules (including builder modules), sorted so the output is
+/// stable, and can be logged at startup to show how the component is composed. A
+/// [subcomponent](crate::subcomponent) only lists its own modules.
+///
+/// ```
+/// # use lockjaw::*;
+/// pub struct MyModule {}
+///
+/// #[module]
+/// impl MyModule {
+///     #[provides]
+///     pub fn provide_string() -> String {
+///         "foo".to_owned()
+///     }
+/// }
+///
+/// #[component(modules: [MyModule])]
+/// pub trait MyComponent {
+///     fn installed_modules(&self) -> InstalledModules;
+/// }
+///
+/// pub fn main() {
+///     let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+///     let installed_modules = component.installed_modules();
+///     assert_eq!(installed_modules.len(), 1);
+///     assert!(installed_modules.names()[0].ends_with("::MyModule"));
+/// }
+/// lockjaw::epilogue!();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledModules {
+    names: Vec<&'static str>,
+}
+
+impl InstalledModules {
+    #[doc(hidden)]
+    pub fn new(names: Vec<&'static str>) -> Self {
+        InstalledModules { names }
+    }
+
+    pub fn names(&self) -> &[&'static str] {
+        &self.names
+    }
+
+    pub fn into_vec(self) -> Vec<&'static str> {
+        self.names
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
@@ -0,0 +1,97 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::any::{Any, TypeId};
+
+/// Pre-built values passed to `<dyn Component>::build_with()`, for the types listed in the
+/// `instance_bindings` of the [component](crate::component#instance_bindings).
+///
+/// Values are keyed by their type. A trait object is added with
+/// [`with_boxed()`](Instances::with_boxed), specifying the trait object type.
+///
+/// ```
+/// # use lockjaw::*;
+/// pub trait Greeter {
+///     fn greet(&self) -> String;
+/// }
+///
+/// pub struct Config {
+///     pub name: String,
+/// }
+///
+/// struct GreeterImpl {}
+///
+/// impl Greeter for GreeterImpl {
+///     fn greet(&self) -> String {
+///         "hello".to_owned()
+///     }
+/// }
+///
+/// #[component(instance_bindings: [crate::Config, dyn crate::Greeter])]
+/// pub trait MyComponent {
+///     fn config(&self) -> &crate::Config;
+///     fn greeter(&self) -> Cl<dyn crate::Greeter>;
+/// }
+///
+/// pub fn main() {
+///     let component: Box<dyn MyComponent> = <dyn MyComponent>::build_with(
+///         Instances::new()
+///             .with(Config {
+///                 name: "foo".to_owned(),
+///             })
+///             .with_boxed::<dyn Greeter>(Box::new(GreeterImpl {})),
+///     );
+///     assert_eq!(component.config().name, "foo");
+///     assert_eq!(component.greeter().greet(), "hello");
+/// }
+/// lockjaw::epilogue!();
+/// ```
+#[derive(Debug, Default)]
+pub struct Instances {
+    values: Vec<(TypeId, Box<dyn Any>)>,
+}
+
+impl Instances {
+    pub fn new() -> Self {
+        Instances { values: Vec::new() }
+    }
+
+    /// Adds `value`, replacing any value previously added for `T`.
+    pub fn with<T: 'static>(self, value: T) -> Self {
+        self.with_boxed::<T>(Box::new(value))
+    }
+
+    /// Adds a boxed `value`, replacing any value previously added for `T`. `T` can be a trait
+    /// object, e.g. `instances.with_boxed::<dyn Foo>(Box::new(FooImpl {}))`.
+    pub fn with_boxed<T: ?Sized + 'static>(mut self, value: Box<T>) -> Self {
+        self.values
+            .retain(|(type_id, _)| *type_id != TypeId::of::<T>());
+        self.values.push((TypeId::of::<T>(), Box::new(value)));
+        self
+    }
+
+    #[doc(hidden)]
+    pub fn take<T: ?Sized + 'static>(&mut self) -> Option<Box<T>> {
+        let index = self
+            .values
+            .iter()
+            .position(|(type_id, _)| *type_id == TypeId::of::<T>())?;
+        let (_, value) = self.values.swap_remove(index);
+        value.downcast::<Box<T>>().ok().map(|value| *value)
+    }
+}
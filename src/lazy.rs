@@ -27,6 +27,9 @@ use crate::{Once, Provider};
 ///
 /// If multiple instances of the object is needed, use [`Provider<T>`](Provider) instead
 ///
+/// `Lazy<&T>` and `Lazy<Cl<T>>` defers retrieving a [scoped](crate::injectable#scope) binding, so it
+/// is not created until it is actually used.
+///
 /// ```
 /// # use lockjaw::{epilogue, injectable, module, component, Cl, Lazy};
 /// # use std::cell::RefCell;
@@ -104,7 +107,7 @@ impl<'a, T> Lazy<'a, T> {
     }
 
     /// Creates or retrieves a cached instance and returns a reference to it.
-    pub fn get(&'a self) -> &'a T {
-        self.value.get(|| self.provider.get())
+    pub fn get(&self) -> &T {
+        self.value.get_or_init(|| self.provider.get())
     }
 }
@@ -15,8 +15,14 @@ limitations under the License.
 */
 
 #![allow(stable_features)]
+#![cfg_attr(not(feature = "std"), no_std)]
 #![doc = include_str ! ("../README.md")]
 
+extern crate alloc;
+// lets the lockjaw attributes in `contrib` refer to `::lockjaw`.
+extern crate self as lockjaw;
+
+#[cfg(feature = "std")]
 mod build_script;
 mod lazy;
 
@@ -32,6 +38,9 @@ pub use lockjaw_processor::define_component;
 #[doc = include_str ! ("define_subcomponent.md")]
 pub use lockjaw_processor::define_subcomponent;
 
+#[doc = include_str ! ("component_fragment.md")]
+pub use lockjaw_processor::component_fragment;
+
 pub mod component_attributes;
 
 #[doc = include_str ! ("entry_point.md")]
@@ -59,6 +68,9 @@ pub mod module_attributes;
 #[doc = include_str ! ("qualifier.md")]
 pub use lockjaw_processor::qualifier;
 
+#[doc = include_str ! ("register_manifest.md")]
+pub use lockjaw_processor::register_manifest;
+
 #[doc(hidden)]
 pub use lockjaw_processor::private_root_epilogue;
 #[doc(hidden)]
@@ -66,11 +78,69 @@ pub use lockjaw_processor::private_test_epilogue;
 
 mod component_lifetime;
 
-pub use component_lifetime::Cl;
+pub use component_lifetime::{Cl, WeakCl};
 
 mod once;
 pub use once::Once;
 
+#[cfg(feature = "std")]
+mod component_any;
+#[cfg(feature = "std")]
+pub use component_any::downcast_component;
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub use component_any::register_component_cast;
+
+#[cfg(feature = "std")]
+mod generic_component;
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub use generic_component::GenericComponentBuilders;
+
+#[cfg(feature = "std")]
+mod deferred;
+#[doc(hidden)]
+#[cfg(feature = "std")]
+pub use deferred::{collect_deferred, DeferredFactory, DeferredRegistration};
+#[cfg(feature = "std")]
+pub use deferred::{register_deferred, Deferred};
+
+mod contributions;
+pub use contributions::Contributions;
+mod elements;
+pub use elements::Elements;
+#[doc(hidden)]
+pub use elements::ElementsContribution;
+
+mod error;
+pub use error::Error;
+
+mod provisioned;
+pub use provisioned::Provisioned;
+
+mod event_bus;
+pub use event_bus::{EventBus, Subscriber};
+
+mod post_build;
+pub use post_build::PostBuildListener;
+
+mod installed_modules;
+pub use installed_modules::InstalledModules;
+
+mod instances;
+pub use instances::Instances;
+
+#[cfg(feature = "tokio")]
+pub mod task_local;
+#[cfg(feature = "tokio")]
+pub use task_local::current;
+
+#[cfg(feature = "tower")]
+pub mod tower;
+
+#[cfg(feature = "std")]
+pub mod contrib;
+
 /// Function that must be called inside the
 /// [cargo build script](https://doc.rust-lang.org/cargo/reference/build-scripts.html) to set up the
 /// lockjaw environment in a binary crate.
@@ -83,15 +153,82 @@ pub use once::Once;
 ///     lockjaw::build_script();
 /// }
 /// ```
+#[cfg(feature = "std")]
 pub fn build_script() {
-    build_script::build_manifest()
+    build_script_with(BuildScriptOptions::default())
+}
+
+/// Options for [`build_script_with()`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct BuildScriptOptions {
+    /// Whether to gather bindings for `#[cfg(test)]` builds, including `[dev-dependencies]`.
+    /// Crates that never use lockjaw in tests can set this to `false` to skip the work, and tests
+    /// will be built with the regular bindings instead.
+    ///
+    /// Defaults to `true`.
+    pub include_tests: bool,
+}
+
+#[cfg(feature = "std")]
+impl Default for BuildScriptOptions {
+    fn default() -> Self {
+        BuildScriptOptions {
+            include_tests: true,
+        }
+    }
+}
+
+/// [`build_script()`] with additional options.
+///
+/// ```
+/// // build.rs
+/// fn main() {
+///     lockjaw::build_script_with(lockjaw::BuildScriptOptions {
+///         include_tests: false,
+///     });
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub fn build_script_with(options: BuildScriptOptions) {
+    build_script::build_manifest(&options)
 }
 
 mod provider;
 
+#[cfg(feature = "std")]
+pub mod testing;
+
 pub use provider::Provider;
 
 pub use lazy::Lazy;
 
 #[doc = include_str ! ("singleton.md")]
 pub trait Singleton {}
+
+#[doc = include_str ! ("from_parent.md")]
+pub struct FromParent {}
+
+/// Paths used by generated code, so it compiles in both `std` and `no_std` crates.
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "std")]
+    pub use ::inventory;
+    #[cfg(feature = "std")]
+    pub use ::std;
+
+    /// The subset of `std` generated code refers to, assembled from `core` and `alloc`.
+    #[cfg(not(feature = "std"))]
+    pub mod std {
+        pub use ::alloc::{borrow, boxed, collections, fmt, format, rc, slice, str, string, vec};
+        pub use ::core::{
+            any, cell, cmp, convert, default, hash, include, iter, marker, mem, ops, option, panic,
+            ptr, result, time, unimplemented, unreachable,
+        };
+
+        pub mod sync {
+            pub use ::alloc::sync::*;
+            pub use ::core::sync::atomic;
+        }
+    }
+}
@@ -25,9 +25,15 @@ pub use lockjaw_processor::module_provides as provides;
 #[doc = include_str ! ("binds.md")]
 pub use lockjaw_processor::module_binds as binds;
 
+#[doc = include_str ! ("binds_all.md")]
+pub use lockjaw_processor::module_binds_all as binds_all;
+
 #[doc = include_str ! ("binds_option_of.md")]
 pub use lockjaw_processor::module_binds_option_of as binds_option_of;
 
+#[doc = include_str ! ("binds_decorator.md")]
+pub use lockjaw_processor::module_binds_decorator as binds_decorator;
+
 #[doc = include_str ! ("multibinds.md")]
 pub use lockjaw_processor::module_multibinds as multibinds;
 
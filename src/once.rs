@@ -14,24 +14,67 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use std::cell::UnsafeCell;
+use core::cell::UnsafeCell;
 
-/// once
-#[doc(hidden)]
+#[cfg(feature = "std")]
+use std::sync::Once as RawOnce;
+
+/// A cell which is initialized only once, used by generated code to store
+/// [scoped](crate::injectable#scope) bindings.
+///
+/// The value is created by the first call to [`get_or_init()`](#method.get_or_init), and the same
+/// instance is returned by every subsequent call. It is similar to [`std::sync::OnceLock`], and is
+/// also available for users who want to cache values with the same semantics as lockjaw.
+///
+/// ```
+/// # use lockjaw::Once;
+/// let once = Once::new();
+/// assert_eq!(once.try_get(), None);
+/// assert_eq!(*once.get_or_init(|| 42), 42);
+/// // The value is already initialized, and the initializer is not called again.
+/// assert_eq!(*once.get_or_init(|| 0), 42);
+/// assert_eq!(once.try_get(), Some(&42));
+/// ```
+///
+/// # Thread safety
+///
+/// Initialization is synchronized by [`std::sync::Once`]. If multiple threads call
+/// [`get_or_init()`](#method.get_or_init) at the same time, only one initializer runs, and the other
+/// threads block until it finishes. `Once<T>` is `Sync` if `T` is `Send + Sync`.
+///
+/// # Poisoning
+///
+/// If the initializer panics, the `Once` is poisoned, and all subsequent calls to
+/// [`get_or_init()`](#method.get_or_init) panic as well. [`try_get()`](#method.try_get) returns
+/// `None` for a poisoned `Once`.
+///
+/// Calling [`get_or_init()`](#method.get_or_init) on the same `Once` from inside its initializer
+/// deadlocks.
+///
+/// # `no_std`
+///
+/// Without the `std` feature, initialization is synchronized by a spin lock instead, and other
+/// threads spin until the initializer finishes. There is no poisoning; if the initializer panics
+/// with `panic = "unwind"`, subsequent calls spin forever.
 pub struct Once<T> {
-    once: std::sync::Once,
+    once: RawOnce,
     value: UnsafeCell<Option<T>>,
 }
 
+// `value` is only written once inside `once`, and only read after `once` completes.
+unsafe impl<T: Send + Sync> Sync for Once<T> {}
+
 impl<T> Once<T> {
-    pub fn new() -> Self {
+    /// Creates an uninitialized `Once`.
+    pub const fn new() -> Self {
         Once {
-            once: std::sync::Once::new(),
+            once: RawOnce::new(),
             value: UnsafeCell::new(None),
         }
     }
 
-    pub fn get<F>(&self, initializer: F) -> &T
+    /// Returns the value, calling `initializer` to create it if it is not initialized yet.
+    pub fn get_or_init<F>(&self, initializer: F) -> &T
     where
         F: FnOnce() -> T,
     {
@@ -41,4 +84,79 @@ impl<T> Once<T> {
             (&*self.value.get()).as_ref().unwrap()
         }
     }
+
+    #[doc(hidden)]
+    pub fn get<F>(&self, initializer: F) -> &T
+    where
+        F: FnOnce() -> T,
+    {
+        self.get_or_init(initializer)
+    }
+
+    /// Returns the value if it is already initialized.
+    pub fn try_get(&self) -> Option<&T> {
+        if !self.once.is_completed() {
+            return None;
+        }
+        unsafe { (&*self.value.get()).as_ref() }
+    }
+
+    /// Returns the value if it is already initialized.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.value.get_mut().as_mut()
+    }
+
+    /// Consumes the `Once`, returning the value if it is initialized.
+    pub fn into_inner(self) -> Option<T> {
+        self.value.into_inner()
+    }
+}
+
+impl<T> Default for Once<T> {
+    fn default() -> Self {
+        Once::new()
+    }
+}
+
+/// Spin lock based replacement of [`std::sync::Once`] for `no_std`.
+#[cfg(not(feature = "std"))]
+struct RawOnce {
+    state: core::sync::atomic::AtomicU8,
+}
+
+#[cfg(not(feature = "std"))]
+impl RawOnce {
+    const INCOMPLETE: u8 = 0;
+    const RUNNING: u8 = 1;
+    const COMPLETE: u8 = 2;
+
+    const fn new() -> Self {
+        RawOnce {
+            state: core::sync::atomic::AtomicU8::new(Self::INCOMPLETE),
+        }
+    }
+
+    fn call_once<F: FnOnce()>(&self, f: F) {
+        use core::sync::atomic::Ordering;
+        match self.state.compare_exchange(
+            Self::INCOMPLETE,
+            Self::RUNNING,
+            Ordering::Acquire,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => {
+                f();
+                self.state.store(Self::COMPLETE, Ordering::Release);
+            }
+            Err(_) => {
+                while self.state.load(Ordering::Acquire) != Self::COMPLETE {
+                    core::hint::spin_loop();
+                }
+            }
+        }
+    }
+
+    fn is_completed(&self) -> bool {
+        self.state.load(core::sync::atomic::Ordering::Acquire) == Self::COMPLETE
+    }
 }
@@ -0,0 +1,83 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+/// Runs right after the component is constructed, before `build()` returns it.
+///
+/// Listeners are contributed by binding them into `Vec<Cl<dyn PostBuildListener>>` with
+/// [`#[into_vec]`](crate::module_attributes::into_vec). Listeners run in ascending
+/// [`priority()`](PostBuildListener::priority), and listeners with the same priority run in the
+/// order they are bound.
+///
+/// Only [`#[component]`](crate::component) and [`#[define_component]`](crate::define_component)
+/// run listeners. Subcomponents inherit the listeners of their parent, which already ran when the
+/// parent was built.
+///
+/// ```
+/// # use lockjaw::*;
+/// # use std::cell::RefCell;
+/// pub struct Log {
+///     entries: RefCell<Vec<String>>,
+/// }
+///
+/// #[injectable(scope: crate::MyComponent)]
+/// impl Log {
+///     #[inject]
+///     pub fn new() -> Self {
+///         Self {
+///             entries: RefCell::new(Vec::new()),
+///         }
+///     }
+/// }
+///
+/// pub struct MyModule {}
+///
+/// #[module]
+/// impl MyModule {
+///     #[provides]
+///     #[into_vec]
+///     pub fn provide_listener<'a>(log: &'a crate::Log) -> Cl<'a, dyn PostBuildListener> {
+///         Cl::Val(Box::new(move || log.entries.borrow_mut().push("built".to_owned())))
+///     }
+/// }
+///
+/// #[component(modules: [MyModule])]
+/// pub trait MyComponent {
+///     fn log(&self) -> &crate::Log;
+/// }
+///
+/// pub fn main() {
+///     let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+///     assert_eq!(*component.log().entries.borrow(), vec!["built"]);
+/// }
+/// lockjaw::epilogue!();
+/// ```
+pub trait PostBuildListener {
+    fn on_post_build(&self);
+
+    /// Listeners with a lower priority run first.
+    fn priority(&self) -> i32 {
+        0
+    }
+}
+
+impl<F> PostBuildListener for F
+where
+    F: Fn(),
+{
+    fn on_post_build(&self) {
+        self()
+    }
+}
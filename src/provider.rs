@@ -14,6 +14,8 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
+use alloc::boxed::Box;
+
 /// Creates a binding on demand
 ///
 /// `T` will be created each time [`Provider.get()`](#method.get) is called, allowing multiple
@@ -32,9 +34,7 @@ pub struct Provider<'a, T> {
 
 impl<'a, T> Provider<'a, T> {
     pub fn new(f: impl Fn() -> T + 'a) -> Self {
-        Provider {
-            f: std::boxed::Box::new(f),
-        }
+        Provider { f: Box::new(f) }
     }
 
     pub fn get(&self) -> T {
@@ -0,0 +1,61 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+/// Implemented by `dyn Component` for each type returned by its provisions, which can then be
+/// looked up by type with `component.get::<T>()`.
+///
+/// This is an escape hatch for frameworks that need to look up dependencies generically. The set of
+/// types is decided when the component is generated, so requesting a type that is not provisioned is
+/// a compile error rather than a failed lookup at runtime.
+///
+/// Only provisions without parameters returning types without references or lifetimes are
+/// included. Qualified provisions and `impl Trait` provisions are excluded, since their types do not
+/// identify them.
+///
+/// ```
+/// # use lockjaw::*;
+/// # struct StringModule {}
+/// # #[module]
+/// # impl StringModule {
+/// #     #[provides]
+/// #     pub fn provide_string() -> String {
+/// #         "string".to_owned()
+/// #     }
+/// # }
+/// #[component(modules: StringModule)]
+/// pub trait MyComponent {
+///     fn string(&self) -> String;
+/// }
+///
+/// fn lookup<C: ?Sized + Provisioned<String>>(component: &C) -> String {
+///     component.provision()
+/// }
+///
+/// pub fn main() {
+///     let component: Box<dyn MyComponent> = <dyn MyComponent>::new();
+///     assert_eq!(component.get::<String>(), "string");
+///     assert_eq!(lookup(component.as_ref()), "string");
+/// }
+/// epilogue!();
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{T}` is not provisioned by `{Self}`",
+    label = "`{T}` is not provisioned",
+    note = "add a method returning `{T}` to the component"
+)]
+pub trait Provisioned<T> {
+    fn provision(&self) -> T;
+}
@@ -0,0 +1,105 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Request scoped components for async servers, available with the `tokio` feature.
+//!
+//! A server usually builds a [`#[subcomponent]`](crate::subcomponent) for each request, but the
+//! request handlers are invoked by the framework and cannot be injected by the subcomponent
+//! directly. [`scope()`] makes the subcomponent available to everything running in the request's
+//! task, which can then reach back into it with [`current()`].
+//!
+//! Components must be `'static` to be identified. Servers usually keep the root component for the
+//! lifetime of the program, and can leak it at startup so request subcomponents built from it are
+//! `dyn RequestComponent<'static>`.
+//!
+//! ```ignore
+//! # use lockjaw::*;
+//! #[subcomponent(modules: [RequestModule])]
+//! pub trait RequestComponent<'a> {
+//!     fn user(&self) -> crate::User;
+//! }
+//!
+//! async fn handle(app: &'static dyn AppComponent, request: Request) -> Response {
+//!     let component = app.request_component().build(RequestModule { request });
+//!     lockjaw::task_local::scope(&*component, render()).await
+//! }
+//!
+//! async fn render() -> Response {
+//!     let user = lockjaw::current::<dyn RequestComponent<'static>, _>(|c| c.user());
+//!     # unimplemented!()
+//! }
+//! ```
+
+use std::any::Any;
+use std::future::Future;
+use std::sync::Arc;
+
+struct ComponentPtr<C: ?Sized>(*const C);
+
+// SAFETY: the pointer is only dereferenced by `current()` inside the future passed to `scope()`,
+// which is polled by the task holding the `&C` it was created from. The borrow keeps the component
+// alive, and the task can only be sent to another thread if `&C` is `Send`.
+unsafe impl<C: ?Sized> Send for ComponentPtr<C> {}
+unsafe impl<C: ?Sized> Sync for ComponentPtr<C> {}
+
+tokio::task_local! {
+    static COMPONENTS: Vec<Arc<dyn Any + Send + Sync>>;
+}
+
+/// Runs `future` with `component` available through [`current()`].
+///
+/// `component` is usually `dyn` of a [`#[subcomponent]`](crate::subcomponent) trait built for the
+/// request. Scopes can be nested, the components of outer scopes are still available unless
+/// shadowed by a component of the same type.
+///
+/// The component is not available in tasks spawned by `future`, which should enter their own scope.
+pub async fn scope<C: ?Sized + 'static, F: Future>(component: &C, future: F) -> F::Output {
+    let mut components = COMPONENTS
+        .try_with(|components| components.clone())
+        .unwrap_or_default();
+    components.push(Arc::new(ComponentPtr(component as *const C)));
+    COMPONENTS.scope(components, future).await
+}
+
+/// Calls `f` with the innermost component of type `C` entered with [`scope()`] in the current task,
+/// returning `None` if there is none.
+pub fn try_current<C: ?Sized + 'static, R>(f: impl FnOnce(&C) -> R) -> Option<R> {
+    COMPONENTS
+        .try_with(|components| {
+            components
+                .iter()
+                .rev()
+                .find_map(|component| component.downcast_ref::<ComponentPtr<C>>())
+                // SAFETY: see ComponentPtr. `f` cannot leak the reference as `R` does not borrow it.
+                .map(|ptr| f(unsafe { &*ptr.0 }))
+        })
+        .ok()
+        .flatten()
+}
+
+/// Calls `f` with the innermost component of type `C` entered with [`scope()`] in the current task.
+///
+/// # Panics
+///
+/// Panics if there is no such component in the current task.
+pub fn current<C: ?Sized + 'static, R>(f: impl FnOnce(&C) -> R) -> R {
+    try_current(f).unwrap_or_else(|| {
+        panic!(
+            "no {} in the current task, call lockjaw::task_local::scope() first",
+            std::any::type_name::<C>()
+        )
+    })
+}
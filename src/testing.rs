@@ -0,0 +1,98 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! Utilities for writing compile-fail tests against lockjaw diagnostics, and architecture tests
+//! against the binding graph.
+//!
+//! [trybuild](https://crates.io/crates/trybuild) compiles each test file as a standalone crate
+//! without running the build script, so lockjaw is unable to gather the bindings through the
+//! regular [`build_script()`](crate::build_script). Wrapping the path with [`set_src_path()`]
+//! makes [`epilogue!()`](crate::epilogue) parse the bindings directly from the file instead.
+//!
+//! ```ignore
+//! use lockjaw::testing::set_src_path;
+//!
+//! #[test]
+//! fn missing_binding() {
+//!     let t = trybuild::TestCases::new();
+//!     t.compile_fail(set_src_path("tests/compile_fail/missing_binding.rs"));
+//! }
+//! ```
+//!
+//! trybuild only compiles the cases when the `TestCases` is dropped, which reads the path set by
+//! the last `set_src_path()` call. Each `TestCases` should only contain one case, and be dropped
+//! before `set_src_path()` is called again.
+
+/// Env var read by [`epilogue!()`](crate::epilogue) to parse bindings from a single source file.
+pub const TRYBUILD_PATH_ENV: &str = lockjaw_common::environment::LOCKJAW_TRYBUILD_PATH_ENV;
+
+/// Instructs lockjaw to gather bindings from `path` when compiling trybuild test cases.
+///
+/// `path` is relative to the `CARGO_MANIFEST_DIR` of the test crate. Returns `path` unmodified so
+/// it can be passed to trybuild directly.
+pub fn set_src_path(path: &str) -> &str {
+    std::env::set_var(
+        TRYBUILD_PATH_ENV,
+        format!(
+            "{}/{}",
+            std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set"),
+            path
+        ),
+    );
+    path
+}
+
+/// Asserts a component provides a binding, without constructing the component.
+///
+/// ```ignore
+/// assert_binding_exists!(ComponentPath, Type);
+/// assert_binding_exists!(ComponentPath, Type, scope: ScopePath, qualified: QualifierPath);
+/// ```
+///
+/// The graph of the component is resolved at compile time from the test manifest, so large
+/// applications can write architecture tests like "`AppComponent` provides `Database` with scope
+/// `AppComponent`" without building any objects. A failed assertion panics when the test runs.
+///
+/// `use` declarations cannot be resolved by the macro. Paths are instead matched against the end of
+/// the fully qualified path, so `Database` matches `crate::storage::Database`. A longer path is
+/// required if it matches more than one binding. Only bindings of the component itself are checked,
+/// a subcomponent does not see the bindings of its parents.
+///
+/// ```
+/// # use lockjaw::*;
+/// # use lockjaw::testing::assert_binding_exists;
+/// pub struct Database {}
+///
+/// #[injectable(scope: crate::AppComponent)]
+/// impl Database {
+///     #[inject]
+///     pub fn new() -> Database {
+///         Database {}
+///     }
+/// }
+///
+/// #[component]
+/// pub trait AppComponent {
+///     fn database(&self) -> &Database;
+/// }
+///
+/// pub fn main() {
+///     assert_binding_exists!(AppComponent, Database, scope: AppComponent);
+///     assert_binding_exists!(AppComponent, &Database);
+/// }
+/// epilogue!();
+/// ```
+pub use lockjaw_processor::assert_binding_exists;
@@ -0,0 +1,122 @@
+/*
+Copyright 2025 Google LLC
+
+Licensed under the Apache License, Version 2.0 (the "License");
+you may not use this file except in compliance with the License.
+You may obtain a copy of the License at
+
+    https://www.apache.org/licenses/LICENSE-2.0
+
+Unless required by applicable law or agreed to in writing, software
+distributed under the License is distributed on an "AS IS" BASIS,
+WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+See the License for the specific language governing permissions and
+limitations under the License.
+*/
+
+//! [tower](https://docs.rs/tower) integration, available with the `tower` feature.
+//!
+//! [`ComponentLayer`] builds a request scoped component (usually a
+//! [`#[subcomponent]`](crate::subcomponent) of the application component) for each request, and
+//! inserts the dependencies it provides into the request's
+//! [extensions](http::Request::extensions), where [axum](https://docs.rs/axum) handlers can take
+//! them with `Extension<T>`.
+//!
+//! Components are not `Send`, so the component only lives while the dependencies are provided and
+//! is dropped before the request reaches the handler. The dependencies must be
+//! `Clone + Send + Sync + 'static` to be stored in the extensions.
+//!
+//! ```ignore
+//! # use lockjaw::*;
+//! #[subcomponent(builder_modules: RequestModules)]
+//! pub trait RequestComponent<'a> {
+//!     fn user_handler(&self) -> crate::UserHandler;
+//! }
+//!
+//! let app: &'static dyn AppComponent = Box::leak(<dyn AppComponent>::new());
+//! let router = axum::Router::new()
+//!     .route("/user", axum::routing::get(user))
+//!     .layer(lockjaw::tower::ComponentLayer::new(move |request: &http::Request<_>| {
+//!         app.request_component()
+//!             .build(RequestModules::from(request))
+//!             .user_handler()
+//!     }));
+//!
+//! async fn user(Extension(handler): Extension<UserHandler>) -> String {
+//!     handler.user()
+//! }
+//! ```
+
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// [`Layer`](tower_layer::Layer) providing dependencies from a component built for each request.
+///
+/// `provide` is called with each request, and should build the component and return the
+/// dependencies requested from it.
+pub struct ComponentLayer<F> {
+    provide: Arc<F>,
+}
+
+impl<F> ComponentLayer<F> {
+    pub fn new(provide: F) -> Self {
+        ComponentLayer {
+            provide: Arc::new(provide),
+        }
+    }
+}
+
+impl<F> Clone for ComponentLayer<F> {
+    fn clone(&self) -> Self {
+        ComponentLayer {
+            provide: self.provide.clone(),
+        }
+    }
+}
+
+impl<S, F> tower_layer::Layer<S> for ComponentLayer<F> {
+    type Service = ComponentService<S, F>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ComponentService {
+            inner,
+            provide: self.provide.clone(),
+        }
+    }
+}
+
+/// [`Service`](tower_service::Service) created by [`ComponentLayer`].
+pub struct ComponentService<S, F> {
+    inner: S,
+    provide: Arc<F>,
+}
+
+impl<S: Clone, F> Clone for ComponentService<S, F> {
+    fn clone(&self) -> Self {
+        ComponentService {
+            inner: self.inner.clone(),
+            provide: self.provide.clone(),
+        }
+    }
+}
+
+impl<S, F, B, D> tower_service::Service<http::Request<B>> for ComponentService<S, F>
+where
+    S: tower_service::Service<http::Request<B>>,
+    F: Fn(&http::Request<B>) -> D,
+    D: Clone + Send + Sync + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: http::Request<B>) -> Self::Future {
+        let dependencies = (self.provide)(&request);
+        request.extensions_mut().insert(dependencies);
+        self.inner.call(request)
+    }
+}